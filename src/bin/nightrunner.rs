@@ -0,0 +1,220 @@
+//! Command line runner for nightrunner games.
+//!
+//! Build it with `cargo build --features cli`, then run
+//! `nightrunner help` to see what it can do.
+
+use std::io::{self, BufRead, Write};
+use std::path::Path;
+use std::process::ExitCode;
+
+use nightrunner_lib::config::{Config, ConfigFormat};
+use nightrunner_lib::testing::Walkthrough;
+use nightrunner_lib::{NRResult, NightRunnerBuilder, ParsingResult};
+
+const USAGE: &str = "\
+Usage: nightrunner <command> [arguments]
+
+Commands:
+  play <game> [--seed <seed>]       play a game in the terminal
+  validate <game>                   look for problems in a game
+  walkthrough <script>...           run walkthrough scripts
+  export <game> [--output <path>]   convert a game to JSON, or to YAML or
+                                    TOML when the output is a .yml or .toml
+                                    file, or to YAML files in a folder
+  schema [--output <folder>]        print a JSON Schema for game files, or
+                                    write schemas for game files and each
+                                    YAML file of a game folder to a folder
+  help                              show this message
+
+A game is a folder with the game's YAML files, or a JSON, YAML or TOML
+file.";
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let result = match args.first().map(|command| command.as_str()) {
+        Some("play") => play(&args[1..]),
+        Some("validate") => validate(&args[1..]),
+        Some("walkthrough") => walkthrough(&args[1..]),
+        Some("export") => export(&args[1..]),
+        Some("schema") => schema(&args[1..]),
+        Some("help") | Some("--help") | Some("-h") => {
+            println!("{}", USAGE);
+            Ok(true)
+        }
+        _ => {
+            eprintln!("{}", USAGE);
+            Ok(false)
+        }
+    };
+    match result {
+        Ok(true) => ExitCode::SUCCESS,
+        Ok(false) => ExitCode::FAILURE,
+        Err(error) => {
+            eprintln!("error: {}", error);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// Splits the arguments into positional arguments and the
+/// value of an option, like `--seed 3`.
+fn split_option(args: &[String], option: &str) -> NRResult<(Vec<String>, Option<String>)> {
+    let mut positional = vec![];
+    let mut value = None;
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        if arg == option {
+            let option_value = args
+                .next()
+                .ok_or_else(|| format!("{} needs a value", option))?;
+            value = Some(option_value.clone());
+        } else if arg.starts_with("--") {
+            return Err(format!("unknown option {}", arg).into());
+        } else {
+            positional.push(arg.clone());
+        }
+    }
+    Ok((positional, value))
+}
+
+fn game_path(args: &[String]) -> NRResult<String> {
+    match args {
+        [game] => Ok(game.clone()),
+        _ => Err(format!("expected one game\n\n{}", USAGE).into()),
+    }
+}
+
+/// Loads a game from a folder of YAML files, or a JSON,
+/// YAML or TOML file.
+fn load_game(game: &str) -> NRResult<Config> {
+    let path = Path::new(game);
+    if path.is_dir() {
+        Config::try_from_path(game)
+    } else if path.is_file() {
+        Config::from_file(path)
+    } else {
+        Err(format!("could not find a game at {}", game).into())
+    }
+}
+
+fn play(args: &[String]) -> NRResult<bool> {
+    let (args, seed) = split_option(args, "--seed")?;
+    let game = game_path(&args)?;
+    let config = load_game(&game)?;
+    let mut builder = NightRunnerBuilder::new();
+    if let Some(seed) = seed {
+        builder = builder.with_seed(seed.parse()?);
+    }
+    for migration in &config.migrations {
+        eprintln!("warning: {}", migration);
+    }
+    let mut night_runner = builder.with_config(config).build();
+    let intro = night_runner.game_intro();
+    if !intro.is_empty() {
+        println!("{}\n", intro);
+    }
+    println!("{}", night_runner.first_room_text()?.message.trim());
+    let stdin = io::stdin();
+    let mut lines = stdin.lock().lines();
+    loop {
+        print!("\n> ");
+        io::stdout().flush()?;
+        let input = match lines.next() {
+            Some(line) => line?,
+            None => return Ok(true),
+        };
+        if input.trim().is_empty() {
+            continue;
+        }
+        let result = night_runner.parse_input(input.trim());
+        if let Ok(ParsingResult::Quit) = result {
+            return Ok(true);
+        }
+        let output = night_runner
+            .transcript()
+            .entries
+            .last()
+            .map(|entry| entry.outcome.text())
+            .unwrap_or_default();
+        println!("{}", output.trim());
+    }
+}
+
+fn validate(args: &[String]) -> NRResult<bool> {
+    let game = game_path(args)?;
+    let config = load_game(&game)?;
+    let warnings = if Path::new(&game).is_dir() {
+        Config::lint_path(&game)?
+    } else if ConfigFormat::from_path(&game) == Some(ConfigFormat::Json) {
+        Config::lint_json(&std::fs::read_to_string(&game)?)?
+    } else {
+        config.lint()
+    };
+    for migration in &config.migrations {
+        println!("warning: {}", migration);
+    }
+    for warning in &warnings {
+        println!("warning: {}", warning);
+    }
+    let report = config.check_solvability();
+    println!("{}", report);
+    Ok(report.is_solvable())
+}
+
+fn walkthrough(args: &[String]) -> NRResult<bool> {
+    if args.is_empty() {
+        return Err(format!("expected at least one script\n\n{}", USAGE).into());
+    }
+    let mut passed = true;
+    for script in args {
+        let walkthrough = Walkthrough::from_file(script)?;
+        match walkthrough.run(walkthrough.load_game()?) {
+            Ok(()) => println!("{}: ok", script),
+            Err(failure) => {
+                println!("{}: {}", script, failure);
+                passed = false;
+            }
+        }
+    }
+    Ok(passed)
+}
+
+fn export(args: &[String]) -> NRResult<bool> {
+    let (args, output) = split_option(args, "--output")?;
+    let config = load_game(&game_path(&args)?)?;
+    match output {
+        Some(output) if output.ends_with('/') || Path::new(&output).is_dir() => {
+            config.to_yaml_dir(&output)?
+        }
+        Some(output) => match ConfigFormat::from_path(&output) {
+            Some(ConfigFormat::Yaml) => std::fs::write(output, config.to_yaml())?,
+            Some(ConfigFormat::Toml) => std::fs::write(output, config.to_toml())?,
+            _ => std::fs::write(output, config.to_json())?,
+        },
+        None => println!("{}", config.to_json()),
+    }
+    Ok(true)
+}
+
+fn schema(args: &[String]) -> NRResult<bool> {
+    let (args, output) = split_option(args, "--output")?;
+    if !args.is_empty() {
+        return Err(format!("unexpected arguments\n\n{}", USAGE).into());
+    }
+    let game_schema = serde_json::to_string_pretty(&Config::json_schema())?;
+    let output = match output {
+        Some(output) => output,
+        None => {
+            println!("{}", game_schema);
+            return Ok(true);
+        }
+    };
+    let folder = Path::new(&output);
+    std::fs::create_dir_all(folder)?;
+    std::fs::write(folder.join("game.schema.json"), game_schema)?;
+    for (file, schema) in Config::yaml_file_schemas() {
+        let name = file.replace(".yml", ".schema.json");
+        std::fs::write(folder.join(name), serde_json::to_string_pretty(&schema)?)?;
+    }
+    Ok(true)
+}
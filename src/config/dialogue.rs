@@ -0,0 +1,108 @@
+use serde::{Deserialize, Serialize};
+
+use super::{Condition, Effect};
+
+/// A conversation tree for a subject, started when the
+/// player talks to the subject and no event handles it.
+///
+/// # Examples:
+/// ```rust
+/// # use nightrunner_lib::config::Dialogue;
+/// let yaml = r#"
+/// start: 1
+/// nodes:
+///   - id: 1
+///     text: Hello there, stranger.
+///     choices:
+///       - text: Who are you?
+///         next: 2
+///       - text: I found your key.
+///         conditions:
+///           - type: has_item
+///             item_id: 3
+///         effects:
+///           - type: remove_item
+///             item_id: 3
+///         next: 3
+///       - text: Goodbye.
+///   - id: 2
+///     text: Just a humble shopkeeper.
+///     choices:
+///       - text: Goodbye.
+///   - id: 3
+///     text: Thank you! Take this.
+///     effects:
+///       - type: add_item
+///         item_id: 4
+/// "#;
+/// let dialogue: Dialogue = serde_yaml::from_str(yaml).unwrap();
+/// assert_eq!(dialogue.nodes.len(), 3);
+/// ```
+#[derive(Debug, Clone, Eq, Ord, PartialEq, PartialOrd, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
+pub struct Dialogue {
+    /// The id of the node the conversation starts with.
+    pub start: u16,
+    /// All the nodes in the conversation.
+    pub nodes: Vec<DialogueNode>,
+}
+
+impl Dialogue {
+    /// Returns the node with the given id.
+    pub fn node(&self, node_id: u16) -> Option<&DialogueNode> {
+        self.nodes.iter().find(|node| node.id == node_id)
+    }
+}
+
+/// A line said by the subject, and the answers the
+/// player can pick from. A node without available
+/// choices ends the conversation.
+#[derive(Debug, Clone, Eq, Ord, PartialEq, PartialOrd, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
+pub struct DialogueNode {
+    /// The id of the node, used by choices to point to it.
+    pub id: u16,
+    /// What the subject says.
+    pub text: String,
+    /// Effects applied when the conversation reaches this node.
+    #[serde(default)]
+    pub effects: Vec<Effect>,
+    /// The answers the player can pick from.
+    #[serde(default)]
+    pub choices: Vec<DialogueChoice>,
+}
+
+/// An answer the player can pick in a conversation.
+#[derive(Debug, Clone, Eq, Ord, PartialEq, PartialOrd, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
+pub struct DialogueChoice {
+    /// What the player says.
+    pub text: String,
+    /// The choice is only offered when all of these are met.
+    #[serde(default)]
+    pub conditions: Vec<Condition>,
+    /// Effects applied when the player picks this choice.
+    #[serde(default)]
+    pub effects: Vec<Effect>,
+    /// The node the conversation continues with. When this
+    /// isn't set, picking the choice ends the conversation.
+    #[serde(default)]
+    pub next: Option<u16>,
+}
+
+/// The conversation the player is currently having.
+#[derive(Debug, Clone, Eq, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub struct ActiveDialogue {
+    /// The subject the player is talking to.
+    pub subject_id: u16,
+    /// The node the conversation is at.
+    pub node_id: u16,
+    /// The positions in the node's choices of the answers
+    /// offered to the player, in the order they were shown.
+    #[serde(default)]
+    pub choices: Vec<usize>,
+}
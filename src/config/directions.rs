@@ -24,6 +24,7 @@ impl AllowedDirections {
 /// so inputs like "climb down" or "go
 /// left" will be supported.
 #[derive(Clone, Debug, Deserialize, Serialize, Eq, Ord, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "snake_case")]
 pub enum Directions {
     #[serde(rename = "east")]
@@ -0,0 +1,108 @@
+use std::path::Path;
+
+use serde::Serialize;
+
+use super::{Config, ConfigData, PlayerStats, Settings, FORMAT_VERSION};
+use crate::NRResult;
+
+impl Config {
+    /// Returns the game data as JSON, in the layout read by
+    /// `Config::from_json`.
+    ///
+    /// # Examples:
+    /// ```rust
+    /// # use nightrunner_lib::config::Config;
+    /// let config = Config::from_path("fixtures/");
+    /// assert_eq!(Config::from_json(&config.to_json()), config);
+    /// ```
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(&self.to_data()).unwrap()
+    }
+    /// Returns the game data as a single YAML document, in
+    /// the layout read by `Config::from_yaml`.
+    pub fn to_yaml(&self) -> String {
+        serde_yaml::to_string(&self.to_data()).unwrap()
+    }
+    /// Returns the game data as a single TOML document, in
+    /// the layout read by `Config::from_toml`.
+    ///
+    /// # Examples:
+    /// ```rust
+    /// # use nightrunner_lib::config::Config;
+    /// let config = Config::from_path("fixtures/");
+    /// assert_eq!(Config::from_toml(&config.to_toml()), config);
+    /// ```
+    pub fn to_toml(&self) -> String {
+        toml::to_string(&self.to_data()).unwrap()
+    }
+    /// Writes the game data to YAML files in the path, in
+    /// the layout read by `Config::from_path`. The optional
+    /// files are only written when they differ from their
+    /// defaults, except for `format_version.yml`, which is
+    /// always written.
+    ///
+    /// Symbolic ids are resolved when a game is loaded, so
+    /// like the other exports the files use numeric ids, and
+    /// a game written with symbolic ids won't get them back.
+    ///
+    /// # Examples:
+    /// ```rust
+    /// # use nightrunner_lib::config::Config;
+    /// # let folder = std::env::temp_dir().join("nightrunner_to_yaml_dir_doc/");
+    /// # let path = folder.to_str().unwrap();
+    /// let config = Config::from_path("fixtures/");
+    /// config.to_yaml_dir(path).unwrap();
+    /// assert_eq!(Config::from_path(path), config);
+    /// # std::fs::remove_dir_all(path).unwrap();
+    /// ```
+    pub fn to_yaml_dir(&self, path: &str) -> NRResult<()> {
+        std::fs::create_dir_all(path)?;
+        let folder = Path::new(path);
+        write_yaml(folder, "format_version.yml", &FORMAT_VERSION)?;
+        write_yaml(folder, "narratives.yml", &self.narratives)?;
+        write_yaml(folder, "items.yml", &self.items)?;
+        write_yaml(folder, "rooms.yml", &self.room_blueprints)?;
+        write_yaml(folder, "verbs.yml", &self.allowed_verbs)?;
+        write_yaml(folder, "subjects.yml", &self.subjects)?;
+        write_yaml(folder, "events.yml", &self.events)?;
+        write_yaml(folder, "intro.yml", &self.intro)?;
+        if self.settings != Settings::default() {
+            write_yaml(folder, "settings.yml", &self.settings)?;
+        }
+        if !self.daemons.is_empty() {
+            write_yaml(folder, "daemons.yml", &self.daemons)?;
+        }
+        if !self.variables.is_empty() {
+            write_yaml(folder, "variables.yml", &self.variables)?;
+        }
+        if self.stats != PlayerStats::default() {
+            write_yaml(folder, "stats.yml", &self.stats)?;
+        }
+        Ok(())
+    }
+    fn to_data(&self) -> ConfigData {
+        ConfigData {
+            format_version: FORMAT_VERSION,
+            items: self.items.clone(),
+            narratives: self.narratives.clone(),
+            room_blueprints: self.room_blueprints.clone(),
+            subjects: self.subjects.clone(),
+            events: self.events.clone(),
+            intro: self.intro.clone(),
+            allowed_verbs: self.allowed_verbs.clone(),
+            settings: self.settings.clone(),
+            daemons: self.daemons.clone(),
+            variables: self.variables.clone(),
+            stats: self.stats.clone(),
+        }
+    }
+}
+
+fn write_yaml<T: Serialize + ?Sized>(folder: &Path, file_name: &str, data: &T) -> NRResult<()> {
+    std::fs::write(folder.join(file_name), serde_yaml::to_string(data)?)?;
+    Ok(())
+}
+
+#[cfg(test)]
+#[path = "export_tests.rs"]
+mod export_tests;
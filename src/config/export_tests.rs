@@ -0,0 +1,82 @@
+use super::*;
+use crate::config::{Condition, Daemon, Effect};
+#[cfg(test)]
+use pretty_assertions::assert_eq;
+
+fn temp_folder(name: &str) -> String {
+    let folder = std::env::temp_dir().join(format!("nightrunner_{}_{}", name, std::process::id()));
+    format!("{}/", folder.to_string_lossy())
+}
+
+/// The fixtures with every optional part of the game data set.
+fn full_config() -> Config {
+    let mut config = Config::from_path("fixtures/");
+    config.settings.informational_commands_take_turn = false;
+    config.daemons.push(Daemon {
+        id: 1,
+        name: "clock".to_string(),
+        conditions: vec![Condition::EveryTurns { turns: 3 }],
+        effects: vec![Effect::Message {
+            text: "The clock chimes.".to_string(),
+        }],
+    });
+    config.variables.insert("chimes".to_string(), 2);
+    config.stats.health = Some(10);
+    config.stats.custom.insert("strength".to_string(), 3);
+    config
+}
+
+#[test]
+fn it_round_trips_json() {
+    let config = full_config();
+    let json = config.to_json();
+    assert_eq!(Config::from_json(&json), config);
+    // the JSON has the same layout as the data read by from_json
+    let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+    assert!(value["room_blueprints"].is_array());
+    assert!(value.get("allowed_prepositions").is_none());
+}
+
+#[test]
+fn it_round_trips_a_single_yaml_file() {
+    let config = full_config();
+    assert_eq!(Config::from_yaml(&config.to_yaml()), config);
+    assert_eq!(
+        Config::from_yaml(&config.to_yaml()).to_json(),
+        config.to_json()
+    );
+}
+
+#[test]
+fn it_round_trips_a_yaml_folder() {
+    let folder = temp_folder("export_full");
+    let config = full_config();
+    config.to_yaml_dir(&folder).unwrap();
+    assert_eq!(Config::from_path(&folder), config);
+    std::fs::remove_dir_all(&folder).unwrap();
+}
+
+#[test]
+fn it_only_writes_optional_files_when_needed() {
+    let folder = temp_folder("export_plain");
+    Config::from_path("fixtures/").to_yaml_dir(&folder).unwrap();
+    let mut files: Vec<String> = std::fs::read_dir(&folder)
+        .unwrap()
+        .map(|entry| entry.unwrap().file_name().to_string_lossy().to_string())
+        .collect();
+    files.sort();
+    assert_eq!(
+        files,
+        vec![
+            "events.yml",
+            "format_version.yml",
+            "intro.yml",
+            "items.yml",
+            "narratives.yml",
+            "rooms.yml",
+            "subjects.yml",
+            "verbs.yml"
+        ]
+    );
+    std::fs::remove_dir_all(&folder).unwrap();
+}
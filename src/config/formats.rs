@@ -0,0 +1,223 @@
+use std::path::{Path, PathBuf};
+
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use serde_json::{json, Map, Value};
+
+use super::Config;
+use crate::parser::errors::{
+    ConflictingFormatVersions, DuplicateSection, IncludeCycle, UnknownConfigFormat,
+};
+use crate::NRResult;
+
+/// The file formats a whole game can be written in. They
+/// all share the layout of the JSON data.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConfigFormat {
+    /// A `.json` file.
+    Json,
+    /// A `.yml` or `.yaml` file.
+    Yaml,
+    /// A `.toml` file.
+    Toml,
+}
+
+impl ConfigFormat {
+    /// Picks the format from the extension of the file.
+    ///
+    /// # Examples:
+    /// ```rust
+    /// # use nightrunner_lib::config::ConfigFormat;
+    /// assert_eq!(ConfigFormat::from_path("game.toml"), Some(ConfigFormat::Toml));
+    /// assert_eq!(ConfigFormat::from_path("game.yaml"), Some(ConfigFormat::Yaml));
+    /// assert_eq!(ConfigFormat::from_path("game.txt"), None);
+    /// ```
+    pub fn from_path(path: impl AsRef<Path>) -> Option<ConfigFormat> {
+        match path.as_ref().extension()?.to_str()? {
+            "json" => Some(ConfigFormat::Json),
+            "yml" | "yaml" => Some(ConfigFormat::Yaml),
+            "toml" => Some(ConfigFormat::Toml),
+            _ => None,
+        }
+    }
+    pub(crate) fn parse<T: DeserializeOwned>(self, data: &str) -> NRResult<T> {
+        Ok(match self {
+            ConfigFormat::Json => serde_json::from_str(data)?,
+            ConfigFormat::Yaml => serde_yaml::from_str(data)?,
+            ConfigFormat::Toml => toml::from_str(data)?,
+        })
+    }
+}
+
+/// One game file. Every section is optional, since a file
+/// can leave some of them to the files it includes. The
+/// entries are only read into the config once every file is
+/// merged, so they can reference symbolic ids from other
+/// files. With the `schema` feature, the schema of each
+/// section is the one of the entries it's read into.
+#[derive(Debug, Default, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(default)]
+pub(super) struct GameFile {
+    #[cfg_attr(feature = "schema", schemars(with = "Option<u32>"))]
+    format_version: Option<Value>,
+    include: Vec<String>,
+    #[cfg_attr(feature = "schema", schemars(with = "Vec<super::Item>"))]
+    items: Vec<Value>,
+    #[cfg_attr(feature = "schema", schemars(with = "Vec<super::Narrative>"))]
+    narratives: Vec<Value>,
+    #[cfg_attr(
+        feature = "schema",
+        schemars(with = "Vec<super::rooms::RoomBlueprint>")
+    )]
+    room_blueprints: Vec<Value>,
+    #[cfg_attr(feature = "schema", schemars(with = "Vec<super::Subject>"))]
+    subjects: Vec<Value>,
+    #[cfg_attr(feature = "schema", schemars(with = "Vec<super::Event>"))]
+    events: Vec<Value>,
+    #[cfg_attr(feature = "schema", schemars(with = "Option<String>"))]
+    intro: Option<Value>,
+    #[cfg_attr(feature = "schema", schemars(with = "Vec<super::Verb>"))]
+    allowed_verbs: Vec<Value>,
+    #[cfg_attr(feature = "schema", schemars(with = "Option<super::Settings>"))]
+    settings: Option<Value>,
+    #[cfg_attr(feature = "schema", schemars(with = "Vec<super::Daemon>"))]
+    daemons: Vec<Value>,
+    #[cfg_attr(
+        feature = "schema",
+        schemars(with = "std::collections::BTreeMap<String, i32>")
+    )]
+    variables: Map<String, Value>,
+    #[cfg_attr(feature = "schema", schemars(with = "Option<super::PlayerStats>"))]
+    stats: Option<Value>,
+}
+
+impl GameFile {
+    /// Adds the sections of an included file to this one.
+    fn merge(&mut self, other: GameFile) -> NRResult<()> {
+        self.items.extend(other.items);
+        self.narratives.extend(other.narratives);
+        self.room_blueprints.extend(other.room_blueprints);
+        self.subjects.extend(other.subjects);
+        self.events.extend(other.events);
+        self.allowed_verbs.extend(other.allowed_verbs);
+        self.daemons.extend(other.daemons);
+        self.variables.extend(other.variables);
+        merge_once(&mut self.intro, other.intro)?;
+        merge_once(&mut self.settings, other.settings)?;
+        merge_once(&mut self.stats, other.stats)?;
+        match (&self.format_version, other.format_version) {
+            (Some(version), Some(other)) if *version != other => {
+                return Err(ConflictingFormatVersions.into())
+            }
+            (None, other) => self.format_version = other,
+            _ => {}
+        }
+        Ok(())
+    }
+    /// Returns the game data in the layout of the JSON data.
+    fn into_value(self) -> Value {
+        let mut data = json!({
+            "items": self.items,
+            "narratives": self.narratives,
+            "room_blueprints": self.room_blueprints,
+            "subjects": self.subjects,
+            "events": self.events,
+            "intro": self.intro.unwrap_or_else(|| json!("")),
+            "allowed_verbs": self.allowed_verbs,
+            "daemons": self.daemons,
+            "variables": self.variables,
+        });
+        if let Some(settings) = self.settings {
+            data["settings"] = settings;
+        }
+        if let Some(stats) = self.stats {
+            data["stats"] = stats;
+        }
+        if let Some(format_version) = self.format_version {
+            data["format_version"] = format_version;
+        }
+        data
+    }
+}
+
+fn merge_once(section: &mut Option<Value>, other: Option<Value>) -> NRResult<()> {
+    match (&section, other) {
+        (Some(_), Some(_)) => Err(DuplicateSection.into()),
+        (None, Some(other)) => {
+            *section = Some(other);
+            Ok(())
+        }
+        (_, None) => Ok(()),
+    }
+}
+
+/// Reads a game file and the files it includes. `parents`
+/// holds the files that included this one, to catch files
+/// that include themselves.
+fn read_game_file(path: &Path, parents: &mut Vec<PathBuf>) -> NRResult<GameFile> {
+    let format = ConfigFormat::from_path(path).ok_or(UnknownConfigFormat)?;
+    let canonical = path.canonicalize()?;
+    if parents.contains(&canonical) {
+        return Err(IncludeCycle.into());
+    }
+    let mut game_file: GameFile = format.parse(&std::fs::read_to_string(path)?)?;
+    let folder = path.parent().unwrap_or_else(|| Path::new(""));
+    parents.push(canonical);
+    for include in std::mem::take(&mut game_file.include) {
+        let included = read_game_file(&folder.join(include), parents)?;
+        game_file.merge(included)?;
+    }
+    parents.pop();
+    Ok(game_file)
+}
+
+impl Config {
+    /// Loads config from a single TOML document with the
+    /// same layout as the JSON data, as written by
+    /// `Config::to_toml`.
+    ///
+    /// ## Example:
+    /// ```rust
+    /// # use nightrunner_lib::config::Config;
+    /// let data = std::fs::read_to_string("fixtures/single_file/game.toml").unwrap();
+    /// let config = Config::from_toml(&data);
+    /// assert_eq!(config, Config::from_path("fixtures/"));
+    /// ```
+    pub fn from_toml(data: &str) -> Config {
+        Config::from_value(toml::from_str(data).unwrap()).unwrap()
+    }
+    /// Loads the whole game from one JSON, YAML or TOML
+    /// file, picking the format from the file extension.
+    ///
+    /// The file has the same layout as the JSON data, and
+    /// can list other game files to include, relative to
+    /// itself, so a big game can be split into chapters:
+    ///
+    /// ```yaml
+    /// intro: A long night.
+    /// include:
+    ///   - chapters/chapter_1.yml
+    ///   - chapters/chapter_2.toml
+    /// ```
+    ///
+    /// The lists in included files are added to the ones in
+    /// the including file, and can reference the symbolic ids
+    /// of entries in any of the files. The intro, settings and
+    /// stats can only be set in one of the files.
+    ///
+    /// ## Example:
+    /// ```rust
+    /// # use nightrunner_lib::config::Config;
+    /// let config = Config::from_file("fixtures/single_file/game.yml").unwrap();
+    /// assert_eq!(config, Config::from_path("fixtures/"));
+    /// ```
+    pub fn from_file(path: impl AsRef<Path>) -> NRResult<Config> {
+        let game_file = read_game_file(path.as_ref(), &mut vec![])?;
+        Config::from_value(game_file.into_value())
+    }
+}
+
+#[cfg(test)]
+#[path = "formats_tests.rs"]
+mod formats_tests;
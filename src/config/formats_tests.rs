@@ -0,0 +1,199 @@
+use super::*;
+use crate::config::Config;
+use crate::parser::errors::UnsupportedFormatVersion;
+#[cfg(test)]
+use pretty_assertions::assert_eq;
+
+fn temp_folder(name: &str) -> PathBuf {
+    let folder = std::env::temp_dir().join(format!("nightrunner_{}_{}", name, std::process::id()));
+    std::fs::create_dir_all(&folder).unwrap();
+    folder
+}
+
+#[test]
+fn it_picks_the_format_from_the_extension() {
+    assert_eq!(
+        ConfigFormat::from_path("game.json"),
+        Some(ConfigFormat::Json)
+    );
+    assert_eq!(
+        ConfigFormat::from_path("game.yml"),
+        Some(ConfigFormat::Yaml)
+    );
+    assert_eq!(
+        ConfigFormat::from_path("chapters/one.yaml"),
+        Some(ConfigFormat::Yaml)
+    );
+    assert_eq!(
+        ConfigFormat::from_path("game.toml"),
+        Some(ConfigFormat::Toml)
+    );
+    assert_eq!(ConfigFormat::from_path("fixtures/"), None);
+}
+
+#[test]
+fn it_loads_a_yaml_file_split_into_chapters() {
+    let config = Config::from_file("fixtures/single_file/game.yml").unwrap();
+    assert_eq!(config, Config::from_path("fixtures/"));
+}
+
+#[test]
+fn it_loads_a_toml_file() {
+    let config = Config::from_file("fixtures/single_file/game.toml").unwrap();
+    assert_eq!(config, Config::from_path("fixtures/"));
+}
+
+#[test]
+fn it_round_trips_toml() {
+    let mut config = Config::from_path("fixtures/");
+    config.variables.insert("chimes".to_string(), 2);
+    config.stats.health = Some(10);
+    assert_eq!(Config::from_toml(&config.to_toml()), config);
+}
+
+#[test]
+fn it_loads_a_json_file() {
+    let folder = temp_folder("formats_json");
+    let path = folder.join("game.json");
+    let config = Config::from_path("fixtures/");
+    std::fs::write(&path, config.to_json()).unwrap();
+    assert_eq!(Config::from_file(&path).unwrap(), config);
+    std::fs::remove_dir_all(folder).unwrap();
+}
+
+#[test]
+fn it_includes_files_in_other_formats() {
+    let folder = temp_folder("formats_mixed");
+    std::fs::write(
+        folder.join("game.yml"),
+        "intro: A long night.\ninclude: [rooms.toml]\n",
+    )
+    .unwrap();
+    std::fs::write(
+        folder.join("rooms.toml"),
+        "[[room_blueprints]]\nid = 1\nname = \"hall\"\ndescription = \"a hall\"\n\
+         exits = []\nitem_ids = []\nnarrative = 1\nsubject_ids = []\n",
+    )
+    .unwrap();
+    let config = Config::from_file(folder.join("game.yml")).unwrap();
+    assert_eq!(config.intro, "A long night.");
+    assert_eq!(config.room_blueprints.len(), 1);
+    assert_eq!(config.room_blueprints[0].name, "hall");
+    std::fs::remove_dir_all(folder).unwrap();
+}
+
+#[test]
+fn it_rejects_unknown_formats() {
+    let error = Config::from_file("fixtures/single_file/game.txt").unwrap_err();
+    assert_eq!(error.to_string(), UnknownConfigFormat.to_string());
+}
+
+#[test]
+fn it_rejects_files_that_include_themselves() {
+    let folder = temp_folder("formats_cycle");
+    std::fs::write(folder.join("game.yml"), "include: [chapter.yml]\n").unwrap();
+    std::fs::write(folder.join("chapter.yml"), "include: [game.yml]\n").unwrap();
+    let error = Config::from_file(folder.join("game.yml")).unwrap_err();
+    assert_eq!(error.to_string(), IncludeCycle.to_string());
+    std::fs::remove_dir_all(folder).unwrap();
+}
+
+#[test]
+fn it_rejects_an_intro_set_twice() {
+    let folder = temp_folder("formats_duplicate");
+    std::fs::write(
+        folder.join("game.yml"),
+        "intro: one\ninclude: [chapter.yml]\n",
+    )
+    .unwrap();
+    std::fs::write(folder.join("chapter.yml"), "intro: two\n").unwrap();
+    let error = Config::from_file(folder.join("game.yml")).unwrap_err();
+    assert_eq!(error.to_string(), DuplicateSection.to_string());
+    std::fs::remove_dir_all(folder).unwrap();
+}
+
+/// Copies the single file fixtures to a temporary folder,
+/// with `game.yml` changed by `edit`.
+fn single_file_game(name: &str, edit: impl Fn(String) -> String) -> PathBuf {
+    let folder = temp_folder(name);
+    std::fs::create_dir_all(folder.join("chapters")).unwrap();
+    for chapter in ["chapters/chapter_1.yml", "chapters/chapter_2.yml"] {
+        std::fs::copy(
+            Path::new("fixtures/single_file").join(chapter),
+            folder.join(chapter),
+        )
+        .unwrap();
+    }
+    let game = std::fs::read_to_string("fixtures/single_file/game.yml").unwrap();
+    std::fs::write(folder.join("game.yml"), edit(game)).unwrap();
+    folder
+}
+
+#[test]
+fn it_reads_the_format_version() {
+    let folder = single_file_game("formats_version", |game| {
+        game.replace("format_version: 2", "format_version: 99")
+    });
+    let error = Config::from_file(folder.join("game.yml")).unwrap_err();
+    assert_eq!(error.to_string(), UnsupportedFormatVersion.to_string());
+    std::fs::remove_dir_all(folder).unwrap();
+
+    // a game at the current version isn't migrated again,
+    // even when it has fields older versions used
+    let folder = single_file_game("formats_current", |game| game);
+    let chapter = folder.join("chapters/chapter_1.yml");
+    let data = std::fs::read_to_string(&chapter).unwrap();
+    std::fs::write(
+        &chapter,
+        data.replacen(
+            "    name: room 1\n",
+            "    name: room 1\n    room_events: [1]\n",
+            1,
+        ),
+    )
+    .unwrap();
+    let config = Config::from_file(folder.join("game.yml")).unwrap();
+    assert!(config.migrations.is_empty());
+    let game = std::fs::read_to_string(folder.join("game.yml")).unwrap();
+    std::fs::write(
+        folder.join("game.yml"),
+        game.replace("format_version: 2\n", ""),
+    )
+    .unwrap();
+    let config = Config::from_file(folder.join("game.yml")).unwrap();
+    assert_eq!(config.migrations.len(), 1);
+    std::fs::remove_dir_all(folder).unwrap();
+}
+
+#[test]
+fn it_rejects_different_format_versions() {
+    let folder = single_file_game("formats_conflict", |game| game);
+    let chapter = folder.join("chapters/chapter_1.yml");
+    let data = std::fs::read_to_string(&chapter).unwrap();
+    std::fs::write(&chapter, data.replace("---\n", "---\nformat_version: 1\n")).unwrap();
+    let error = Config::from_file(folder.join("game.yml")).unwrap_err();
+    assert_eq!(error.to_string(), ConflictingFormatVersions.to_string());
+    std::fs::write(&chapter, data.replace("---\n", "---\nformat_version: 2\n")).unwrap();
+    assert!(Config::from_file(folder.join("game.yml")).is_ok());
+    std::fs::remove_dir_all(folder).unwrap();
+}
+
+#[test]
+fn it_resolves_symbols_across_included_files() {
+    let folder = temp_folder("formats_symbols");
+    std::fs::write(
+        folder.join("game.yml"),
+        "include: [chapter.toml]\nnarratives:\n  - id: hall\n    text: a hall\n    description: hall\n",
+    )
+    .unwrap();
+    std::fs::write(
+        folder.join("chapter.toml"),
+        "[[room_blueprints]]\nid = \"hall\"\nname = \"hall\"\ndescription = \"a hall\"\n\
+         exits = []\nitem_ids = []\nnarrative = \"hall\"\nsubject_ids = []\n",
+    )
+    .unwrap();
+    let config = Config::from_file(folder.join("game.yml")).unwrap();
+    assert_eq!(config.room_blueprints[0].id, 1);
+    assert_eq!(config.room_blueprints[0].narrative, config.narratives[0].id);
+    std::fs::remove_dir_all(folder).unwrap();
+}
@@ -0,0 +1,559 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use super::symbols::Symbols;
+use super::{Config, Effect, SubjectLocation, VerbFunction, YamlSections};
+use crate::NRResult;
+
+/// The parts of a game's data that hold entries with ids.
+#[derive(Debug, Clone, Copy, Eq, Ord, PartialEq, PartialOrd, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ConfigSection {
+    /// `narratives.yml`, or `narratives` in JSON data.
+    Narratives,
+    /// `items.yml`, or `items` in JSON data.
+    Items,
+    /// `subjects.yml`, or `subjects` in JSON data.
+    Subjects,
+    /// `verbs.yml`, or `allowed_verbs` in JSON data.
+    Verbs,
+    /// `events.yml`, or `events` in JSON data.
+    Events,
+    /// `rooms.yml`, or `room_blueprints` in JSON data.
+    Rooms,
+    /// `daemons.yml`, or `daemons` in JSON data.
+    Daemons,
+}
+
+impl ConfigSection {
+    /// Every section, in the order they are checked.
+    pub const ALL: [ConfigSection; 7] = [
+        ConfigSection::Narratives,
+        ConfigSection::Items,
+        ConfigSection::Subjects,
+        ConfigSection::Verbs,
+        ConfigSection::Events,
+        ConfigSection::Rooms,
+        ConfigSection::Daemons,
+    ];
+    /// The name of the YAML file holding the section.
+    pub fn file_name(&self) -> &'static str {
+        match self {
+            ConfigSection::Narratives => "narratives.yml",
+            ConfigSection::Items => "items.yml",
+            ConfigSection::Subjects => "subjects.yml",
+            ConfigSection::Verbs => "verbs.yml",
+            ConfigSection::Events => "events.yml",
+            ConfigSection::Rooms => "rooms.yml",
+            ConfigSection::Daemons => "daemons.yml",
+        }
+    }
+    /// The key holding the section in JSON data.
+    pub fn json_key(&self) -> &'static str {
+        match self {
+            ConfigSection::Narratives => "narratives",
+            ConfigSection::Items => "items",
+            ConfigSection::Subjects => "subjects",
+            ConfigSection::Verbs => "allowed_verbs",
+            ConfigSection::Events => "events",
+            ConfigSection::Rooms => "room_blueprints",
+            ConfigSection::Daemons => "daemons",
+        }
+    }
+    pub(crate) fn entry_name(&self) -> &'static str {
+        match self {
+            ConfigSection::Narratives => "narrative",
+            ConfigSection::Items => "item",
+            ConfigSection::Subjects => "subject",
+            ConfigSection::Verbs => "verb",
+            ConfigSection::Events => "event",
+            ConfigSection::Rooms => "room",
+            ConfigSection::Daemons => "daemon",
+        }
+    }
+}
+
+/// What a lint warning is about.
+#[derive(Debug, Clone, Copy, Eq, Ord, PartialEq, PartialOrd, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LintKind {
+    /// No room, event, or topic uses the narrative.
+    UnusedNarrative,
+    /// The item isn't in any room, and nothing gives it
+    /// to the player.
+    UnusedItem,
+    /// The subject isn't in any room, and nothing brings
+    /// it into the game.
+    UnusedSubject,
+    /// No event uses the verb. Only verbs that trigger
+    /// events are checked.
+    UnusedVerb,
+    /// No exit leads to the room, and nothing moves the
+    /// player there.
+    NoWayIn,
+    /// Another entry in the same section has the same id.
+    DuplicateId,
+}
+
+/// Where an entry a lint warning is about was defined.
+#[derive(Debug, Clone, Eq, Ord, PartialEq, PartialOrd, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub struct SourceLocation {
+    /// The section of the game data the entry is in.
+    pub section: ConfigSection,
+    /// The id of the entry.
+    pub id: u16,
+    /// The file the entry is in, when the game was read
+    /// from files.
+    pub file: Option<String>,
+    /// The line the entry starts on, starting at 1, when
+    /// the game data was read from its source.
+    pub line: Option<usize>,
+}
+
+impl fmt::Display for SourceLocation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match (&self.file, self.line) {
+            (Some(file), Some(line)) => write!(f, "{}:{}", file, line),
+            (Some(file), None) => write!(f, "{}", file),
+            (None, Some(line)) => write!(f, "{} line {}", self.section.json_key(), line),
+            // the game wasn't read from files, so there's only
+            // the section of the game data to point to
+            (None, None) => write!(f, "{}", self.section.json_key()),
+        }
+    }
+}
+
+/// Content a game defines but never uses, or ids defined
+/// more than once.
+#[derive(Debug, Clone, Eq, Ord, PartialEq, PartialOrd, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub struct LintWarning {
+    /// Where the entry was defined.
+    pub location: SourceLocation,
+    /// What the warning is about.
+    pub kind: LintKind,
+    /// Describes the problem.
+    pub message: String,
+}
+
+impl fmt::Display for LintWarning {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}: {}", self.location, self.message)
+    }
+}
+
+impl Config {
+    /// Finds content the game defines but never uses, like
+    /// narratives nothing displays or items the player can
+    /// never come across, and ids used by more than one entry
+    /// in the same section.
+    ///
+    /// The warnings don't say where in the source each entry
+    /// is, since the config doesn't keep it. Use
+    /// `Config::lint_path` or `Config::lint_json` for that.
+    ///
+    /// # Examples:
+    /// ```rust
+    /// # use nightrunner_lib::config::{Config, LintKind, Narrative};
+    /// let mut config = Config::from_path("fixtures/");
+    /// config.narratives.push(Narrative {
+    ///     id: 42,
+    ///     text: "nobody reads this".to_string(),
+    ///     description: "forgotten".to_string(),
+    /// });
+    /// let warnings = config.lint();
+    /// let warning = warnings.iter().find(|w| w.location.id == 42).unwrap();
+    /// assert_eq!(warning.kind, LintKind::UnusedNarrative);
+    /// ```
+    pub fn lint(&self) -> Vec<LintWarning> {
+        let mut warnings = self.duplicate_ids();
+        let effects = self.effects();
+
+        let mut used_narratives: BTreeSet<u16> = BTreeSet::new();
+        used_narratives.extend(self.room_blueprints.iter().map(|room| room.narrative));
+        for event in &self.events {
+            used_narratives.extend(event.narrative);
+            used_narratives.extend(event.narrative_after);
+            used_narratives.extend(event.narrative_changes.iter().map(|c| c.narrative));
+        }
+        for subject in &self.subjects {
+            used_narratives.extend(subject.topics.iter().map(|topic| topic.narrative));
+        }
+        for effect in &effects {
+            if let Effect::SetNarrative { narrative, .. } = effect {
+                used_narratives.insert(*narrative);
+            }
+        }
+        for narrative in &self.narratives {
+            if !used_narratives.contains(&narrative.id) {
+                warnings.push(warning(
+                    ConfigSection::Narratives,
+                    narrative.id,
+                    LintKind::UnusedNarrative,
+                    format!(
+                        "narrative {} ({}) isn't used by any room, event, or topic",
+                        narrative.id, narrative.description
+                    ),
+                ));
+            }
+        }
+
+        let mut placed_items: BTreeSet<u16> = BTreeSet::new();
+        placed_items.extend(self.room_blueprints.iter().flat_map(|r| r.item_ids.iter()));
+        placed_items.extend(self.events.iter().filter_map(|event| event.add_item));
+        for effect in &effects {
+            if let Effect::AddItem { item_id } = effect {
+                placed_items.insert(*item_id);
+            }
+        }
+        for item in &self.items {
+            if !placed_items.contains(&item.id) {
+                warnings.push(warning(
+                    ConfigSection::Items,
+                    item.id,
+                    LintKind::UnusedItem,
+                    format!(
+                        "item {} ({}) isn't in any room and is never given to the player",
+                        item.id, item.name
+                    ),
+                ));
+            }
+        }
+
+        let mut placed_subjects: BTreeSet<u16> = BTreeSet::new();
+        placed_subjects.extend(
+            self.room_blueprints
+                .iter()
+                .flat_map(|room| room.subject_ids.iter()),
+        );
+        for event in &self.events {
+            placed_subjects.extend(event.add_subject);
+            placed_subjects.extend(
+                event
+                    .move_subjects
+                    .iter()
+                    .filter(|subject_move| subject_move.location != SubjectLocation::Nowhere)
+                    .map(|subject_move| subject_move.subject_id),
+            );
+        }
+        for effect in &effects {
+            if let Effect::MoveSubject {
+                subject_id,
+                location,
+            } = effect
+            {
+                if *location != SubjectLocation::Nowhere {
+                    placed_subjects.insert(*subject_id);
+                }
+            }
+        }
+        for subject in &self.subjects {
+            if !placed_subjects.contains(&subject.id) {
+                warnings.push(warning(
+                    ConfigSection::Subjects,
+                    subject.id,
+                    LintKind::UnusedSubject,
+                    format!(
+                        "subject {} ({}) isn't in any room and is never brought into the game",
+                        subject.id, subject.name
+                    ),
+                ));
+            }
+        }
+
+        let used_verbs: BTreeSet<u16> = self
+            .events
+            .iter()
+            .filter_map(|event| event.required_verb)
+            .collect();
+        for verb in &self.allowed_verbs {
+            if verb.verb_function == VerbFunction::Normal && !used_verbs.contains(&verb.id) {
+                warnings.push(warning(
+                    ConfigSection::Verbs,
+                    verb.id,
+                    LintKind::UnusedVerb,
+                    format!(
+                        "verb {} ({}) isn't used by any event",
+                        verb.id,
+                        verb.names.first().cloned().unwrap_or_default()
+                    ),
+                ));
+            }
+        }
+
+        let mut ways_in: BTreeSet<u16> = BTreeSet::from([1]);
+        for room in &self.room_blueprints {
+            ways_in.extend(
+                room.exits
+                    .iter()
+                    .filter(|exit| exit.room_id != room.id)
+                    .map(|exit| exit.room_id),
+            );
+        }
+        ways_in.extend(self.events.iter().filter_map(|event| event.destination));
+        for effect in &effects {
+            if let Effect::MovePlayer { room_id } = effect {
+                ways_in.insert(*room_id);
+            }
+        }
+        for room in &self.room_blueprints {
+            if !ways_in.contains(&room.id) {
+                warnings.push(warning(
+                    ConfigSection::Rooms,
+                    room.id,
+                    LintKind::NoWayIn,
+                    format!(
+                        "room {} ({}) has no exits leading to it and no event moves the player there",
+                        room.id, room.name
+                    ),
+                ));
+            }
+        }
+        warnings
+    }
+
+    /// Loads the YAML files in the path and lints them, with
+    /// the file and line of each warning. See [Config::lint].
+    pub fn lint_path(path: &str) -> NRResult<Vec<LintWarning>> {
+        let data = YamlSections::from_folder(path)?.to_value()?;
+        let symbols = Symbols::collect(&data)?;
+        let config = Config::from_value(data)?;
+        let mut warnings = config.lint();
+        for section in ConfigSection::ALL {
+            let data = match std::fs::read_to_string(Path::new(path).join(section.file_name())) {
+                Ok(data) => data,
+                Err(_) => continue,
+            };
+            let entries = serde_yaml::from_str::<serde_json::Value>(&data)?;
+            let ids = entry_ids(&entries, section, &symbols);
+            let lines = yaml_entry_lines(&data);
+            locate(
+                &mut warnings,
+                section,
+                &ids,
+                &lines,
+                Some(section.file_name()),
+            );
+        }
+        Ok(warnings)
+    }
+
+    /// Loads the JSON data and lints it, with the line of
+    /// each warning. See [Config::lint].
+    pub fn lint_json(data: &str) -> NRResult<Vec<LintWarning>> {
+        let value: serde_json::Value = serde_json::from_str(data)?;
+        let symbols = Symbols::collect(&value)?;
+        let config = Config::from_value(value.clone())?;
+        let mut warnings = config.lint();
+        for section in ConfigSection::ALL {
+            let ids = entry_ids(&value[section.json_key()], section, &symbols);
+            let lines = json_entry_lines(data, section.json_key());
+            locate(&mut warnings, section, &ids, &lines, None);
+        }
+        Ok(warnings)
+    }
+
+    fn duplicate_ids(&self) -> Vec<LintWarning> {
+        let sections: [(ConfigSection, Vec<u16>); 7] = [
+            (
+                ConfigSection::Narratives,
+                self.narratives.iter().map(|n| n.id).collect(),
+            ),
+            (
+                ConfigSection::Items,
+                self.items.iter().map(|i| i.id).collect(),
+            ),
+            (
+                ConfigSection::Subjects,
+                self.subjects.iter().map(|s| s.id).collect(),
+            ),
+            (
+                ConfigSection::Verbs,
+                self.allowed_verbs.iter().map(|v| v.id).collect(),
+            ),
+            (
+                ConfigSection::Events,
+                self.events.iter().map(|e| e.id).collect(),
+            ),
+            (
+                ConfigSection::Rooms,
+                self.room_blueprints.iter().map(|r| r.id).collect(),
+            ),
+            (
+                ConfigSection::Daemons,
+                self.daemons.iter().map(|d| d.id).collect(),
+            ),
+        ];
+        let mut warnings = vec![];
+        for (section, ids) in sections {
+            let mut counts: BTreeMap<u16, usize> = BTreeMap::new();
+            for id in ids {
+                let count = counts.entry(id).or_insert(0);
+                *count += 1;
+                if *count > 1 {
+                    warnings.push(warning(
+                        section,
+                        id,
+                        LintKind::DuplicateId,
+                        format!(
+                            "{} id {} is already used by another {}",
+                            section.entry_name(),
+                            id,
+                            section.entry_name()
+                        ),
+                    ));
+                }
+            }
+        }
+        warnings
+    }
+}
+
+fn warning(section: ConfigSection, id: u16, kind: LintKind, message: String) -> LintWarning {
+    LintWarning {
+        location: SourceLocation {
+            section,
+            id,
+            file: None,
+            line: None,
+        },
+        kind,
+        message,
+    }
+}
+
+/// Fills in the file and line of the warnings about entries
+/// of a section. Entries with the same id are told apart by
+/// their order, which loading the config keeps, so each
+/// duplicate id warning points at the next entry with that id.
+fn locate(
+    warnings: &mut [LintWarning],
+    section: ConfigSection,
+    ids: &[Option<u16>],
+    lines: &[usize],
+    file: Option<&str>,
+) {
+    let mut duplicates: BTreeMap<u16, usize> = BTreeMap::new();
+    for lint_warning in warnings
+        .iter_mut()
+        .filter(|w| w.location.section == section)
+    {
+        let occurrence = if lint_warning.kind == LintKind::DuplicateId {
+            let seen = duplicates.entry(lint_warning.location.id).or_insert(1);
+            *seen += 1;
+            *seen
+        } else {
+            1
+        };
+        let index = ids
+            .iter()
+            .enumerate()
+            .filter(|(_, id)| **id == Some(lint_warning.location.id))
+            .map(|(index, _)| index)
+            .nth(occurrence - 1);
+        lint_warning.location.file = file.map(|file| file.to_string());
+        lint_warning.location.line = index.and_then(|index| lines.get(index).copied());
+    }
+}
+
+/// Returns the id of each entry of a section, looking up the
+/// numeric id of entries with symbolic ids.
+fn entry_ids(
+    entries: &serde_json::Value,
+    section: ConfigSection,
+    symbols: &Symbols,
+) -> Vec<Option<u16>> {
+    entries
+        .as_array()
+        .map(|entries| {
+            entries
+                .iter()
+                .map(|entry| match &entry["id"] {
+                    serde_json::Value::String(symbol) => symbols.id(section, symbol),
+                    id => id.as_u64().and_then(|id| u16::try_from(id).ok()),
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Returns the line each entry of a YAML list starts on.
+fn yaml_entry_lines(data: &str) -> Vec<usize> {
+    data.lines()
+        .enumerate()
+        .filter(|(_, line)| *line == "-" || line.starts_with("- "))
+        .map(|(index, _)| index + 1)
+        .collect()
+}
+
+/// Returns the line each entry of the list under a top level
+/// key of a JSON object starts on.
+fn json_entry_lines(data: &str, key: &str) -> Vec<usize> {
+    let mut lines = vec![];
+    let mut line = 1;
+    let mut depth = 0;
+    let mut in_string = false;
+    let mut escaped = false;
+    let mut string = String::new();
+    let mut last_key = String::new();
+    let mut list_depth = None;
+    let mut expecting_entry = false;
+    for character in data.chars() {
+        if character == '\n' {
+            line += 1;
+        }
+        if in_string {
+            if escaped {
+                escaped = false;
+            } else if character == '\\' {
+                escaped = true;
+            } else if character == '"' {
+                in_string = false;
+                if depth == 1 {
+                    last_key = std::mem::take(&mut string);
+                }
+            } else if depth == 1 {
+                string.push(character);
+            }
+            continue;
+        }
+        if character.is_whitespace() {
+            continue;
+        }
+        if expecting_entry && character != ']' {
+            lines.push(line);
+            expecting_entry = false;
+        }
+        match character {
+            '"' => {
+                in_string = true;
+                string.clear();
+            }
+            '{' | '[' => {
+                depth += 1;
+                if character == '[' && depth == 2 && last_key == key {
+                    list_depth = Some(depth);
+                    expecting_entry = true;
+                }
+            }
+            '}' | ']' => {
+                if list_depth == Some(depth) {
+                    list_depth = None;
+                    expecting_entry = false;
+                }
+                depth -= 1;
+            }
+            ',' if list_depth == Some(depth) => expecting_entry = true,
+            _ => {}
+        }
+    }
+    lines
+}
+
+#[cfg(test)]
+#[path = "lint_tests.rs"]
+mod lint_tests;
@@ -0,0 +1,187 @@
+use super::*;
+use crate::config::{Item, Narrative};
+use crate::util::test_helpers::mock_json_data;
+#[cfg(test)]
+use pretty_assertions::assert_eq;
+
+fn kinds(warnings: &[LintWarning]) -> Vec<(LintKind, u16)> {
+    warnings
+        .iter()
+        .map(|warning| (warning.kind, warning.location.id))
+        .collect()
+}
+
+#[test]
+fn it_lints_the_fixtures_with_file_locations() {
+    // nothing displays the fixtures' narrative 5
+    assert_eq!(
+        kinds(&Config::from_path("fixtures/").lint()),
+        vec![(LintKind::UnusedNarrative, 5)]
+    );
+    let warnings = Config::lint_path("fixtures/").unwrap();
+    assert_eq!(
+        warnings[0].location,
+        SourceLocation {
+            section: ConfigSection::Narratives,
+            id: 5,
+            file: Some("narratives.yml".to_string()),
+            line: Some(14),
+        }
+    );
+    assert!(warnings[0]
+        .to_string()
+        .starts_with("narratives.yml:14: narrative 5"));
+}
+
+#[test]
+fn it_finds_unused_content() {
+    let mut config = Config::from_path("fixtures/");
+    config.narratives.push(Narrative {
+        id: 10,
+        text: "unused".to_string(),
+        description: "unused narrative".to_string(),
+    });
+    config.items.push(Item {
+        id: 10,
+        name: "lost item".to_string(),
+        description: "lost".to_string(),
+        can_pick: true,
+        capabilities: Default::default(),
+    });
+    let mut subject = config.subjects[0].clone();
+    subject.id = 10;
+    config.subjects.push(subject);
+    let mut verb = config.allowed_verbs[6].clone();
+    verb.id = 12;
+    config.allowed_verbs.push(verb);
+    config.room_blueprints[0].exits.clear();
+    assert_eq!(
+        kinds(&config.lint()),
+        vec![
+            (LintKind::UnusedNarrative, 5),
+            (LintKind::UnusedNarrative, 10),
+            (LintKind::UnusedItem, 10),
+            (LintKind::UnusedSubject, 10),
+            (LintKind::UnusedVerb, 12),
+            (LintKind::NoWayIn, 2),
+        ]
+    );
+    // without a source, warnings point to the section
+    assert!(config.lint()[0].to_string().starts_with("narratives: "));
+
+    // an event taking the player to room 2 is a way in
+    config.events[0].destination = Some(2);
+    assert!(!kinds(&config.lint()).contains(&(LintKind::NoWayIn, 2)));
+}
+
+#[test]
+fn it_finds_duplicate_ids_with_their_lines() {
+    let yaml = "\
+---
+- id: 1
+  name: item1
+  description: item 1 description
+  can_pick: false
+- id: 2
+  name: item2
+  description: item 2 description
+  can_pick: true
+- id: 1
+  name: item3
+  description: item 3 description
+  can_pick: true
+";
+    let lines = yaml_entry_lines(yaml);
+    assert_eq!(lines, vec![2, 6, 10]);
+    let ids = entry_ids(
+        &serde_yaml::from_str(yaml).unwrap(),
+        ConfigSection::Items,
+        &Symbols::default(),
+    );
+    let mut config = Config::from_path("fixtures/");
+    config.items = serde_yaml::from_str(yaml).unwrap();
+    config.items.sort_by_key(|item| item.id);
+    let mut warnings = config.duplicate_ids();
+    locate(
+        &mut warnings,
+        ConfigSection::Items,
+        &ids,
+        &lines,
+        Some("items.yml"),
+    );
+    assert_eq!(warnings.len(), 1);
+    assert_eq!(
+        warnings[0].to_string(),
+        "items.yml:10: item id 1 is already used by another item"
+    );
+}
+
+#[test]
+fn it_finds_lines_in_json_data() {
+    let mut data: serde_json::Value = serde_json::from_str(&mock_json_data()).unwrap();
+    let mut narrative = data["narratives"][0].clone();
+    narrative["id"] = 42.into();
+    data["narratives"]
+        .as_array_mut()
+        .unwrap()
+        .push(narrative.clone());
+    let json = serde_json::to_string_pretty(&data).unwrap();
+    let warnings = Config::lint_json(&json).unwrap();
+    assert_eq!(
+        kinds(&warnings),
+        vec![
+            (LintKind::UnusedNarrative, 5),
+            (LintKind::UnusedNarrative, 42)
+        ]
+    );
+    let line = warnings[1].location.line.unwrap();
+    let entry = json.lines().nth(line - 1).unwrap();
+    assert_eq!(entry.trim(), "{");
+    let entry_end = json
+        .lines()
+        .skip(line)
+        .position(|l| l.trim().starts_with('}'));
+    let entry: Vec<&str> = json.lines().skip(line).take(entry_end.unwrap()).collect();
+    assert!(entry.iter().any(|l| l.contains("\"id\": 42")));
+    assert!(warnings[1].to_string().starts_with("narratives line "));
+}
+
+#[test]
+fn it_finds_lines_of_entries_with_symbolic_ids() {
+    let mut data: serde_json::Value = serde_json::from_str(&mock_json_data()).unwrap();
+    let mut narrative = data["narratives"][0].clone();
+    narrative["id"] = "forgotten".into();
+    data["narratives"].as_array_mut().unwrap().push(narrative);
+    let json = serde_json::to_string_pretty(&data).unwrap();
+    let warnings = Config::lint_json(&json).unwrap();
+    // the symbol gets the id after the highest narrative id
+    assert_eq!(
+        kinds(&warnings),
+        vec![
+            (LintKind::UnusedNarrative, 5),
+            (LintKind::UnusedNarrative, 6)
+        ]
+    );
+    let line = warnings[1].location.line.unwrap();
+    let entry: Vec<&str> = json.lines().skip(line).take(3).collect();
+    assert!(entry.iter().any(|l| l.contains("\"id\": \"forgotten\"")));
+}
+
+#[test]
+fn it_finds_entry_lines_in_compact_json() {
+    let json = "{\"items\": [\n{\"id\": 1},\n {\"id\": 2, \"name\": \"[x]\"}], \"other\": [1,\n2]}";
+    assert_eq!(json_entry_lines(json, "items"), vec![2, 3]);
+    assert_eq!(json_entry_lines(json, "other"), vec![3, 4]);
+}
+
+#[test]
+fn it_returns_errors_for_games_it_cant_load() {
+    assert!(Config::lint_path("no/such/game/").is_err());
+    let mut data: serde_json::Value =
+        serde_json::from_str(&Config::from_path("fixtures/").to_json()).unwrap();
+    data["format_version"] = 99.into();
+    assert!(Config::lint_json(&data.to_string()).is_err());
+    data["format_version"] = 2.into();
+    data["events"][0]["required_item"] = "missing".into();
+    assert!(Config::lint_json(&data.to_string()).is_err());
+}
@@ -0,0 +1,117 @@
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::parser::errors::UnsupportedFormatVersion;
+use crate::NRResult;
+
+/// The version of the layout of game data read by this
+/// version of the library. Game data without a
+/// `format_version` is read as version 1.
+pub const FORMAT_VERSION: u32 = 2;
+
+/// Upgrades game data from the version before `version` to
+/// `version`, returning what was changed.
+type MigrationStep = fn(&mut Value) -> Vec<String>;
+
+/// Every migration, in the order they are applied.
+const MIGRATIONS: [(u32, MigrationStep); 1] = [(2, place_events_by_location)];
+
+/// A change made to game data written for an older version
+/// of the library, to bring it up to the current layout.
+#[derive(Debug, Clone, Eq, Ord, PartialEq, PartialOrd, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub struct Migration {
+    /// The format version the change upgraded the data to.
+    pub version: u32,
+    /// What was changed.
+    pub message: String,
+}
+
+impl fmt::Display for Migration {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "format version {}: {}", self.version, self.message)
+    }
+}
+
+/// Upgrades game data, in the layout of the JSON data, to
+/// the current format version, returning the changes made.
+pub(crate) fn migrate(data: &mut Value) -> NRResult<Vec<Migration>> {
+    if !data.is_object() {
+        // not game data, which reading the config reports
+        return Ok(vec![]);
+    }
+    let version = match &data["format_version"] {
+        Value::Null => 1,
+        version => version
+            .as_u64()
+            .and_then(|version| u32::try_from(version).ok())
+            .ok_or(UnsupportedFormatVersion)?,
+    };
+    if version > FORMAT_VERSION {
+        return Err(UnsupportedFormatVersion.into());
+    }
+    let mut migrations = vec![];
+    for (to_version, step) in MIGRATIONS {
+        if to_version > version {
+            migrations.extend(step(data).into_iter().map(|message| Migration {
+                version: to_version,
+                message,
+            }));
+        }
+    }
+    data["format_version"] = FORMAT_VERSION.into();
+    Ok(migrations)
+}
+
+/// Version 2 places events in rooms by `Event.location`,
+/// instead of listing them in the room's `room_events`.
+fn place_events_by_location(data: &mut Value) -> Vec<String> {
+    let mut messages = vec![];
+    let mut locations = vec![];
+    if let Some(rooms) = data["room_blueprints"].as_array_mut() {
+        for room in rooms.iter_mut().filter_map(Value::as_object_mut) {
+            let room_events = match room.remove("room_events") {
+                Some(room_events) => room_events,
+                None => continue,
+            };
+            let room_id = room.get("id").cloned().unwrap_or_default();
+            messages.push(format!(
+                "removed room_events from room {}, events are placed in rooms by their location",
+                id_text(&room_id)
+            ));
+            for event_id in room_events.as_array().into_iter().flatten() {
+                locations.push((event_id.clone(), room_id.clone()));
+            }
+        }
+    }
+    if let Some(events) = data["events"].as_array_mut() {
+        for event in events.iter_mut().filter_map(Value::as_object_mut) {
+            if !event.get("location").is_none_or(Value::is_null) {
+                continue;
+            }
+            let event_id = event.get("id").cloned().unwrap_or_default();
+            if let Some((_, room_id)) = locations.iter().find(|(id, _)| *id == event_id) {
+                messages.push(format!(
+                    "set the location of event {} to room {}, which listed it in room_events",
+                    id_text(&event_id),
+                    id_text(room_id)
+                ));
+                event.insert("location".to_string(), room_id.clone());
+            }
+        }
+    }
+    messages
+}
+
+fn id_text(id: &Value) -> String {
+    match id {
+        Value::String(symbol) => symbol.clone(),
+        id => id.to_string(),
+    }
+}
+
+#[cfg(test)]
+#[path = "migrations_tests.rs"]
+mod migrations_tests;
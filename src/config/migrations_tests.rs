@@ -0,0 +1,72 @@
+use super::*;
+use crate::config::Config;
+#[cfg(test)]
+use pretty_assertions::assert_eq;
+
+/// The fixtures as they were written before events had a
+/// location, with the events listed in their rooms instead.
+fn version_1_data() -> Value {
+    let mut data: Value = serde_json::from_str(&Config::from_path("fixtures/").to_json()).unwrap();
+    data.as_object_mut().unwrap().remove("format_version");
+    for (room, events) in [(0, vec![1, 4, 2, 3, 6]), (1, vec![5])] {
+        data["room_blueprints"][room]["room_events"] = events.into();
+    }
+    for event in data["events"].as_array_mut().unwrap() {
+        event.as_object_mut().unwrap().remove("location");
+    }
+    data
+}
+
+#[test]
+fn it_places_events_listed_in_rooms() {
+    let config = Config::from_json(&version_1_data().to_string());
+    assert_eq!(
+        Config {
+            migrations: vec![],
+            ..config.clone()
+        },
+        Config::from_path("fixtures/")
+    );
+    assert_eq!(
+        config.migrations[0].to_string(),
+        "format version 2: removed room_events from room 1, \
+         events are placed in rooms by their location"
+    );
+    assert_eq!(
+        config.migrations.last().unwrap().to_string(),
+        "format version 2: set the location of event 6 to room 1, \
+         which listed it in room_events"
+    );
+    assert_eq!(config.migrations.len(), 8);
+}
+
+#[test]
+fn it_keeps_the_location_of_events() {
+    let mut data = version_1_data();
+    data["events"][4]["location"] = 1.into();
+    let migrations = migrate(&mut data).unwrap();
+    assert_eq!(data["events"][4]["location"], 1);
+    assert_eq!(data["format_version"], FORMAT_VERSION);
+    assert!(data["room_blueprints"][1].get("room_events").is_none());
+    assert_eq!(migrations.len(), 7);
+}
+
+#[test]
+fn it_leaves_current_data_alone() {
+    let config = Config::from_path("fixtures/");
+    assert!(config.migrations.is_empty());
+    let mut data: Value = serde_json::from_str(&config.to_json()).unwrap();
+    let before = data.clone();
+    assert!(migrate(&mut data).unwrap().is_empty());
+    assert_eq!(data, before);
+}
+
+#[test]
+fn it_rejects_newer_format_versions() {
+    let mut data = version_1_data();
+    data["format_version"] = (FORMAT_VERSION + 1).into();
+    let error = migrate(&mut data).unwrap_err();
+    assert_eq!(error.to_string(), UnsupportedFormatVersion.to_string());
+    data["format_version"] = "two".into();
+    assert!(migrate(&mut data).is_err());
+}
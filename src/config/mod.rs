@@ -1,19 +1,45 @@
 pub(crate) mod determiners;
+pub(crate) mod dialogue;
 pub(crate) mod directions;
+pub(crate) mod export;
+pub(crate) mod formats;
+pub(crate) mod lint;
+pub(crate) mod migrations;
 pub(crate) mod movements;
 pub(crate) mod prepositions;
 pub(crate) mod rooms;
+pub(crate) mod rules;
+#[cfg(feature = "schema")]
+pub(crate) mod schema;
+pub(crate) mod solvability;
+pub(crate) mod sources;
+pub(crate) mod stats;
+pub(crate) mod symbols;
 
-use crate::parser::errors::{InvalidRoom, InvalidSubject, NoItem};
+use crate::parser::errors::{InvalidNarrative, InvalidRoom, InvalidSubject, NoItem};
 use crate::NRResult;
 
 use self::determiners::AllowedDeterminers;
+pub use self::dialogue::{ActiveDialogue, Dialogue, DialogueChoice, DialogueNode};
 use self::directions::AllowedDirections;
+pub use self::formats::ConfigFormat;
+pub use self::lint::{ConfigSection, LintKind, LintWarning, SourceLocation};
+use self::migrations::migrate;
+pub use self::migrations::{Migration, FORMAT_VERSION};
 use self::movements::AllowedMovements;
 use self::prepositions::AllowedPrepositions;
 use self::rooms::{Room, RoomBlueprint};
+pub use self::rules::{Condition, Daemon, Effect};
+pub use self::solvability::{EffectSource, Ending, SolvabilityReport};
+pub use self::sources::YamlSections;
+pub use self::stats::{Award, GameOver, PlayerStats};
+use self::symbols::resolve_symbols;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
 use serde::{Deserialize, Serialize};
 use serde_json;
+use std::collections::BTreeMap;
+use std::sync::Arc;
 
 /// This struct holds the texts used to display the story
 /// in the game. These narratives are used to display
@@ -23,6 +49,7 @@ use serde_json;
 /// item, room, or subject's description. For everything
 /// else, the narrative's text is used.
 #[derive(Debug, Clone, Eq, Ord, PartialEq, PartialOrd, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "snake_case")]
 pub struct Narrative {
     /// Narrative id used when referencing the narrative.
@@ -79,6 +106,7 @@ pub struct Narrative {
 /// };
 /// ```
 #[derive(Debug, Clone, Eq, Ord, PartialEq, PartialOrd, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "snake_case")]
 pub struct Verb {
     /// The id of the verb used when referencing the verb.
@@ -102,6 +130,26 @@ impl std::fmt::Display for Verb {
             VerbFunction::Inventory => write!(f, "inventory"),
             VerbFunction::Quit => write!(f, "quit"),
             VerbFunction::Talk => write!(f, "talk"),
+            VerbFunction::Ask => write!(f, "ask"),
+            VerbFunction::Tell => write!(f, "tell"),
+            VerbFunction::Give => write!(f, "give"),
+            VerbFunction::Show => write!(f, "show"),
+            VerbFunction::Eat => write!(f, "eat"),
+            VerbFunction::Drink => write!(f, "drink"),
+            VerbFunction::Read => write!(f, "read"),
+            VerbFunction::Wear => write!(f, "wear"),
+            VerbFunction::Remove => write!(f, "remove"),
+            VerbFunction::Open => write!(f, "open"),
+            VerbFunction::Close => write!(f, "close"),
+            VerbFunction::Lock => write!(f, "lock"),
+            VerbFunction::Unlock => write!(f, "unlock"),
+            VerbFunction::TurnOn => write!(f, "turn on"),
+            VerbFunction::TurnOff => write!(f, "turn off"),
+            VerbFunction::Put => write!(f, "put"),
+            VerbFunction::Score => write!(f, "score"),
+            VerbFunction::Restart => write!(f, "restart"),
+            VerbFunction::Undo => write!(f, "undo"),
+            VerbFunction::Redo => write!(f, "redo"),
             VerbFunction::Normal => write!(f, "{}", self.names[0]),
         }
     }
@@ -113,6 +161,7 @@ impl std::fmt::Display for Verb {
 /// 'look' is used to look at the room or item but it can
 /// be named anything.
 #[derive(Debug, Clone, Eq, Ord, PartialEq, PartialOrd, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "snake_case")]
 pub enum VerbFunction {
     #[serde(rename = "quit")]
@@ -141,6 +190,74 @@ pub enum VerbFunction {
     #[serde(rename = "talk")]
     /// The talk verb is used to talk to a character in a room.
     Talk,
+    #[serde(rename = "ask")]
+    /// The ask verb is used to ask a character about a topic,
+    /// as in "ask guard about key".
+    Ask,
+    #[serde(rename = "tell")]
+    /// The tell verb is used to tell a character about a topic,
+    /// as in "tell wizard about dragon".
+    Tell,
+    #[serde(rename = "give")]
+    /// The give verb is used to give an item to a character,
+    /// as in "give coin to beggar". See `Subject.accepts`.
+    Give,
+    #[serde(rename = "show")]
+    /// The show verb is used to show an item to a character
+    /// without giving it away, as in "show badge to guard".
+    Show,
+    #[serde(rename = "eat")]
+    /// The eat verb is used to eat an edible item.
+    Eat,
+    #[serde(rename = "drink")]
+    /// The drink verb is used to drink a drinkable item.
+    Drink,
+    #[serde(rename = "read")]
+    /// The read verb is used to read the text on an item.
+    Read,
+    #[serde(rename = "wear")]
+    /// The wear verb is used to put on a wearable item.
+    Wear,
+    #[serde(rename = "remove")]
+    /// The remove verb is used to take off a worn item.
+    Remove,
+    #[serde(rename = "open")]
+    /// The open verb is used to open an item, such as a box.
+    Open,
+    #[serde(rename = "close")]
+    /// The close verb is used to close an open item.
+    Close,
+    #[serde(rename = "lock")]
+    /// The lock verb is used to lock an item with its key.
+    Lock,
+    #[serde(rename = "unlock")]
+    /// The unlock verb is used to unlock an item with its key.
+    Unlock,
+    #[serde(rename = "turn_on")]
+    /// The turn on verb is used to switch an item on. Verb
+    /// names can have more than one word, such as "turn on".
+    TurnOn,
+    #[serde(rename = "turn_off")]
+    /// The turn off verb is used to switch an item off.
+    TurnOff,
+    #[serde(rename = "put")]
+    /// The put verb is used to put an item inside another
+    /// one, as in "put coin in box".
+    Put,
+    #[serde(rename = "score")]
+    /// The score verb is used to show the player's score
+    /// and stats.
+    Score,
+    #[serde(rename = "restart")]
+    /// The restart verb is used to start the game over.
+    Restart,
+    #[serde(rename = "undo")]
+    /// The undo verb is used to take back the last command.
+    Undo,
+    #[serde(rename = "redo")]
+    /// The redo verb is used to play again a command that
+    /// was taken back.
+    Redo,
     #[serde(rename = "normal")]
     /// Any other verbs should be set to this variant and will
     /// be parsed by the event handling function.
@@ -165,9 +282,20 @@ pub enum VerbFunction {
 ///    name: "person".to_string(),
 ///    description: "A person dressed all in black".to_string(),
 ///    default_text: "Person: I'm busy now. Maybe later.".to_string(),
+///    behavior: None,
+///    dialogue: None,
+///    topics: vec![],
+///    unknown_topic_text: None,
+///    accepts: vec![],
+///    refusal_text: None,
+///    disposition: 0,
+///    flags: vec![],
+///    disposition_texts: vec![],
+///    item_ids: vec![],
 /// };
 /// ```
 #[derive(Debug, Clone, Eq, Ord, PartialEq, PartialOrd, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "snake_case")]
 pub struct Subject {
     /// The id of the subject used when referencing the subject.
@@ -181,6 +309,217 @@ pub struct Subject {
     /// interacts with the subject and no active events
     /// are associated with this subject.
     pub default_text: String,
+    /// How the subject moves around the game on its own.
+    /// Subjects without a behavior stay where they are
+    /// unless an event moves them.
+    #[serde(default)]
+    pub behavior: Option<SubjectBehavior>,
+    /// The conversation the player can have with the subject
+    /// by talking to it, when no event handles the talking.
+    #[serde(default)]
+    pub dialogue: Option<Dialogue>,
+    /// What the subject says when the player asks or
+    /// tells it about something.
+    #[serde(default)]
+    pub topics: Vec<Topic>,
+    /// What the subject says when asked about a topic it
+    /// doesn't know. When this isn't set a generic text
+    /// is used.
+    #[serde(default)]
+    pub unknown_topic_text: Option<String>,
+    /// Items the subject reacts to when the player gives
+    /// or shows them.
+    #[serde(default)]
+    pub accepts: Vec<AcceptedItem>,
+    /// What the subject says when it's given or shown an
+    /// item it doesn't accept. When this isn't set a generic
+    /// text is used.
+    #[serde(default)]
+    pub refusal_text: Option<String>,
+    /// How the subject feels about the player. Negative values
+    /// are hostile and positive values are friendly, see
+    /// `Settings` for where each band starts. This is the
+    /// starting value, and events, dialogue choices and daemons
+    /// can change it during the game.
+    #[serde(default)]
+    pub disposition: i32,
+    /// Flags set on the subject, such as "bribed" or "asleep".
+    /// Like `disposition`, these can change during the game.
+    #[serde(default)]
+    pub flags: Vec<String>,
+    /// Descriptions and default texts used instead of the
+    /// regular ones while the subject's disposition is in
+    /// a band.
+    #[serde(default)]
+    pub disposition_texts: Vec<DispositionText>,
+    /// Ids of the items the subject holds, like the items the
+    /// player gave it.
+    #[serde(default)]
+    pub item_ids: Vec<u16>,
+}
+
+impl Subject {
+    /// Returns the disposition band the subject is in.
+    pub fn disposition_band(&self, settings: &Settings) -> DispositionBand {
+        if self.disposition <= settings.hostile_disposition {
+            DispositionBand::Hostile
+        } else if self.disposition >= settings.friendly_disposition {
+            DispositionBand::Friendly
+        } else {
+            DispositionBand::Neutral
+        }
+    }
+    /// Returns true if the flag is set on the subject.
+    pub fn has_flag(&self, flag: &str) -> bool {
+        self.flags.iter().any(|f| f == flag)
+    }
+    /// Returns the description for the subject's current
+    /// disposition band.
+    pub fn current_description(&self, settings: &Settings) -> String {
+        self.disposition_text(settings)
+            .and_then(|text| text.description.clone())
+            .unwrap_or_else(|| self.description.clone())
+    }
+    /// Returns the default text for the subject's current
+    /// disposition band.
+    pub fn current_default_text(&self, settings: &Settings) -> String {
+        self.disposition_text(settings)
+            .and_then(|text| text.default_text.clone())
+            .unwrap_or_else(|| self.default_text.clone())
+    }
+    fn disposition_text(&self, settings: &Settings) -> Option<&DispositionText> {
+        let band = self.disposition_band(settings);
+        self.disposition_texts.iter().find(|text| text.band == band)
+    }
+}
+
+/// How a subject feels about the player, based on
+/// its disposition.
+#[derive(Debug, Clone, Copy, Eq, Ord, PartialEq, PartialOrd, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum DispositionBand {
+    /// The disposition is at or below `Settings.hostile_disposition`.
+    Hostile,
+    /// The disposition is between the hostile and friendly bands.
+    Neutral,
+    /// The disposition is at or above `Settings.friendly_disposition`.
+    Friendly,
+}
+
+/// Texts for a subject in a disposition band. Texts that
+/// aren't set fall back to the subject's regular ones.
+///
+/// # Examples:
+/// ```rust
+/// # use nightrunner_lib::config::{DispositionBand, DispositionText};
+/// let yaml = r#"
+/// band: hostile
+/// description: The guard glares at you.
+/// default_text: "Guard: Get lost."
+/// "#;
+/// let text: DispositionText = serde_yaml::from_str(yaml).unwrap();
+/// assert_eq!(text.band, DispositionBand::Hostile);
+/// ```
+#[derive(Debug, Clone, Eq, Ord, PartialEq, PartialOrd, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
+pub struct DispositionText {
+    /// The band these texts are used in.
+    pub band: DispositionBand,
+    /// Replaces the subject's description.
+    #[serde(default)]
+    pub description: Option<String>,
+    /// Replaces the subject's default text.
+    #[serde(default)]
+    pub default_text: Option<String>,
+}
+
+/// An item a subject accepts from the player.
+///
+/// # Examples:
+/// ```rust
+/// # use nightrunner_lib::config::AcceptedItem;
+/// // the beggar takes the coin and the player
+/// // earns a point
+/// let yaml = r#"
+/// item_id: 3
+/// text: "Bless you, kind stranger!"
+/// show_text: "The beggar's eyes light up."
+/// effects:
+///   - type: change_variable
+///     name: karma
+///     by: 1
+/// "#;
+/// let accepted: AcceptedItem = serde_yaml::from_str(yaml).unwrap();
+/// assert_eq!(accepted.item_id, 3);
+/// ```
+#[derive(Debug, Clone, Eq, Ord, PartialEq, PartialOrd, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
+pub struct AcceptedItem {
+    /// The item the subject accepts.
+    pub item_id: u16,
+    /// What the subject says when given the item.
+    pub text: String,
+    /// What the subject says when shown the item. When
+    /// this isn't set, the subject just looks at it.
+    #[serde(default)]
+    pub show_text: Option<String>,
+    /// The subject only accepts the item when all of
+    /// these are met.
+    #[serde(default)]
+    pub conditions: Vec<Condition>,
+    /// Effects applied when the item is given.
+    #[serde(default)]
+    pub effects: Vec<Effect>,
+}
+
+/// Something a subject can be asked or told about.
+///
+/// # Examples:
+/// ```rust
+/// # use nightrunner_lib::config::Topic;
+/// // "ask guard about key" displays narrative 7,
+/// // but only before event 3 is completed
+/// let yaml = r#"
+/// keywords: [key, keys]
+/// narrative: 7
+/// conditions:
+///   - type: event_not_completed
+///     event_id: 3
+/// "#;
+/// let topic: Topic = serde_yaml::from_str(yaml).unwrap();
+/// assert!(topic.matches("the key"));
+/// ```
+#[derive(Debug, Clone, Eq, Ord, PartialEq, PartialOrd, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
+pub struct Topic {
+    /// Words the player can use for the topic.
+    pub keywords: Vec<String>,
+    /// The narrative with the subject's response.
+    pub narrative: u16,
+    /// The topic is only known when all of these are met.
+    #[serde(default)]
+    pub conditions: Vec<Condition>,
+    /// Effects applied when the subject responds.
+    #[serde(default)]
+    pub effects: Vec<Effect>,
+}
+
+impl Topic {
+    /// Returns true if any of the words the player used
+    /// for the topic is one of the keywords.
+    pub fn matches(&self, topic: &str) -> bool {
+        let topic = topic.to_lowercase();
+        topic == self.keywords.join(" ").to_lowercase()
+            || topic.split_whitespace().any(|word| {
+                self.keywords
+                    .iter()
+                    .any(|keyword| keyword.to_lowercase() == word)
+            })
+    }
 }
 
 impl std::fmt::Display for Subject {
@@ -189,6 +528,166 @@ impl std::fmt::Display for Subject {
     }
 }
 
+/// Where a subject currently is in the game.
+///
+/// Every subject has exactly one location. When the game starts
+/// each subject is placed in the first room that lists it in
+/// `subject_ids`, and subjects not listed in any room start
+/// `Nowhere` until an event brings them into the game.
+///
+/// In YAML and JSON a room location is written as the room id,
+/// and the other locations as `nowhere` and `following_player`.
+///
+/// # Examples:
+/// ```rust
+/// # use nightrunner_lib::config::SubjectLocation;
+/// let location: SubjectLocation = serde_json::from_str("2").unwrap();
+/// assert_eq!(location, SubjectLocation::Room(2));
+/// let location: SubjectLocation = serde_json::from_str(r#""following_player""#).unwrap();
+/// assert_eq!(location, SubjectLocation::FollowingPlayer);
+/// ```
+#[derive(Debug, Clone, Copy, Eq, Ord, PartialEq, PartialOrd, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(from = "SubjectLocationData", into = "SubjectLocationData")]
+pub enum SubjectLocation {
+    /// The subject is in the room with this id.
+    Room(u16),
+    /// The subject isn't anywhere in the game world.
+    Nowhere,
+    /// The subject follows the player from room to room.
+    FollowingPlayer,
+}
+
+/// Serialized form of [SubjectLocation] so rooms can be
+/// written as plain ids.
+#[derive(Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(untagged)]
+enum SubjectLocationData {
+    Room(u16),
+    Other(SubjectLocationKeyword),
+}
+
+#[derive(Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
+enum SubjectLocationKeyword {
+    Nowhere,
+    FollowingPlayer,
+}
+
+impl From<SubjectLocationData> for SubjectLocation {
+    fn from(data: SubjectLocationData) -> Self {
+        match data {
+            SubjectLocationData::Room(room_id) => SubjectLocation::Room(room_id),
+            SubjectLocationData::Other(SubjectLocationKeyword::Nowhere) => SubjectLocation::Nowhere,
+            SubjectLocationData::Other(SubjectLocationKeyword::FollowingPlayer) => {
+                SubjectLocation::FollowingPlayer
+            }
+        }
+    }
+}
+
+impl From<SubjectLocation> for SubjectLocationData {
+    fn from(location: SubjectLocation) -> Self {
+        match location {
+            SubjectLocation::Room(room_id) => SubjectLocationData::Room(room_id),
+            SubjectLocation::Nowhere => SubjectLocationData::Other(SubjectLocationKeyword::Nowhere),
+            SubjectLocation::FollowingPlayer => {
+                SubjectLocationData::Other(SubjectLocationKeyword::FollowingPlayer)
+            }
+        }
+    }
+}
+
+/// The ways a subject can move around on its own.
+#[derive(Debug, Clone, Copy, Eq, Ord, PartialEq, PartialOrd, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum SubjectMovement {
+    /// The subject starts following the player once the
+    /// player leaves a room they share.
+    Follow,
+    /// The subject walks the rooms listed in the behavior
+    /// in order, going back to the first room after the last.
+    Patrol,
+    /// The subject takes a random exit from the room it is in.
+    /// If the behavior lists any rooms, the subject only
+    /// wanders between those rooms.
+    Wander,
+}
+
+/// Describes how a subject moves around the game by itself.
+/// Subjects move at the end of each turn, and the player is told
+/// when a subject arrives in or leaves the room they are in.
+///
+/// The randomness used by wandering subjects comes from the
+/// random number generator stored in the game state, so a game
+/// started with the same seed will always play out the same way.
+///
+/// # Examples:
+///
+/// A guard that starts patrolling rooms 1, 2, and 3, moving
+/// every other turn, once event 4 is completed:
+/// ```rust
+/// # use nightrunner_lib::config::{SubjectBehavior, SubjectMovement};
+/// let behavior = SubjectBehavior {
+///     movement: SubjectMovement::Patrol,
+///     rooms: vec![1, 2, 3],
+///     every: 2,
+///     after_event: Some(4),
+/// };
+/// ```
+#[derive(Debug, Clone, Eq, Ord, PartialEq, PartialOrd, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
+pub struct SubjectBehavior {
+    /// How the subject moves.
+    pub movement: SubjectMovement,
+    /// The patrol route, or the rooms a wandering subject
+    /// is allowed to enter. Not used by `Follow`.
+    #[serde(default)]
+    pub rooms: Vec<u16>,
+    /// The subject moves once every this many turns.
+    /// Not used by `Follow`.
+    #[serde(default = "SubjectBehavior::default_every")]
+    pub every: u32,
+    /// The behavior only starts once this event is completed.
+    /// When this isn't set, the behavior is active from the
+    /// start of the game.
+    #[serde(default)]
+    pub after_event: Option<u16>,
+}
+
+impl SubjectBehavior {
+    fn default_every() -> u32 {
+        1
+    }
+}
+
+/// A subject movement queued by an event. The subject is
+/// moved from wherever it currently is, keeping its runtime
+/// state.
+///
+/// # Examples:
+/// ```rust
+/// # use nightrunner_lib::config::{SubjectLocation, SubjectMove};
+/// // subject 2 leaves for room 3 when the event completes
+/// let subject_move = SubjectMove {
+///     subject_id: 2,
+///     location: SubjectLocation::Room(3),
+/// };
+/// ```
+#[derive(Debug, Clone, Eq, Ord, PartialEq, PartialOrd, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
+pub struct SubjectMove {
+    /// The subject to move.
+    pub subject_id: u16,
+    /// Where the subject should go.
+    pub location: SubjectLocation,
+}
+
 /// An event controls the flow of the game.
 /// You can have multiple events in a room.
 ///
@@ -222,7 +721,13 @@ impl std::fmt::Display for Subject {
 ///   add_subject: None,
 ///   move_subject_to_location: None,
 ///   narrative_after: None,
+///   narrative_after_room: None,
+///   narrative_changes: Vec::new(),
 ///   remove_subject: false,
+///   move_subjects: Vec::new(),
+///   at_turn: None,
+///   turns_after: None,
+///   effects: Vec::new(),
 /// };
 /// ```
 ///
@@ -251,11 +756,18 @@ impl std::fmt::Display for Subject {
 ///   add_subject: None,
 ///   move_subject_to_location: None,
 ///   narrative_after: None,
+///   narrative_after_room: None,
+///   narrative_changes: Vec::new(),
 ///   remove_subject: false,
+///   move_subjects: Vec::new(),
+///   at_turn: None,
+///   turns_after: None,
+///   effects: Vec::new(),
 /// };
 /// ```
 
 #[derive(Debug, Clone, Eq, Ord, PartialEq, PartialOrd, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "snake_case")]
 pub struct Event {
     /// The id of the event used when referencing the event.
@@ -294,6 +806,18 @@ pub struct Event {
     /// If a new narrative should be displayed after this
     /// event, this should be the id of the new narrative.
     pub narrative_after: Option<u16>,
+    /// The room whose narrative is replaced by `narrative_after`.
+    /// When this isn't set, the narrative of the room where the
+    /// event happens is replaced.
+    #[serde(default)]
+    pub narrative_after_room: Option<u16>,
+    /// Narrative changes for any room in the game, applied when
+    /// the event is completed. Unlike `narrative_after`, these
+    /// don't depend on `remove_old_narrative`, so an event in
+    /// one room can change what the player sees when they
+    /// return to another room.
+    #[serde(default)]
+    pub narrative_changes: Vec<NarrativeChange>,
     /// If the event removes an item from the inventory,
     /// this is the item id.
     pub remove_item: Option<u16>,
@@ -312,6 +836,114 @@ pub struct Event {
     /// the event also moves the subject to a different room,
     /// this is the new room id.
     pub move_subject_to_location: Option<u16>,
+    /// Subjects moved by this event. Unlike `remove_subject`,
+    /// these can be any subject in the game, wherever they are.
+    #[serde(default)]
+    pub move_subjects: Vec<SubjectMove>,
+    /// If set, the event happens on its own once this turn
+    /// is reached, wherever the player is.
+    #[serde(default)]
+    pub at_turn: Option<u32>,
+    /// If set, the event happens on its own a number of turns
+    /// after another event was completed, wherever the player is.
+    #[serde(default)]
+    pub turns_after: Option<TurnsAfterEvent>,
+    /// Effects applied when the event is completed, such as
+    /// changing a subject's disposition.
+    #[serde(default)]
+    pub effects: Vec<Effect>,
+}
+
+impl Event {
+    /// Returns true if the event happens on its own after
+    /// some turns instead of being triggered by the player.
+    pub fn is_timed(&self) -> bool {
+        self.at_turn.is_some() || self.turns_after.is_some()
+    }
+}
+
+/// Delay for an event that should happen some turns
+/// after another event.
+///
+/// # Examples:
+/// ```rust
+/// # use nightrunner_lib::config::TurnsAfterEvent;
+/// // the event happens 3 turns after event 1 is completed
+/// let delay = TurnsAfterEvent {
+///     event_id: 1,
+///     turns: 3,
+/// };
+/// ```
+#[derive(Debug, Clone, Copy, Eq, Ord, PartialEq, PartialOrd, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
+pub struct TurnsAfterEvent {
+    /// The event that starts the countdown.
+    pub event_id: u16,
+    /// How many turns after that event this one happens.
+    pub turns: u32,
+}
+
+/// Game wide settings.
+///
+/// These are read from the optional `settings.yml` file
+/// or the `settings` field in JSON data. When missing,
+/// the defaults are used.
+///
+/// # Examples:
+/// ```rust
+/// # use nightrunner_lib::config::Settings;
+/// // looking around, asking for help and checking the
+/// // inventory don't make time pass
+/// let settings = Settings {
+///     informational_commands_take_turn: false,
+///     ..Settings::default()
+/// };
+/// ```
+#[derive(Debug, Clone, Eq, PartialEq, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case", default)]
+pub struct Settings {
+    /// Whether `look`, `help` and `inventory` commands advance
+    /// the turn counter. Default is true.
+    pub informational_commands_take_turn: bool,
+    /// Subjects with a disposition at or below this value
+    /// are hostile. Default is -10.
+    pub hostile_disposition: i32,
+    /// Subjects with a disposition at or above this value
+    /// are friendly. Default is 10.
+    pub friendly_disposition: i32,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Settings {
+            informational_commands_take_turn: true,
+            hostile_disposition: -10,
+            friendly_disposition: 10,
+        }
+    }
+}
+
+/// A change to the narrative of a room, queued by an event.
+///
+/// # Examples:
+/// ```rust
+/// # use nightrunner_lib::config::NarrativeChange;
+/// // after the event, room 1 will display narrative 5
+/// let change = NarrativeChange {
+///     room_id: 1,
+///     narrative: 5,
+/// };
+/// ```
+#[derive(Debug, Clone, Eq, Ord, PartialEq, PartialOrd, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
+pub struct NarrativeChange {
+    /// The room whose narrative should change.
+    pub room_id: u16,
+    /// The id of the narrative the room should display.
+    pub narrative: u16,
 }
 
 impl Event {
@@ -333,6 +965,7 @@ impl Event {
 /// It contains the name of the item, the description
 /// and whether or not the item can be picked up.
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "snake_case")]
 pub struct Item {
     /// The id of the item used when referencing the item.
@@ -349,6 +982,70 @@ pub struct Item {
     /// if an item can't be picked up you will
     /// want to use a subject instead.
     pub can_pick: bool,
+    /// What the player can do with the item using the
+    /// built-in verbs, such as eating or wearing it.
+    #[serde(default)]
+    pub capabilities: ItemCapabilities,
+}
+
+/// Describes what the built-in verbs can do with an item.
+/// Items without a capability for a verb are left to the
+/// events using that verb.
+///
+/// # Examples:
+/// ```rust
+/// # use nightrunner_lib::config::ItemCapabilities;
+/// // a locked chest that can be opened with item 5
+/// let yaml = r#"
+/// openable: true
+/// container: true
+/// key: 5
+/// locked: true
+/// "#;
+/// let capabilities: ItemCapabilities = serde_yaml::from_str(yaml).unwrap();
+/// assert!(capabilities.container);
+/// assert!(!capabilities.edible);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case", default)]
+pub struct ItemCapabilities {
+    /// The item can be eaten, which uses it up.
+    pub edible: bool,
+    /// The item can be drunk, which uses it up.
+    pub drinkable: bool,
+    /// The text displayed when the player reads the item.
+    pub text: Option<String>,
+    /// The slot the item takes when worn, such as "head".
+    /// Only one item can be worn in each slot.
+    pub wear_slot: Option<String>,
+    /// The item can be opened and closed.
+    pub openable: bool,
+    /// Other items can be put in the item while it's open.
+    pub container: bool,
+    /// The item can be locked and unlocked with this item.
+    pub key: Option<u16>,
+    /// The item is locked when the game starts.
+    pub locked: bool,
+    /// The item can be turned on and off.
+    pub switchable: bool,
+    /// Effects applied when the item is eaten, drunk, or read.
+    pub effects: Vec<Effect>,
+}
+
+/// The state of an item that changes while playing,
+/// such as a door being open or a lamp being on.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub struct ItemState {
+    /// The item is open.
+    pub open: bool,
+    /// The item is locked.
+    pub locked: bool,
+    /// The item is turned on.
+    pub on: bool,
+    /// Items put inside the item.
+    pub contents: Vec<Item>,
 }
 
 impl std::fmt::Display for Item {
@@ -364,8 +1061,10 @@ impl std::fmt::Display for Item {
 /// have to be sent with the JSON data to deserialize a Config
 /// struct. Instead we deserialize the data into this struct and
 /// then we can just copy the fields into the Config struct.
-#[derive(Deserialize, Debug)]
+#[derive(Deserialize, Serialize, Debug)]
 struct ConfigData {
+    #[serde(default)]
+    format_version: u32,
     items: Vec<Item>,
     narratives: Vec<Narrative>,
     room_blueprints: Vec<RoomBlueprint>,
@@ -373,6 +1072,14 @@ struct ConfigData {
     events: Vec<Event>,
     intro: String,
     allowed_verbs: Vec<Verb>,
+    #[serde(default)]
+    settings: Settings,
+    #[serde(default)]
+    daemons: Vec<Daemon>,
+    #[serde(default)]
+    variables: BTreeMap<String, i32>,
+    #[serde(default)]
+    stats: PlayerStats,
 }
 
 /// This holds the configurations for the game.
@@ -392,6 +1099,17 @@ struct ConfigData {
 /// you should use the Config::init_yaml or the
 /// Config::init_json functions to load the data
 /// from a YAML file or JSON data.
+///
+/// Entries can have symbolic ids, like `id: brass_key`,
+/// instead of numbers, and other entries can use them
+/// wherever they reference the entry, like
+/// `required_item: brass_key`. When the game is loaded
+/// each symbolic id is given the next number after the
+/// highest numeric id in its section, in the order the
+/// entries are written, so the first room written is
+/// still the room the game starts in when every room
+/// has a symbolic id. Referencing a symbolic id no entry
+/// has is an error.
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize, Eq)]
 #[serde(rename_all = "snake_case")]
 pub struct Config {
@@ -415,6 +1133,23 @@ pub struct Config {
     pub events: Vec<Event>,
     /// The intro text to be displayed when the game starts.
     pub intro: String,
+    /// Game wide settings.
+    #[serde(default)]
+    pub settings: Settings,
+    /// Rules checked at the end of every turn.
+    #[serde(default)]
+    pub daemons: Vec<Daemon>,
+    /// Game variables and their values when the game starts.
+    #[serde(default)]
+    pub variables: BTreeMap<String, i32>,
+    /// The stats the player starts the game with.
+    #[serde(default)]
+    pub stats: PlayerStats,
+    /// The changes made to bring game data written for an
+    /// older version of the library up to the current
+    /// layout. See [Migration].
+    #[serde(skip)]
+    pub migrations: Vec<Migration>,
     pub(crate) room_blueprints: Vec<RoomBlueprint>,
     // /// All the possible rooms in the game.
     // pub rooms: Vec<Room>,
@@ -442,6 +1177,11 @@ impl Default for Config {
             room_blueprints: Vec::new(),
             events: Vec::new(),
             intro: String::new(),
+            settings: Settings::default(),
+            daemons: Vec::new(),
+            variables: BTreeMap::new(),
+            stats: PlayerStats::default(),
+            migrations: Vec::new(),
         }
     }
 }
@@ -453,7 +1193,7 @@ impl Config {
     /// This is useful for web frontends
     /// Arguments:
     /// * `data` - serialized JSON to be used
-    /// for the game configuration.
+    ///   for the game configuration.
     ///
     /// ## Example:
     /// ```rust
@@ -691,16 +1431,45 @@ impl Config {
     /// }"#;
     /// ```
     pub fn from_json(data: &str) -> Config {
-        let config_data: ConfigData = serde_json::from_str(data).unwrap();
+        Config::from_value(serde_json::from_str(data).unwrap()).unwrap()
+    }
+    /// Loads config from a single YAML document with the
+    /// same layout as the JSON data, as written by
+    /// `Config::to_yaml`.
+    ///
+    /// ## Example:
+    /// ```rust
+    /// # use nightrunner_lib::config::Config;
+    /// let config = Config::from_path("./fixtures/");
+    /// assert_eq!(Config::from_yaml(&config.to_yaml()), config);
+    /// ```
+    pub fn from_yaml(data: &str) -> Config {
+        Config::from_value(serde_yaml::from_str(data).unwrap()).unwrap()
+    }
+    /// Loads config from game data in the layout of the JSON
+    /// data, after upgrading it to the current format version
+    /// and replacing its symbolic ids with numeric ids.
+    fn from_value(mut data: serde_json::Value) -> NRResult<Config> {
+        let migrations = migrate(&mut data)?;
+        resolve_symbols(&mut data)?;
+        let config_data: ConfigData = serde_json::from_value(data)?;
+        Ok(Config {
+            migrations,
+            ..Config::from_data(config_data)
+        })
+    }
+    fn from_data(config_data: ConfigData) -> Config {
         let mut items = config_data.items;
         let mut verbs = config_data.allowed_verbs;
         let mut subjects = config_data.subjects;
         let mut narratives = config_data.narratives;
         let mut events = config_data.events;
         let mut room_blueprints = config_data.room_blueprints;
+        let mut daemons = config_data.daemons;
 
-        room_blueprints.sort_by(|a, b| a.id.cmp(&b.id));
-        events.sort_by(|a, b| a.id.cmp(&b.id));
+        room_blueprints.sort_by_key(|a| a.id);
+        events.sort_by_key(|a| a.id);
+        daemons.sort_by_key(|a| a.id);
         verbs.sort();
         items.sort();
         subjects.sort();
@@ -717,6 +1486,11 @@ impl Config {
             narratives,
             events,
             intro: config_data.intro,
+            settings: config_data.settings,
+            daemons,
+            variables: config_data.variables,
+            stats: config_data.stats,
+            migrations: Vec::new(),
             room_blueprints,
         }
     }
@@ -739,6 +1513,13 @@ impl Config {
     /// * `intro.yml`
     /// * `rooms.yml`
     ///
+    /// Optional files:
+    /// * `settings.yml`
+    /// * `daemons.yml`
+    /// * `variables.yml`
+    /// * `stats.yml`
+    /// * `format_version.yml`
+    ///
     /// ## Example:
     /// ```rust
     /// # use nightrunner_lib::config::Config;
@@ -749,55 +1530,22 @@ impl Config {
     /// fixtures directory used for unit tests.
     pub fn from_path(path: &str) -> Config {
         let error_message = format!("Could not find config file at {}", path);
-        let narratives_config =
-            std::fs::read_to_string(format!("{}narratives.yml", path)).expect(&error_message);
-        let items_config =
-            std::fs::read_to_string(format!("{}items.yml", path)).expect(&error_message);
-        let rooms_config =
-            std::fs::read_to_string(format!("{}rooms.yml", path)).expect(&error_message);
-        let allowed_verbs_config =
-            std::fs::read_to_string(format!("{}verbs.yml", path)).expect(&error_message);
-        let subjects_config =
-            std::fs::read_to_string(format!("{}subjects.yml", path)).expect(&error_message);
-        let events_config =
-            std::fs::read_to_string(format!("{}events.yml", path)).expect(&error_message);
-        let intro_config =
-            std::fs::read_to_string(format!("{}intro.yml", path)).expect(&error_message);
-
-        let mut narratives: Vec<Narrative> = serde_yaml::from_str(&narratives_config[..]).unwrap();
-        narratives.sort_by(|a, b| a.id.cmp(&b.id));
-
-        let mut items: Vec<Item> = serde_yaml::from_str(&items_config[..]).unwrap();
-        items.sort_by(|a, b| a.id.cmp(&b.id));
-
-        let mut room_blueprints: Vec<RoomBlueprint> =
-            serde_yaml::from_str(&rooms_config[..]).unwrap();
-        room_blueprints.sort_by(|a, b| a.id.cmp(&b.id));
-
-        let mut events: Vec<Event> = serde_yaml::from_str(&events_config[..]).unwrap();
-        events.sort_by(|a, b| a.id.cmp(&b.id));
-
-        let mut subjects: Vec<Subject> = serde_yaml::from_str(&subjects_config[..]).unwrap();
-        subjects.sort_by(|a, b| a.id.cmp(&b.id));
-
-        let mut verbs: Vec<Verb> = serde_yaml::from_str(&allowed_verbs_config[..]).unwrap();
-        verbs.sort_by(|a, b| a.id.cmp(&b.id));
-
-        let intro: String = serde_yaml::from_str(&intro_config[..]).unwrap();
-
-        Config {
-            allowed_determiners: AllowedDeterminers::init(),
-            allowed_prepositions: AllowedPrepositions::init(),
-            allowed_movements: AllowedMovements::init(),
-            allowed_directions: AllowedDirections::init(),
-            allowed_verbs: verbs,
-            items,
-            subjects,
-            narratives,
-            events,
-            intro,
-            room_blueprints,
-        }
+        let sections = YamlSections::from_folder(path).expect(&error_message);
+        Config::from_yaml_sections(&sections).unwrap()
+    }
+    /// Loads config from the YAML files in a folder, like
+    /// `Config::from_path`, but returns an error instead of
+    /// panicking when a file is missing or can't be read.
+    ///
+    /// ## Example:
+    /// ```rust
+    /// # use nightrunner_lib::config::Config;
+    /// let config = Config::try_from_path("./fixtures/").unwrap();
+    /// assert_eq!(config, Config::from_path("./fixtures/"));
+    /// assert!(Config::try_from_path("./missing/").is_err());
+    /// ```
+    pub fn try_from_path(path: &str) -> NRResult<Config> {
+        Config::from_yaml_sections(&YamlSections::from_folder(path)?)
     }
 }
 
@@ -819,9 +1567,45 @@ pub struct State {
     /// This is so we can keep track of updates to the room structs
     /// but keep the config struct clean.
     pub rooms: Vec<Room>,
+    /// Subjects that aren't currently anywhere in the game
+    /// world, either because no room lists them or because
+    /// an event removed them.
+    pub offstage_subjects: Vec<Subject>,
+    /// Subjects following the player from room to room.
+    pub followers: Vec<Subject>,
+    /// Number of turns played so far.
+    pub turn: u32,
+    /// The turn in which each completed event was completed,
+    /// used to time events that happen after other events.
+    pub event_turns: BTreeMap<u16, u32>,
+    /// Number of turns the player has stayed in the current room.
+    pub turns_in_room: u32,
+    /// Current values of the game variables.
+    pub variables: BTreeMap<String, i32>,
+    /// The state of the items that were opened, locked, turned
+    /// on, or filled. Items that aren't here are in the state
+    /// given by their capabilities.
+    pub item_states: BTreeMap<u16, ItemState>,
+    /// The conversation the player is having, if any. While
+    /// this is set, numbers typed by the player pick one of
+    /// the dialogue choices.
+    pub dialogue: Option<ActiveDialogue>,
+    /// How the game ended, once it has. While this is set
+    /// the game refuses any command other than quitting,
+    /// restarting, undoing, or redoing.
+    pub game_over: Option<GameOver>,
+    /// The seed used for the random number generator.
+    /// Starting a game with the same seed and inputs will
+    /// always produce the same results.
+    pub seed: u64,
+    /// Random number generator used for anything random
+    /// happening in the game, such as wandering subjects.
+    pub rng: StdRng,
     /// This Config struct holds all the game data
-    /// such as verbs, items, etc.
-    pub config: Config,
+    /// such as verbs, items, etc. It never changes during
+    /// the game, so it's shared between the state and every
+    /// copy of it kept for undoing commands.
+    pub config: Arc<Config>,
 }
 
 impl State {
@@ -840,6 +1624,33 @@ impl State {
     /// let state2 = State::init(config2);
     /// ```
     pub fn init(config: Config) -> Self {
+        Self::init_with_seed(config, 0)
+    }
+    /// Same as `State::init`, but seeds the random number
+    /// generator with the given seed instead of `0`.
+    ///
+    /// ## Example:
+    /// ```rust
+    /// # use nightrunner_lib::config::{Config, State};
+    /// let state = State::init_with_seed(Config::from_path("./fixtures/"), 42);
+    /// assert_eq!(state.seed, 42);
+    /// ```
+    pub fn init_with_seed(config: Config, seed: u64) -> Self {
+        Self::init_shared(Arc::new(config), seed)
+    }
+    /// Same as `State::init_with_seed`, but uses a config
+    /// that is already shared, such as the config of
+    /// another state, instead of taking ownership of one.
+    ///
+    /// ## Example:
+    /// ```rust
+    /// # use nightrunner_lib::config::{Config, State};
+    /// # use std::sync::Arc;
+    /// let state = State::init(Config::from_path("./fixtures/"));
+    /// let new_game = State::init_shared(Arc::clone(&state.config), 42);
+    /// assert!(Arc::ptr_eq(&state.config, &new_game.config));
+    /// ```
+    pub fn init_shared(config: Arc<Config>, seed: u64) -> Self {
         let items = &config.items;
         let subjects = &config.subjects;
         let events = &config.events;
@@ -851,6 +1662,15 @@ impl State {
         //     .collect::<Vec<Room>>();
         let room_blueprints = &config.room_blueprints;
         let rooms = Room::build_rooms(room_blueprints, events, items, subjects);
+        let offstage_subjects = subjects
+            .iter()
+            .filter(|subject| {
+                !rooms
+                    .iter()
+                    .any(|room| room.subjects.iter().any(|s| s.id == subject.id))
+            })
+            .cloned()
+            .collect();
         // .iter()
         // .map(|room_blueprint| {
         //     let mut room = Room {
@@ -905,9 +1725,22 @@ impl State {
             input: String::new(),
             current_room: 1,
             player: Player {
-                inventory: Storage::default(),
+                health: config.stats.health,
+                stats: config.stats.custom.clone(),
+                ..Player::default()
             },
             rooms,
+            offstage_subjects,
+            followers: vec![],
+            turn: 0,
+            event_turns: BTreeMap::new(),
+            turns_in_room: 0,
+            item_states: BTreeMap::new(),
+            dialogue: None,
+            game_over: None,
+            variables: config.variables.clone(),
+            seed,
+            rng: StdRng::seed_from_u64(seed),
             config,
         }
         // Rc::new(RefCell::new(state))
@@ -927,7 +1760,9 @@ impl State {
             .unwrap();
         narrative.clone()
     }
-    /// Sets the current room's narrative
+    /// Sets the current room's narrative. See
+    /// `State::set_room_narrative` for a version that returns
+    /// an error instead of panicking.
     pub fn set_narrative(&mut self, narrative_id: u16) {
         let room = self
             .rooms
@@ -936,6 +1771,30 @@ impl State {
             .unwrap();
         room.narrative = narrative_id;
     }
+    /// Sets the narrative of any room in the game.
+    ///
+    /// Returns an error if either the room or the narrative
+    /// don't exist.
+    ///
+    /// ## Example:
+    /// ```rust
+    /// # use nightrunner_lib::config::{Config, State};
+    /// let mut state = State::init(Config::from_path("./fixtures/"));
+    /// assert!(state.set_room_narrative(2, 3).is_ok());
+    /// assert!(state.set_room_narrative(42, 3).is_err());
+    /// ```
+    pub fn set_room_narrative(&mut self, room_id: u16, narrative_id: u16) -> NRResult<()> {
+        if !self.config.narratives.iter().any(|n| n.id == narrative_id) {
+            return Err(InvalidNarrative.into());
+        }
+        let room = self
+            .rooms
+            .iter_mut()
+            .find(|r| r.id == room_id)
+            .ok_or(InvalidRoom)?;
+        room.narrative = narrative_id;
+        Ok(())
+    }
     /// Checks if an event is completed.
     pub fn is_event_completed(&self, event_id: u16) -> bool {
         for room in self.rooms.iter() {
@@ -959,42 +1818,164 @@ impl State {
             }
         }
     }
-    /// Moves a subject to a different room.
-    pub fn move_subject(&mut self, subject_id: u16, location: u16) -> NRResult<()> {
-        self.remove_subject(subject_id)?;
-        let subject = self
-            .config
-            .subjects
+    /// Returns where a subject currently is, or `None` if
+    /// the subject doesn't exist.
+    ///
+    /// ## Example:
+    /// ```rust
+    /// # use nightrunner_lib::config::{Config, State, SubjectLocation};
+    /// let state = State::init(Config::from_path("./fixtures/"));
+    /// assert_eq!(state.subject_location(2), Some(SubjectLocation::Room(2)));
+    /// assert_eq!(state.subject_location(42), None);
+    /// ```
+    pub fn subject_location(&self, subject_id: u16) -> Option<SubjectLocation> {
+        if let Some(room) = self
+            .rooms
             .iter()
-            .find(|s| s.id == subject_id)
-            .ok_or(InvalidSubject)?;
-        self.rooms
-            .iter_mut()
-            .find(|r| r.id == location)
-            .ok_or(InvalidRoom)?
-            .add_subject(subject.clone());
-        Ok(())
+            .find(|r| r.subjects.iter().any(|s| s.id == subject_id))
+        {
+            Some(SubjectLocation::Room(room.id))
+        } else if self.followers.iter().any(|s| s.id == subject_id) {
+            Some(SubjectLocation::FollowingPlayer)
+        } else if self.offstage_subjects.iter().any(|s| s.id == subject_id) {
+            Some(SubjectLocation::Nowhere)
+        } else {
+            None
+        }
     }
-    /// Removes a subject from the current room.
-    pub fn remove_subject(&mut self, subject_id: u16) -> NRResult<()> {
-        let current_room = self
+    /// Returns the subjects in a room. The subjects following
+    /// the player are included when `room_id` is the current room.
+    pub fn subjects_in_room(&self, room_id: u16) -> Vec<&Subject> {
+        let mut subjects = self
             .rooms
-            .iter_mut()
-            .find(|r| r.id == self.current_room)
-            .ok_or(InvalidRoom)?;
-        current_room.remove_subject(subject_id);
+            .iter()
+            .filter(|r| r.id == room_id)
+            .flat_map(|r| r.subjects.iter())
+            .collect::<Vec<&Subject>>();
+        if room_id == self.current_room {
+            subjects.extend(self.followers.iter());
+        }
+        subjects
+    }
+    /// Moves a subject to a new location.
+    ///
+    /// The subject is taken from wherever it currently is,
+    /// so any runtime state it carries is kept.
+    pub fn set_subject_location(
+        &mut self,
+        subject_id: u16,
+        location: SubjectLocation,
+    ) -> NRResult<()> {
+        if let SubjectLocation::Room(room_id) = location {
+            if !self.rooms.iter().any(|r| r.id == room_id) {
+                return Err(InvalidRoom.into());
+            }
+        }
+        let subject = self.take_subject(subject_id)?;
+        match location {
+            SubjectLocation::Room(room_id) => self
+                .rooms
+                .iter_mut()
+                .find(|r| r.id == room_id)
+                .ok_or(InvalidRoom)?
+                .add_subject(subject),
+            SubjectLocation::FollowingPlayer => self.followers.push(subject),
+            SubjectLocation::Nowhere => self.offstage_subjects.push(subject),
+        }
         Ok(())
     }
-    /// Adds a subject to the current room.
+    /// Moves a subject to a different room.
+    pub fn move_subject(&mut self, subject_id: u16, location: u16) -> NRResult<()> {
+        self.set_subject_location(subject_id, SubjectLocation::Room(location))
+    }
+    /// Removes a subject from the room it is in.
+    pub fn remove_subject(&mut self, subject_id: u16) -> NRResult<()> {
+        self.set_subject_location(subject_id, SubjectLocation::Nowhere)
+    }
+    /// Adds a subject to the current room. If the subject is
+    /// already somewhere in the game it's taken from there, so
+    /// it's never in two places. Use `State::add_subject_by_id`
+    /// to keep the runtime state of the subject in the game.
     pub fn add_subject(&mut self, subject: Subject) -> NRResult<()> {
-        let current_room = self
-            .rooms
+        if !self.rooms.iter().any(|r| r.id == self.current_room) {
+            return Err(InvalidRoom.into());
+        }
+        let _ = self.take_subject(subject.id);
+        self.rooms
             .iter_mut()
             .find(|r| r.id == self.current_room)
-            .ok_or(InvalidRoom)?;
-        current_room.add_subject(subject);
+            .ok_or(InvalidRoom)?
+            .add_subject(subject);
         Ok(())
     }
+    /// Moves a subject from wherever it is to the current room,
+    /// keeping its runtime state.
+    pub fn add_subject_by_id(&mut self, subject_id: u16) -> NRResult<()> {
+        self.set_subject_location(subject_id, SubjectLocation::Room(self.current_room))
+    }
+    /// Returns the current state of an item.
+    pub fn item_state(&self, item_id: u16) -> ItemState {
+        self.item_states
+            .get(&item_id)
+            .cloned()
+            .unwrap_or_else(|| self.initial_item_state(item_id))
+    }
+    /// Same as `State::item_state`, but returns a mutable
+    /// reference so the item's state can change.
+    pub fn item_state_mut(&mut self, item_id: u16) -> &mut ItemState {
+        let initial_state = self.initial_item_state(item_id);
+        self.item_states.entry(item_id).or_insert(initial_state)
+    }
+    fn initial_item_state(&self, item_id: u16) -> ItemState {
+        let locked = self
+            .config
+            .items
+            .iter()
+            .find(|item| item.id == item_id)
+            .is_some_and(|item| item.capabilities.locked);
+        ItemState {
+            locked,
+            ..ItemState::default()
+        }
+    }
+    /// Returns the subject wherever it currently is, with its
+    /// current disposition and flags.
+    pub fn subject(&self, subject_id: u16) -> Option<&Subject> {
+        self.rooms
+            .iter()
+            .flat_map(|room| room.subjects.iter())
+            .chain(self.followers.iter())
+            .chain(self.offstage_subjects.iter())
+            .find(|s| s.id == subject_id)
+    }
+    /// Same as `State::subject`, but returns a mutable
+    /// reference so the subject's attributes can change.
+    pub fn subject_mut(&mut self, subject_id: u16) -> Option<&mut Subject> {
+        self.rooms
+            .iter_mut()
+            .flat_map(|room| room.subjects.iter_mut())
+            .chain(self.followers.iter_mut())
+            .chain(self.offstage_subjects.iter_mut())
+            .find(|s| s.id == subject_id)
+    }
+    fn take_subject(&mut self, subject_id: u16) -> NRResult<Subject> {
+        for room in self.rooms.iter_mut() {
+            if let Some(index) = room.subjects.iter().position(|s| s.id == subject_id) {
+                return Ok(room.subjects.remove(index));
+            }
+        }
+        if let Some(index) = self.followers.iter().position(|s| s.id == subject_id) {
+            return Ok(self.followers.remove(index));
+        }
+        if let Some(index) = self
+            .offstage_subjects
+            .iter()
+            .position(|s| s.id == subject_id)
+        {
+            return Ok(self.offstage_subjects.remove(index));
+        }
+        Err(InvalidSubject.into())
+    }
 }
 
 /// This struct represents the storage for both the player
@@ -1039,6 +2020,38 @@ impl Storage {
 pub struct Player {
     /// The player's inventory
     pub inventory: Storage,
+    /// Ids of the items from the inventory the player is wearing.
+    pub worn: Vec<u16>,
+    /// The player's health, if the game tracks it.
+    pub health: Option<i32>,
+    /// The player's other stats.
+    pub stats: BTreeMap<String, i32>,
+    /// The points the player was awarded so far.
+    pub awards: Vec<Award>,
+}
+
+impl Player {
+    /// Returns the player's score, adding up the points
+    /// of every award.
+    pub fn score(&self) -> i32 {
+        self.awards
+            .iter()
+            .map(|award| award.points)
+            .fold(0, i32::saturating_add)
+    }
+    /// Returns the value of one of the player's stats.
+    /// Stats that were never set are `0`.
+    pub fn stat(&self, name: &str) -> i32 {
+        self.stats.get(name).copied().unwrap_or(0)
+    }
+    /// Removes an item from the player's inventory, taking
+    /// it off first if the player is wearing it, and returns
+    /// the item removed.
+    pub fn remove_item(&mut self, item: Item) -> NRResult<Item> {
+        let item = self.inventory.remove_item(item)?;
+        self.worn.retain(|id| *id != item.id);
+        Ok(item)
+    }
 }
 
 #[cfg(test)]
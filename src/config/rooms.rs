@@ -3,6 +3,7 @@ use serde::{Deserialize, Serialize};
 use super::{directions::Directions, Event, Item, Storage, Subject};
 
 #[derive(Serialize, Deserialize, Debug, Clone, Eq, Ord, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub(crate) struct RoomBlueprint {
     pub(crate) id: u16,
     pub(crate) name: String,
@@ -84,6 +85,9 @@ impl Room {
         items: &[Item],
         subjects: &[Subject],
     ) -> Vec<Room> {
+        // A subject can only be in one place at a time, so
+        // it is placed in the first room that lists it.
+        let mut placed_subjects: Vec<u16> = vec![];
         blueprints
             .iter()
             .map(|room_blueprint| {
@@ -103,9 +107,13 @@ impl Room {
                     }
                 }
                 for subject_id in &room_blueprint.subject_ids {
+                    if placed_subjects.contains(subject_id) {
+                        continue;
+                    }
                     if let Some(subject) = subjects.iter().find(|subject| subject.id == *subject_id)
                     {
                         room.subjects.push(subject.clone());
+                        placed_subjects.push(*subject_id);
                     }
                 }
                 for event in events {
@@ -121,6 +129,7 @@ impl Room {
 
 /// This struct represents exits from a room.
 #[derive(Debug, Clone, Deserialize, Serialize, Eq, Ord, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "snake_case")]
 pub struct Exits {
     /// The room that the exit leads to.
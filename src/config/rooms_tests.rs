@@ -1,7 +1,7 @@
 use super::*;
 use crate::{
     config::directions::Directions,
-    config::{rooms::Room, Item},
+    config::{rooms::Room, Item, ItemCapabilities},
 };
 #[cfg(test)]
 use pretty_assertions::assert_eq;
@@ -28,6 +28,7 @@ fn it_builds_a_room() {
             name: "item1".to_string(),
             description: "item1".to_string(),
             can_pick: false,
+            capabilities: ItemCapabilities::default(),
         }],
         &[],
     );
@@ -45,6 +46,7 @@ fn it_builds_a_room() {
                 name: "item1".to_string(),
                 description: "item1".to_string(),
                 can_pick: false,
+                capabilities: ItemCapabilities::default(),
             }],
         },
         events: vec![],
@@ -95,6 +97,7 @@ fn it_adds_item() {
         name: "text".to_owned(),
         description: "text".to_owned(),
         can_pick: true,
+        capabilities: ItemCapabilities::default(),
     };
     room.stash.add_item(item.clone());
     assert_eq!(room.stash.items.len(), 1);
@@ -107,6 +110,7 @@ fn it_removes_item() {
         name: "text".to_owned(),
         description: "text".to_owned(),
         can_pick: true,
+        capabilities: ItemCapabilities::default(),
     };
     let mut room = Room {
         id: 1,
@@ -141,6 +145,16 @@ fn it_adds_subject() {
         name: "text".to_owned(),
         description: "text".to_owned(),
         default_text: "default text".to_owned(),
+        behavior: None,
+        dialogue: None,
+        topics: vec![],
+        unknown_topic_text: None,
+        accepts: vec![],
+        refusal_text: None,
+        disposition: 0,
+        flags: vec![],
+        disposition_texts: vec![],
+        item_ids: vec![],
     };
     let mut room = Room {
         id: 1,
@@ -178,9 +192,51 @@ fn it_removes_subject() {
             name: "text".to_owned(),
             description: "text".to_owned(),
             default_text: "default text".to_owned(),
+            behavior: None,
+            dialogue: None,
+            topics: vec![],
+            unknown_topic_text: None,
+            accepts: vec![],
+            refusal_text: None,
+            disposition: 0,
+            flags: vec![],
+            disposition_texts: vec![],
+            item_ids: vec![],
         }],
     };
     assert!(!room.subjects.is_empty());
     room.remove_subject(1);
     assert!(room.subjects.is_empty());
 }
+
+#[test]
+fn it_places_subjects_in_one_room() {
+    let subject = Subject {
+        id: 1,
+        name: "text".to_owned(),
+        description: "text".to_owned(),
+        default_text: "default text".to_owned(),
+        behavior: None,
+        dialogue: None,
+        topics: vec![],
+        unknown_topic_text: None,
+        accepts: vec![],
+        refusal_text: None,
+        disposition: 0,
+        flags: vec![],
+        disposition_texts: vec![],
+        item_ids: vec![],
+    };
+    let blueprints = [1, 2].map(|id| RoomBlueprint {
+        id,
+        name: "text".to_string(),
+        description: "text".to_string(),
+        exits: vec![],
+        item_ids: vec![],
+        narrative: 1,
+        subject_ids: vec![1],
+    });
+    let rooms = Room::build_rooms(&blueprints, &[], &[], &[subject]);
+    assert_eq!(rooms[0].subjects.len(), 1);
+    assert!(rooms[1].subjects.is_empty());
+}
@@ -0,0 +1,561 @@
+use serde::{Deserialize, Serialize};
+
+use super::{Award, DispositionBand, GameOver, State, SubjectLocation};
+use crate::parser::errors::{InvalidEvent, InvalidSubject, NoItem};
+use crate::NRResult;
+
+/// A check against the current state of the game.
+///
+/// Conditions are written as maps with a `type` field
+/// naming the check, and the check's arguments.
+///
+/// # Examples:
+/// ```rust
+/// # use nightrunner_lib::config::Condition;
+/// let yaml = r#"
+/// - type: player_in_room
+///   room_id: 3
+/// - type: variable
+///   name: fuel
+///   at_most: 0
+/// "#;
+/// let conditions: Vec<Condition> = serde_yaml::from_str(yaml).unwrap();
+/// assert_eq!(conditions[0], Condition::PlayerInRoom { room_id: 3 });
+/// ```
+#[derive(Debug, Clone, Eq, Ord, PartialEq, PartialOrd, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Condition {
+    /// The player is in the given room.
+    PlayerInRoom {
+        /// The room the player has to be in.
+        room_id: u16,
+    },
+    /// The player has been in the same room for at least
+    /// this many turns.
+    TurnsInRoom {
+        /// The number of turns.
+        at_least: u32,
+    },
+    /// The current turn is a multiple of `turns`.
+    EveryTurns {
+        /// How often the condition is met.
+        turns: u32,
+    },
+    /// The event was completed.
+    EventCompleted {
+        /// The event that has to be completed.
+        event_id: u16,
+    },
+    /// The event wasn't completed yet.
+    EventNotCompleted {
+        /// The event that can't be completed.
+        event_id: u16,
+    },
+    /// The player has the item in their inventory.
+    HasItem {
+        /// The item the player has to carry.
+        item_id: u16,
+    },
+    /// The subject is at the given location.
+    SubjectAt {
+        /// The subject to check.
+        subject_id: u16,
+        /// Where the subject has to be.
+        location: SubjectLocation,
+    },
+    /// The subject's disposition is within the given bounds.
+    Disposition {
+        /// The subject to check.
+        subject_id: u16,
+        /// The lowest disposition allowed, if any.
+        #[serde(default)]
+        at_least: Option<i32>,
+        /// The highest disposition allowed, if any.
+        #[serde(default)]
+        at_most: Option<i32>,
+    },
+    /// The subject's disposition is in the given band.
+    DispositionBand {
+        /// The subject to check.
+        subject_id: u16,
+        /// The band the subject has to be in.
+        band: DispositionBand,
+    },
+    /// The flag is set on the subject.
+    SubjectHasFlag {
+        /// The subject to check.
+        subject_id: u16,
+        /// The flag that has to be set.
+        flag: String,
+    },
+    /// The flag isn't set on the subject.
+    SubjectLacksFlag {
+        /// The subject to check.
+        subject_id: u16,
+        /// The flag that can't be set.
+        flag: String,
+    },
+    /// A game variable is within the given bounds. Variables
+    /// that were never set are `0`.
+    Variable {
+        /// The name of the variable.
+        name: String,
+        /// The lowest value allowed, if any.
+        #[serde(default)]
+        at_least: Option<i32>,
+        /// The highest value allowed, if any.
+        #[serde(default)]
+        at_most: Option<i32>,
+    },
+    /// The player's health is within the given bounds.
+    /// Never met in games that don't track health.
+    Health {
+        /// The lowest value allowed, if any.
+        #[serde(default)]
+        at_least: Option<i32>,
+        /// The highest value allowed, if any.
+        #[serde(default)]
+        at_most: Option<i32>,
+    },
+    /// The player's score is within the given bounds.
+    Score {
+        /// The lowest value allowed, if any.
+        #[serde(default)]
+        at_least: Option<i32>,
+        /// The highest value allowed, if any.
+        #[serde(default)]
+        at_most: Option<i32>,
+    },
+    /// One of the player's stats is within the given bounds.
+    /// Stats that were never set are `0`.
+    Stat {
+        /// The name of the stat.
+        name: String,
+        /// The lowest value allowed, if any.
+        #[serde(default)]
+        at_least: Option<i32>,
+        /// The highest value allowed, if any.
+        #[serde(default)]
+        at_most: Option<i32>,
+    },
+}
+
+impl Condition {
+    /// Returns true if the condition holds for the given state.
+    pub fn is_met(&self, state: &State) -> bool {
+        match self {
+            Condition::PlayerInRoom { room_id } => state.current_room == *room_id,
+            Condition::TurnsInRoom { at_least } => state.turns_in_room >= *at_least,
+            Condition::EveryTurns { turns } => *turns != 0 && state.turn.is_multiple_of(*turns),
+            Condition::EventCompleted { event_id } => state.is_event_completed(*event_id),
+            Condition::EventNotCompleted { event_id } => !state.is_event_completed(*event_id),
+            Condition::HasItem { item_id } => state
+                .player
+                .inventory
+                .items
+                .iter()
+                .any(|item| item.id == *item_id),
+            Condition::SubjectAt {
+                subject_id,
+                location,
+            } => state.subject_location(*subject_id) == Some(*location),
+            Condition::Disposition {
+                subject_id,
+                at_least,
+                at_most,
+            } => state.subject(*subject_id).is_some_and(|subject| {
+                at_least.is_none_or(|min| subject.disposition >= min)
+                    && at_most.is_none_or(|max| subject.disposition <= max)
+            }),
+            Condition::DispositionBand { subject_id, band } => state
+                .subject(*subject_id)
+                .is_some_and(|subject| subject.disposition_band(&state.config.settings) == *band),
+            Condition::SubjectHasFlag { subject_id, flag } => state
+                .subject(*subject_id)
+                .is_some_and(|subject| subject.has_flag(flag)),
+            Condition::SubjectLacksFlag { subject_id, flag } => state
+                .subject(*subject_id)
+                .is_some_and(|subject| !subject.has_flag(flag)),
+            Condition::Variable {
+                name,
+                at_least,
+                at_most,
+            } => within(state.variable(name), *at_least, *at_most),
+            Condition::Health { at_least, at_most } => state
+                .player
+                .health
+                .is_some_and(|health| within(health, *at_least, *at_most)),
+            Condition::Score { at_least, at_most } => {
+                within(state.player.score(), *at_least, *at_most)
+            }
+            Condition::Stat {
+                name,
+                at_least,
+                at_most,
+            } => within(state.player.stat(name), *at_least, *at_most),
+        }
+    }
+}
+
+fn within(value: i32, at_least: Option<i32>, at_most: Option<i32>) -> bool {
+    at_least.is_none_or(|min| value >= min) && at_most.is_none_or(|max| value <= max)
+}
+
+/// A change to the state of the game.
+///
+/// Like conditions, effects are written as maps with a
+/// `type` field naming the effect.
+///
+/// # Examples:
+/// ```rust
+/// # use nightrunner_lib::config::Effect;
+/// let yaml = r#"
+/// - type: change_variable
+///   name: fuel
+///   by: -1
+/// - type: message
+///   text: The lamp flickers.
+/// "#;
+/// let effects: Vec<Effect> = serde_yaml::from_str(yaml).unwrap();
+/// assert_eq!(
+///     effects[1],
+///     Effect::Message {
+///         text: "The lamp flickers.".to_string()
+///     }
+/// );
+/// ```
+#[derive(Debug, Clone, Eq, Ord, PartialEq, PartialOrd, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Effect {
+    /// Shows a message to the player.
+    Message {
+        /// The text to show.
+        text: String,
+    },
+    /// Sets a game variable to a value.
+    SetVariable {
+        /// The name of the variable.
+        name: String,
+        /// The new value.
+        value: i32,
+    },
+    /// Adds to or subtracts from a game variable.
+    ChangeVariable {
+        /// The name of the variable.
+        name: String,
+        /// The amount to add. Use negative numbers to subtract.
+        by: i32,
+    },
+    /// Gives an item to the player.
+    AddItem {
+        /// The item to give.
+        item_id: u16,
+    },
+    /// Takes an item away from the player, if they have it.
+    RemoveItem {
+        /// The item to take.
+        item_id: u16,
+    },
+    /// Moves a subject anywhere in the game.
+    MoveSubject {
+        /// The subject to move.
+        subject_id: u16,
+        /// Where to move the subject to.
+        location: SubjectLocation,
+    },
+    /// Moves the player to a room.
+    MovePlayer {
+        /// The room to move the player to.
+        room_id: u16,
+    },
+    /// Changes the narrative displayed in a room.
+    SetNarrative {
+        /// The room whose narrative changes.
+        room_id: u16,
+        /// The new narrative.
+        narrative: u16,
+    },
+    /// Adds to or subtracts from a subject's disposition.
+    ChangeDisposition {
+        /// The subject whose disposition changes.
+        subject_id: u16,
+        /// The amount to add. Use negative numbers to subtract.
+        by: i32,
+    },
+    /// Sets a subject's disposition to a value.
+    SetDisposition {
+        /// The subject whose disposition changes.
+        subject_id: u16,
+        /// The new disposition.
+        value: i32,
+    },
+    /// Sets a flag on a subject.
+    SetSubjectFlag {
+        /// The subject to set the flag on.
+        subject_id: u16,
+        /// The flag to set.
+        flag: String,
+    },
+    /// Clears a flag from a subject.
+    ClearSubjectFlag {
+        /// The subject to clear the flag from.
+        subject_id: u16,
+        /// The flag to clear.
+        flag: String,
+    },
+    /// Marks an event as completed.
+    CompleteEvent {
+        /// The event to complete.
+        event_id: u16,
+    },
+    /// Changes the player's health by the given amount.
+    /// Does nothing in games that don't track health.
+    ChangeHealth {
+        /// The amount to add, negative to take damage.
+        by: i32,
+    },
+    /// Sets the player's health.
+    SetHealth {
+        /// The new health.
+        value: i32,
+    },
+    /// Changes one of the player's stats by the given amount.
+    ChangeStat {
+        /// The name of the stat.
+        name: String,
+        /// The amount to add, negative to subtract.
+        by: i32,
+    },
+    /// Sets one of the player's stats.
+    SetStat {
+        /// The name of the stat.
+        name: String,
+        /// The new value.
+        value: i32,
+    },
+    /// Awards points to the player. Points for the same
+    /// reason are only awarded once, and every reason
+    /// counts toward the game's maximum score.
+    AwardPoints {
+        /// What the points are awarded for, shown when the
+        /// player checks the score.
+        reason: String,
+        /// How many points to award.
+        points: i32,
+    },
+    /// Ends the game.
+    EndGame {
+        /// Whether the player won.
+        won: bool,
+        /// The text shown to the player.
+        text: String,
+    },
+}
+
+/// A rule that is checked at the end of every turn.
+///
+/// When all the conditions are met, all the effects
+/// are applied, and any messages are shown to the
+/// player under `MessageParts::DaemonText`.
+///
+/// # Examples:
+/// ```rust
+/// # use nightrunner_lib::config::Daemon;
+/// // a guard notices the player after two turns in room 3
+/// let yaml = r#"
+/// id: 1
+/// name: guard
+/// conditions:
+///   - type: player_in_room
+///     room_id: 3
+///   - type: turns_in_room
+///     at_least: 2
+/// effects:
+///   - type: message
+///     text: The guard notices you and throws you out.
+///   - type: move_player
+///     room_id: 1
+/// "#;
+/// let daemon: Daemon = serde_yaml::from_str(yaml).unwrap();
+/// assert_eq!(daemon.effects.len(), 2);
+/// ```
+#[derive(Debug, Clone, Eq, Ord, PartialEq, PartialOrd, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
+pub struct Daemon {
+    /// The id of the daemon.
+    pub id: u16,
+    /// Name of the daemon. This isn't used in the game
+    /// and is used to make the configuration more readable.
+    pub name: String,
+    /// All of these have to be met for the daemon to run.
+    /// A daemon without conditions runs every turn.
+    #[serde(default)]
+    pub conditions: Vec<Condition>,
+    /// What happens when the daemon runs.
+    #[serde(default)]
+    pub effects: Vec<Effect>,
+}
+
+impl State {
+    /// Returns the value of a game variable. Variables
+    /// that were never set are `0`.
+    pub fn variable(&self, name: &str) -> i32 {
+        self.variables.get(name).copied().unwrap_or(0)
+    }
+    /// Returns true if all the conditions hold.
+    pub fn conditions_met(&self, conditions: &[Condition]) -> bool {
+        conditions.iter().all(|condition| condition.is_met(self))
+    }
+    /// Applies an effect to the state, returning the text
+    /// to show to the player, if the effect has any.
+    ///
+    /// ## Example:
+    /// ```rust
+    /// # use nightrunner_lib::config::{Config, Effect, State};
+    /// let mut state = State::init(Config::from_path("./fixtures/"));
+    /// state
+    ///     .apply_effect(&Effect::ChangeVariable {
+    ///         name: "hunger".to_string(),
+    ///         by: 2,
+    ///     })
+    ///     .unwrap();
+    /// assert_eq!(state.variable("hunger"), 2);
+    /// ```
+    pub fn apply_effect(&mut self, effect: &Effect) -> NRResult<Option<String>> {
+        match effect {
+            Effect::Message { text } => return Ok(Some(text.clone())),
+            Effect::SetVariable { name, value } => {
+                self.variables.insert(name.clone(), *value);
+            }
+            Effect::ChangeVariable { name, by } => {
+                let variable = self.variables.entry(name.clone()).or_insert(0);
+                *variable = variable.saturating_add(*by);
+            }
+            Effect::AddItem { item_id } => {
+                let item = self
+                    .config
+                    .items
+                    .iter()
+                    .find(|item| item.id == *item_id)
+                    .ok_or(NoItem)?
+                    .clone();
+                self.player.inventory.add_item(item);
+            }
+            Effect::RemoveItem { item_id } => {
+                let item = self
+                    .player
+                    .inventory
+                    .items
+                    .iter()
+                    .find(|item| item.id == *item_id)
+                    .cloned();
+                if let Some(item) = item {
+                    self.player.remove_item(item)?;
+                }
+            }
+            Effect::MoveSubject {
+                subject_id,
+                location,
+            } => self.set_subject_location(*subject_id, *location)?,
+            Effect::MovePlayer { room_id } => {
+                if !self.rooms.iter().any(|room| room.id == *room_id) {
+                    return Err(super::InvalidRoom.into());
+                }
+                self.current_room = *room_id;
+            }
+            Effect::SetNarrative { room_id, narrative } => {
+                self.set_room_narrative(*room_id, *narrative)?
+            }
+            Effect::ChangeDisposition { subject_id, by } => {
+                let subject = self.subject_mut(*subject_id).ok_or(InvalidSubject)?;
+                subject.disposition = subject.disposition.saturating_add(*by);
+            }
+            Effect::SetDisposition { subject_id, value } => {
+                self.subject_mut(*subject_id)
+                    .ok_or(InvalidSubject)?
+                    .disposition = *value;
+            }
+            Effect::SetSubjectFlag { subject_id, flag } => {
+                let subject = self.subject_mut(*subject_id).ok_or(InvalidSubject)?;
+                if !subject.has_flag(flag) {
+                    subject.flags.push(flag.clone());
+                }
+            }
+            Effect::ClearSubjectFlag { subject_id, flag } => {
+                let subject = self.subject_mut(*subject_id).ok_or(InvalidSubject)?;
+                subject.flags.retain(|f| f != flag);
+            }
+            Effect::CompleteEvent { event_id } => {
+                if !self
+                    .rooms
+                    .iter()
+                    .any(|room| room.events.iter().any(|event| event.id == *event_id))
+                {
+                    return Err(InvalidEvent.into());
+                }
+                self.complete_event(*event_id);
+            }
+            Effect::ChangeHealth { by } => {
+                if let Some(health) = self.player.health.as_mut() {
+                    *health = health.saturating_add(*by);
+                }
+            }
+            Effect::SetHealth { value } => self.player.health = Some(*value),
+            Effect::ChangeStat { name, by } => {
+                let stat = self.player.stats.entry(name.clone()).or_insert(0);
+                *stat = stat.saturating_add(*by);
+            }
+            Effect::SetStat { name, value } => {
+                self.player.stats.insert(name.clone(), *value);
+            }
+            Effect::AwardPoints { reason, points } => {
+                if self
+                    .player
+                    .awards
+                    .iter()
+                    .any(|award| award.reason == *reason)
+                {
+                    return Ok(None);
+                }
+                self.player.awards.push(Award {
+                    reason: reason.clone(),
+                    points: *points,
+                });
+                let change = if *points < 0 { "down" } else { "up" };
+                let points = points.unsigned_abs();
+                let unit = if points == 1 { "point" } else { "points" };
+                return Ok(Some(format!(
+                    "Your score has gone {} by {} {}.",
+                    change, points, unit
+                )));
+            }
+            Effect::EndGame { won, text } => {
+                if self.game_over.is_none() {
+                    self.game_over = Some(GameOver {
+                        won: *won,
+                        text: text.clone(),
+                    });
+                }
+            }
+        }
+        Ok(None)
+    }
+    /// Applies all the effects in order, returning the text
+    /// they produced.
+    pub fn apply_effects(&mut self, effects: &[Effect]) -> NRResult<Vec<String>> {
+        let mut messages = vec![];
+        for effect in effects {
+            if let Some(message) = self.apply_effect(effect)? {
+                messages.push(message);
+            }
+        }
+        Ok(messages)
+    }
+}
+
+#[cfg(test)]
+#[path = "rules_tests.rs"]
+mod rules_tests;
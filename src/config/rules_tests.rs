@@ -0,0 +1,363 @@
+use super::*;
+use crate::config::{Config, DispositionBand};
+#[cfg(test)]
+use pretty_assertions::assert_eq;
+
+#[test]
+fn it_checks_conditions() {
+    let mut state = State::init(Config::from_path("fixtures/"));
+    state.turn = 4;
+    state.turns_in_room = 2;
+    state.variables.insert("fuel".to_string(), 3);
+    assert!(Condition::PlayerInRoom { room_id: 1 }.is_met(&state));
+    assert!(!Condition::PlayerInRoom { room_id: 2 }.is_met(&state));
+    assert!(Condition::TurnsInRoom { at_least: 2 }.is_met(&state));
+    assert!(!Condition::TurnsInRoom { at_least: 3 }.is_met(&state));
+    assert!(Condition::EveryTurns { turns: 2 }.is_met(&state));
+    assert!(!Condition::EveryTurns { turns: 3 }.is_met(&state));
+    assert!(Condition::EventNotCompleted { event_id: 1 }.is_met(&state));
+    assert!(!Condition::EventCompleted { event_id: 1 }.is_met(&state));
+    assert!(!Condition::HasItem { item_id: 1 }.is_met(&state));
+    assert!(Condition::SubjectAt {
+        subject_id: 2,
+        location: SubjectLocation::Room(2)
+    }
+    .is_met(&state));
+    assert!(Condition::Variable {
+        name: "fuel".to_string(),
+        at_least: Some(1),
+        at_most: Some(3),
+    }
+    .is_met(&state));
+    assert!(!Condition::Variable {
+        name: "fuel".to_string(),
+        at_least: None,
+        at_most: Some(2),
+    }
+    .is_met(&state));
+    assert!(Condition::Variable {
+        name: "hunger".to_string(),
+        at_least: None,
+        at_most: Some(0),
+    }
+    .is_met(&state));
+}
+
+#[test]
+fn it_applies_effects() {
+    let mut state = State::init(Config::from_path("fixtures/"));
+    let messages = state
+        .apply_effects(&[
+            Effect::SetVariable {
+                name: "fuel".to_string(),
+                value: 5,
+            },
+            Effect::ChangeVariable {
+                name: "fuel".to_string(),
+                by: -2,
+            },
+            Effect::Message {
+                text: "The lamp flickers.".to_string(),
+            },
+            Effect::AddItem { item_id: 3 },
+            Effect::MovePlayer { room_id: 2 },
+            Effect::SetNarrative {
+                room_id: 1,
+                narrative: 3,
+            },
+            Effect::CompleteEvent { event_id: 1 },
+            Effect::MoveSubject {
+                subject_id: 2,
+                location: SubjectLocation::Nowhere,
+            },
+        ])
+        .unwrap();
+    assert_eq!(messages, vec!["The lamp flickers.".to_string()]);
+    assert_eq!(state.variable("fuel"), 3);
+    assert!(state.player.inventory.items.iter().any(|item| item.id == 3));
+    assert_eq!(state.current_room, 2);
+    assert_eq!(state.rooms[0].narrative, 3);
+    assert!(state.is_event_completed(1));
+    assert_eq!(state.subject_location(2), Some(SubjectLocation::Nowhere));
+
+    state
+        .apply_effect(&Effect::RemoveItem { item_id: 3 })
+        .unwrap();
+    assert!(state.player.inventory.items.is_empty());
+    assert!(state
+        .apply_effect(&Effect::RemoveItem { item_id: 3 })
+        .is_ok());
+    state.apply_effect(&Effect::AddItem { item_id: 3 }).unwrap();
+    state.player.worn.push(3);
+    state
+        .apply_effect(&Effect::RemoveItem { item_id: 3 })
+        .unwrap();
+    assert!(state.player.worn.is_empty());
+    assert!(state
+        .apply_effect(&Effect::MovePlayer { room_id: 42 })
+        .is_err());
+    assert!(state
+        .apply_effect(&Effect::AddItem { item_id: 42 })
+        .is_err());
+}
+
+#[test]
+fn it_deserializes_rules() {
+    let yaml = r#"
+- id: 1
+  name: lamp
+  conditions:
+    - type: variable
+      name: fuel
+      at_least: 1
+  effects:
+    - type: change_variable
+      name: fuel
+      by: -1
+- id: 2
+  name: rats
+  effects:
+    - type: move_subject
+      subject_id: 2
+      location: following_player
+"#;
+    let daemons: Vec<Daemon> = serde_yaml::from_str(yaml).unwrap();
+    assert_eq!(
+        daemons,
+        vec![
+            Daemon {
+                id: 1,
+                name: "lamp".to_string(),
+                conditions: vec![Condition::Variable {
+                    name: "fuel".to_string(),
+                    at_least: Some(1),
+                    at_most: None,
+                }],
+                effects: vec![Effect::ChangeVariable {
+                    name: "fuel".to_string(),
+                    by: -1,
+                }],
+            },
+            Daemon {
+                id: 2,
+                name: "rats".to_string(),
+                conditions: vec![],
+                effects: vec![Effect::MoveSubject {
+                    subject_id: 2,
+                    location: SubjectLocation::FollowingPlayer,
+                }],
+            },
+        ]
+    );
+}
+
+#[test]
+fn it_changes_and_checks_subject_attributes() {
+    let mut state = State::init(Config::from_path("fixtures/"));
+    state
+        .apply_effects(&[
+            Effect::ChangeDisposition {
+                subject_id: 2,
+                by: -4,
+            },
+            Effect::ChangeDisposition {
+                subject_id: 2,
+                by: -6,
+            },
+            Effect::SetSubjectFlag {
+                subject_id: 2,
+                flag: "angry".to_string(),
+            },
+            Effect::SetSubjectFlag {
+                subject_id: 2,
+                flag: "angry".to_string(),
+            },
+            Effect::SetDisposition {
+                subject_id: 1,
+                value: 12,
+            },
+        ])
+        .unwrap();
+    assert_eq!(state.subject(2).unwrap().disposition, -10);
+    assert_eq!(state.subject(2).unwrap().flags, vec!["angry".to_string()]);
+    assert!(Condition::DispositionBand {
+        subject_id: 2,
+        band: DispositionBand::Hostile
+    }
+    .is_met(&state));
+    assert!(Condition::DispositionBand {
+        subject_id: 1,
+        band: DispositionBand::Friendly
+    }
+    .is_met(&state));
+    assert!(Condition::Disposition {
+        subject_id: 2,
+        at_least: None,
+        at_most: Some(-5),
+    }
+    .is_met(&state));
+    assert!(Condition::SubjectHasFlag {
+        subject_id: 2,
+        flag: "angry".to_string()
+    }
+    .is_met(&state));
+    assert!(Condition::SubjectLacksFlag {
+        subject_id: 1,
+        flag: "angry".to_string()
+    }
+    .is_met(&state));
+
+    state
+        .apply_effect(&Effect::ClearSubjectFlag {
+            subject_id: 2,
+            flag: "angry".to_string(),
+        })
+        .unwrap();
+    assert!(state.subject(2).unwrap().flags.is_empty());
+    assert!(state
+        .apply_effect(&Effect::ChangeDisposition {
+            subject_id: 42,
+            by: 1
+        })
+        .is_err());
+    state
+        .apply_effect(&Effect::SetDisposition {
+            subject_id: 1,
+            value: i32::MIN,
+        })
+        .unwrap();
+    state
+        .apply_effect(&Effect::ChangeDisposition {
+            subject_id: 1,
+            by: -1,
+        })
+        .unwrap();
+    assert_eq!(state.subject(1).unwrap().disposition, i32::MIN);
+}
+
+#[test]
+fn it_changes_and_checks_player_stats() {
+    let mut config = Config::from_path("fixtures/");
+    config.stats.health = Some(10);
+    let mut state = State::init(config);
+    state
+        .apply_effects(&[
+            Effect::ChangeHealth { by: -3 },
+            Effect::ChangeStat {
+                name: "strength".to_string(),
+                by: 2,
+            },
+        ])
+        .unwrap();
+    assert_eq!(state.player.health, Some(7));
+    assert_eq!(state.player.stat("strength"), 2);
+    assert!(Condition::Health {
+        at_least: None,
+        at_most: Some(7),
+    }
+    .is_met(&state));
+    assert!(Condition::Stat {
+        name: "strength".to_string(),
+        at_least: Some(2),
+        at_most: None,
+    }
+    .is_met(&state));
+
+    let award = Effect::AwardPoints {
+        reason: "finding the key".to_string(),
+        points: 5,
+    };
+    assert_eq!(
+        state.apply_effect(&award).unwrap(),
+        Some("Your score has gone up by 5 points.".to_string())
+    );
+    assert_eq!(
+        state.apply_effect(&award).unwrap(),
+        None,
+        "points for the same reason are only awarded once"
+    );
+    assert_eq!(state.player.score(), 5);
+    assert!(Condition::Score {
+        at_least: Some(5),
+        at_most: None,
+    }
+    .is_met(&state));
+
+    state
+        .apply_effect(&Effect::EndGame {
+            won: true,
+            text: "You escaped!".to_string(),
+        })
+        .unwrap();
+    assert_eq!(
+        state.game_over,
+        Some(GameOver {
+            won: true,
+            text: "You escaped!".to_string()
+        })
+    );
+}
+
+#[test]
+fn it_keeps_variables_in_range() {
+    let mut state = State::init(Config::from_path("fixtures/"));
+    state.variables.insert("fuel".to_string(), i32::MAX);
+    state
+        .apply_effect(&Effect::ChangeVariable {
+            name: "fuel".to_string(),
+            by: 1,
+        })
+        .unwrap();
+    assert_eq!(state.variable("fuel"), i32::MAX);
+}
+
+#[test]
+fn it_keeps_stats_and_score_in_range() {
+    let mut state = State::init(Config::from_path("fixtures/"));
+    state.player.health = Some(i32::MAX - 1);
+    state.apply_effect(&Effect::ChangeHealth { by: 5 }).unwrap();
+    assert_eq!(state.player.health, Some(i32::MAX));
+    state
+        .player
+        .stats
+        .insert("strength".to_string(), i32::MIN + 1);
+    state
+        .apply_effect(&Effect::ChangeStat {
+            name: "strength".to_string(),
+            by: -5,
+        })
+        .unwrap();
+    assert_eq!(state.player.stat("strength"), i32::MIN);
+
+    let penalty = Effect::AwardPoints {
+        reason: "waking the guard".to_string(),
+        points: -5,
+    };
+    assert_eq!(
+        state.apply_effect(&penalty).unwrap(),
+        Some("Your score has gone down by 5 points.".to_string())
+    );
+    for (reason, points) in [("a", i32::MAX), ("b", i32::MAX)] {
+        state
+            .apply_effect(&Effect::AwardPoints {
+                reason: reason.to_string(),
+                points,
+            })
+            .unwrap();
+    }
+    assert_eq!(state.player.score(), i32::MAX);
+}
+
+#[test]
+fn it_ignores_health_when_it_isnt_tracked() {
+    let mut state = State::init(Config::from_path("fixtures/"));
+    state
+        .apply_effect(&Effect::ChangeHealth { by: -3 })
+        .unwrap();
+    assert_eq!(state.player.health, None);
+    assert!(!Condition::Health {
+        at_least: None,
+        at_most: None,
+    }
+    .is_met(&state));
+}
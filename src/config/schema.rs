@@ -0,0 +1,165 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use schemars::generate::SchemaSettings;
+use schemars::JsonSchema;
+use serde_json::Value;
+
+use super::formats::GameFile;
+use super::rooms::RoomBlueprint;
+use super::symbols::REFERENCES;
+use super::{Config, Daemon, Event, Item, Narrative, PlayerStats, Settings, Subject, Verb};
+
+/// The definitions of entries whose `id` can be symbolic.
+const ENTRIES: [&str; 7] = [
+    "Narrative",
+    "Item",
+    "Subject",
+    "Verb",
+    "Event",
+    "RoomBlueprint",
+    "Daemon",
+];
+
+impl Config {
+    /// Returns a JSON Schema for the game files read by
+    /// `Config::from_file`, which editors can use to check and
+    /// complete them. Every section is optional, since a file
+    /// can leave some of them to the files it includes.
+    ///
+    /// The schema is generated from the types the data is read
+    /// into, so it always matches what the library reads.
+    /// Only available with the `schema` feature.
+    ///
+    /// # Examples:
+    /// ```rust
+    /// # use nightrunner_lib::config::Config;
+    /// let schema = Config::json_schema();
+    /// assert!(schema["properties"]["room_blueprints"].is_object());
+    /// assert!(schema["properties"]["include"].is_object());
+    /// ```
+    pub fn json_schema() -> Value {
+        schema_for::<GameFile>("game file")
+    }
+    /// Returns a JSON Schema for each YAML file read by
+    /// `Config::from_path`, by file name. Only available with
+    /// the `schema` feature.
+    ///
+    /// # Examples:
+    /// ```rust
+    /// # use nightrunner_lib::config::Config;
+    /// let schemas = Config::yaml_file_schemas();
+    /// assert_eq!(schemas["rooms.yml"]["type"], "array");
+    /// assert_eq!(schemas["intro.yml"]["type"], "string");
+    /// ```
+    pub fn yaml_file_schemas() -> BTreeMap<&'static str, Value> {
+        BTreeMap::from([
+            (
+                "narratives.yml",
+                schema_for::<Vec<Narrative>>("narratives.yml"),
+            ),
+            ("items.yml", schema_for::<Vec<Item>>("items.yml")),
+            ("rooms.yml", schema_for::<Vec<RoomBlueprint>>("rooms.yml")),
+            ("verbs.yml", schema_for::<Vec<Verb>>("verbs.yml")),
+            ("subjects.yml", schema_for::<Vec<Subject>>("subjects.yml")),
+            ("events.yml", schema_for::<Vec<Event>>("events.yml")),
+            ("intro.yml", schema_for::<String>("intro.yml")),
+            ("settings.yml", schema_for::<Settings>("settings.yml")),
+            ("daemons.yml", schema_for::<Vec<Daemon>>("daemons.yml")),
+            (
+                "variables.yml",
+                schema_for::<BTreeMap<String, i32>>("variables.yml"),
+            ),
+            ("stats.yml", schema_for::<PlayerStats>("stats.yml")),
+            (
+                "format_version.yml",
+                schema_for::<u32>("format_version.yml"),
+            ),
+        ])
+    }
+}
+
+fn schema_for<T: JsonSchema>(title: &str) -> Value {
+    let mut schema = SchemaSettings::draft07()
+        .into_generator()
+        .into_root_schema_for::<T>()
+        .to_value();
+    schema["title"] = title.into();
+    allow_symbols(&mut schema);
+    schema
+}
+
+/// Lets the ids of entries, and the fields referencing them,
+/// be symbolic ids as well as numbers.
+fn allow_symbols(schema: &mut Value) {
+    let mut definitions = BTreeSet::new();
+    allow_symbolic_references(schema, &mut definitions);
+    for entry in ENTRIES {
+        if let Some(id) = schema.pointer_mut(&format!("/definitions/{}/properties/id", entry)) {
+            allow_strings(id, &mut definitions);
+        }
+    }
+    // definitions used by references, like subject locations
+    let mut relaxed = BTreeSet::new();
+    while let Some(name) = definitions.difference(&relaxed).next().cloned() {
+        if let Some(definition) = schema.pointer_mut(&format!("/definitions/{}", name)) {
+            allow_strings(definition, &mut definitions);
+        }
+        relaxed.insert(name);
+    }
+}
+
+fn allow_symbolic_references(schema: &mut Value, definitions: &mut BTreeSet<String>) {
+    match schema {
+        Value::Object(fields) => {
+            if let Some(Value::Object(properties)) = fields.get_mut("properties") {
+                for (name, property) in properties.iter_mut() {
+                    if REFERENCES.iter().any(|(field, _)| field == name) {
+                        allow_strings(property, definitions);
+                    }
+                }
+            }
+            for value in fields.values_mut() {
+                allow_symbolic_references(value, definitions);
+            }
+        }
+        Value::Array(values) => {
+            for value in values {
+                allow_symbolic_references(value, definitions);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Lets an integer, or a list of integers, be a string. The
+/// definitions it references are added to `definitions`.
+fn allow_strings(schema: &mut Value, definitions: &mut BTreeSet<String>) {
+    if let Some(reference) = schema["$ref"].as_str() {
+        if let Some(name) = reference.strip_prefix("#/definitions/") {
+            definitions.insert(name.to_string());
+        }
+    }
+    match schema.get_mut("type") {
+        Some(kind) if kind == "integer" => {
+            *kind = serde_json::json!(["integer", "string"]);
+        }
+        Some(Value::Array(kinds)) if kinds.contains(&Value::from("integer")) => {
+            kinds.push("string".into());
+        }
+        _ => {}
+    }
+    if let Some(items) = schema.get_mut("items") {
+        allow_strings(items, definitions);
+    }
+    for key in ["allOf", "anyOf", "oneOf"] {
+        if let Some(Value::Array(branches)) = schema.get_mut(key) {
+            for branch in branches {
+                allow_strings(branch, definitions);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+#[path = "schema_tests.rs"]
+mod schema_tests;
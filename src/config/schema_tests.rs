@@ -0,0 +1,151 @@
+use super::*;
+#[cfg(test)]
+use pretty_assertions::assert_eq;
+
+/// Returns the schema a `$ref` points to, or the schema itself.
+fn resolve<'a>(root: &'a Value, schema: &'a Value) -> &'a Value {
+    match schema["$ref"].as_str() {
+        Some(reference) => root.pointer(&reference[1..]).unwrap(),
+        None => schema,
+    }
+}
+
+/// Collects the fields in `data` that no property of the schema
+/// describes.
+fn undescribed_fields(
+    root: &Value,
+    schema: &Value,
+    data: &Value,
+    path: &str,
+    found: &mut Vec<String>,
+) {
+    let schema = resolve(root, schema);
+    let branches: Vec<&Value> = ["allOf", "anyOf", "oneOf"]
+        .iter()
+        .filter_map(|key| schema[key].as_array())
+        .flatten()
+        .collect();
+    if !branches.is_empty() {
+        // the branch for the data is the one that describes it best
+        let mut best: Option<Vec<String>> = None;
+        for branch in branches {
+            let mut missing = vec![];
+            undescribed_fields(root, branch, data, path, &mut missing);
+            if best.as_ref().is_none_or(|best| missing.len() < best.len()) {
+                best = Some(missing);
+            }
+        }
+        found.extend(best.unwrap_or_default());
+        return;
+    }
+    match data {
+        Value::Object(fields) if schema.get("properties").is_some() => {
+            for (name, value) in fields {
+                let path = format!("{}/{}", path, name);
+                match schema["properties"].get(name) {
+                    Some(property) => undescribed_fields(root, property, value, &path, found),
+                    None => found.push(path),
+                }
+            }
+        }
+        Value::Array(values) => {
+            for (index, value) in values.iter().enumerate() {
+                let path = format!("{}/{}", path, index);
+                undescribed_fields(root, &schema["items"], value, &path, found);
+            }
+        }
+        _ => {}
+    }
+}
+
+#[test]
+fn it_describes_the_game_data() {
+    let schema = Config::json_schema();
+    assert_eq!(schema["$schema"], "http://json-schema.org/draft-07/schema#");
+    // chapter files leave sections to the other files
+    assert_eq!(schema.get("required"), None);
+    assert_eq!(
+        schema["properties"]["include"]["items"]["type"],
+        serde_json::json!("string")
+    );
+    assert_eq!(
+        schema["properties"]["intro"]["type"],
+        serde_json::json!(["string", "null"])
+    );
+}
+
+#[test]
+fn it_describes_included_game_files() {
+    let schema = Config::json_schema();
+    let data: Value =
+        serde_yaml::from_str("include: [chapter.yml]\nitems: []\nformat_version: 2\n").unwrap();
+    let mut found = vec![];
+    undescribed_fields(&schema, &schema, &data, "", &mut found);
+    assert_eq!(found, Vec::<String>::new());
+}
+
+#[test]
+fn it_describes_every_field_of_the_fixtures() {
+    let schema = Config::json_schema();
+    let data: Value = serde_json::from_str(&Config::from_path("fixtures/").to_json()).unwrap();
+    let mut found = vec![];
+    undescribed_fields(&schema, &schema, &data, "", &mut found);
+    assert_eq!(found, Vec::<String>::new());
+}
+
+#[test]
+fn it_allows_symbolic_ids() {
+    let schema = Config::json_schema();
+    let string_or_integer = serde_json::json!(["integer", "string"]);
+    for entry in ENTRIES {
+        assert_eq!(
+            schema["definitions"][entry]["properties"]["id"]["type"], string_or_integer,
+            "{}",
+            entry
+        );
+    }
+    let event = &schema["definitions"]["Event"]["properties"];
+    assert_eq!(event["location"]["type"], string_or_integer);
+    assert_eq!(
+        event["required_item"]["type"],
+        serde_json::json!(["integer", "null", "string"])
+    );
+    let room = &schema["definitions"]["RoomBlueprint"]["properties"];
+    assert_eq!(room["item_ids"]["items"]["type"], string_or_integer);
+    // subject locations are referenced through their definition
+    assert_eq!(
+        schema["definitions"]["SubjectLocation"]["anyOf"][0]["type"],
+        string_or_integer
+    );
+    // dialogue nodes are only referenced within their dialogue
+    assert_eq!(
+        schema["definitions"]["DialogueNode"]["properties"]["id"]["type"],
+        "integer"
+    );
+}
+
+#[test]
+fn it_describes_every_yaml_file() {
+    let schemas = Config::yaml_file_schemas();
+    for file in std::fs::read_dir("fixtures/").unwrap() {
+        let path = file.unwrap().path();
+        if path.is_dir() {
+            continue;
+        }
+        let name = path.file_name().unwrap().to_str().unwrap();
+        let schema = &schemas[name];
+        assert_eq!(schema["title"], name);
+        let data: Value = serde_yaml::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+        let mut found = vec![];
+        undescribed_fields(schema, schema, &data, name, &mut found);
+        assert_eq!(found, Vec::<String>::new());
+    }
+    assert_eq!(
+        schemas["rooms.yml"]["items"]["$ref"],
+        "#/definitions/RoomBlueprint"
+    );
+    assert_eq!(
+        schemas["variables.yml"]["additionalProperties"]["type"],
+        "integer"
+    );
+}
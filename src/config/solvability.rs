@@ -0,0 +1,432 @@
+use std::collections::BTreeSet;
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use super::{Condition, Config, Effect, Event, SubjectLocation};
+
+/// What [Config::check_solvability] found out about a game.
+///
+/// The check is optimistic: it assumes anything the player
+/// could possibly do eventually gets done, ignores items
+/// being taken away, and treats conditions it can't work out
+/// without playing, like variables or dispositions, as met.
+/// Anything reported here can't happen in any playthrough,
+/// but a game without problems can still get stuck.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct SolvabilityReport {
+    /// Rooms the player can never get to.
+    pub unreachable_rooms: Vec<u16>,
+    /// Events that can never happen.
+    pub unreachable_events: Vec<u16>,
+    /// Items the player can never carry. Items that can't
+    /// be picked up are only listed when an event needs
+    /// them and they aren't in a room the player can get to.
+    pub unobtainable_items: Vec<u16>,
+    /// Every way the game can end, and whether the player
+    /// can get to it.
+    pub endings: Vec<Ending>,
+}
+
+/// A way the game can end, from an `EndGame` effect.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct Ending {
+    /// Where the effect ending the game is.
+    pub source: EffectSource,
+    /// Whether the player wins.
+    pub won: bool,
+    /// The text shown to the player.
+    pub text: String,
+    /// Whether the player can get to this ending.
+    pub reachable: bool,
+}
+
+/// The part of the game an effect belongs to.
+#[derive(Debug, Clone, Copy, Eq, Ord, PartialEq, PartialOrd, Deserialize, Serialize)]
+#[serde(tag = "type", content = "id", rename_all = "snake_case")]
+pub enum EffectSource {
+    /// The event with this id.
+    Event(u16),
+    /// The daemon with this id.
+    Daemon(u16),
+    /// The item with this id, used with a built-in verb.
+    Item(u16),
+    /// The dialogue, topics, or accepted items of the
+    /// subject with this id.
+    Subject(u16),
+}
+
+impl fmt::Display for EffectSource {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            EffectSource::Event(id) => write!(f, "event {}", id),
+            EffectSource::Daemon(id) => write!(f, "daemon {}", id),
+            EffectSource::Item(id) => write!(f, "item {}", id),
+            EffectSource::Subject(id) => write!(f, "subject {}", id),
+        }
+    }
+}
+
+impl SolvabilityReport {
+    /// Returns true if every event can happen and every
+    /// ending can be reached.
+    pub fn is_solvable(&self) -> bool {
+        self.unreachable_events.is_empty() && self.endings.iter().all(|ending| ending.reachable)
+    }
+}
+
+impl fmt::Display for SolvabilityReport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut lines = vec![];
+        let ids = |ids: &[u16]| {
+            ids.iter()
+                .map(|id| id.to_string())
+                .collect::<Vec<String>>()
+                .join(", ")
+        };
+        if !self.unreachable_rooms.is_empty() {
+            lines.push(format!(
+                "unreachable rooms: {}",
+                ids(&self.unreachable_rooms)
+            ));
+        }
+        if !self.unreachable_events.is_empty() {
+            lines.push(format!(
+                "events that can never happen: {}",
+                ids(&self.unreachable_events)
+            ));
+        }
+        if !self.unobtainable_items.is_empty() {
+            lines.push(format!(
+                "items that can never be carried: {}",
+                ids(&self.unobtainable_items)
+            ));
+        }
+        for ending in self.endings.iter().filter(|ending| !ending.reachable) {
+            let kind = if ending.won { "winning" } else { "losing" };
+            lines.push(format!(
+                "unreachable {} ending in {}: {}",
+                kind, ending.source, ending.text
+            ));
+        }
+        if lines.is_empty() {
+            write!(f, "no problems found")
+        } else {
+            write!(f, "{}", lines.join("\n"))
+        }
+    }
+}
+
+/// Everything the player can get to, found by repeatedly
+/// applying whatever became possible until nothing changes.
+#[derive(Default)]
+struct Reachable {
+    rooms: BTreeSet<u16>,
+    items: BTreeSet<u16>,
+    subjects: BTreeSet<u16>,
+    events: BTreeSet<u16>,
+    sources: BTreeSet<EffectSource>,
+}
+
+impl Config {
+    /// Works out which rooms, events, items, and endings the
+    /// player can get to, starting in room 1, to catch games
+    /// that can't be finished, like an item locked behind an
+    /// event that needs that same item.
+    ///
+    /// # Examples:
+    /// ```rust
+    /// # use nightrunner_lib::config::{Config, Item, ItemCapabilities};
+    /// let mut config = Config::from_path("fixtures/");
+    /// assert!(config.check_solvability().is_solvable());
+    ///
+    /// // the lamp is only given by an event that needs the lamp
+    /// config.items.push(Item {
+    ///     id: 4,
+    ///     name: "lamp".to_string(),
+    ///     description: "a lamp".to_string(),
+    ///     can_pick: true,
+    ///     capabilities: ItemCapabilities::default(),
+    /// });
+    /// let event = config.events.iter_mut().find(|e| e.id == 6).unwrap();
+    /// event.required_item = Some(4);
+    /// event.add_item = Some(4);
+    /// let report = config.check_solvability();
+    /// assert_eq!(report.unreachable_events, vec![6]);
+    /// assert_eq!(report.unobtainable_items, vec![4]);
+    /// ```
+    pub fn check_solvability(&self) -> SolvabilityReport {
+        let reachable = self.explore();
+        let unreachable_rooms = self
+            .room_blueprints
+            .iter()
+            .map(|room| room.id)
+            .filter(|id| !reachable.rooms.contains(id))
+            .collect();
+        let unreachable_events = self
+            .events
+            .iter()
+            .map(|event| event.id)
+            .filter(|id| !reachable.events.contains(id))
+            .collect();
+        let required_items: BTreeSet<u16> = self
+            .events
+            .iter()
+            .filter_map(|event| event.required_item)
+            .collect();
+        let unobtainable_items = self
+            .items
+            .iter()
+            .filter(|item| {
+                item.can_pick
+                    || (required_items.contains(&item.id)
+                        && !self.is_in_reachable_room(item.id, &reachable))
+            })
+            .map(|item| item.id)
+            .filter(|id| !reachable.items.contains(id))
+            .collect();
+        let endings = self
+            .effect_sources()
+            .into_iter()
+            .flat_map(|(source, effects)| {
+                let reachable = reachable.sources.contains(&source);
+                effects.into_iter().filter_map(move |effect| match effect {
+                    Effect::EndGame { won, text } => Some(Ending {
+                        source,
+                        won: *won,
+                        text: text.clone(),
+                        reachable,
+                    }),
+                    _ => None,
+                })
+            })
+            .collect();
+        SolvabilityReport {
+            unreachable_rooms,
+            unreachable_events,
+            unobtainable_items,
+            endings,
+        }
+    }
+
+    /// Returns every group of effects in the game, with
+    /// where it comes from.
+    pub(crate) fn effect_sources(&self) -> Vec<(EffectSource, Vec<&Effect>)> {
+        let events = self.events.iter().map(|event| {
+            (
+                EffectSource::Event(event.id),
+                event.effects.iter().collect(),
+            )
+        });
+        let daemons = self.daemons.iter().map(|daemon| {
+            (
+                EffectSource::Daemon(daemon.id),
+                daemon.effects.iter().collect(),
+            )
+        });
+        let items = self.items.iter().map(|item| {
+            (
+                EffectSource::Item(item.id),
+                item.capabilities.effects.iter().collect(),
+            )
+        });
+        let subjects = self.subjects.iter().map(|subject| {
+            let dialogue = subject.dialogue.iter().flat_map(|dialogue| {
+                dialogue.nodes.iter().flat_map(|node| {
+                    node.effects
+                        .iter()
+                        .chain(node.choices.iter().flat_map(|choice| choice.effects.iter()))
+                })
+            });
+            let topics = subject.topics.iter().flat_map(|topic| topic.effects.iter());
+            let accepts = subject
+                .accepts
+                .iter()
+                .flat_map(|accepted| accepted.effects.iter());
+            (
+                EffectSource::Subject(subject.id),
+                dialogue.chain(topics).chain(accepts).collect(),
+            )
+        });
+        events.chain(daemons).chain(items).chain(subjects).collect()
+    }
+
+    fn explore(&self) -> Reachable {
+        let mut reachable = Reachable::default();
+        if self.room_blueprints.iter().any(|room| room.id == 1) {
+            reachable.rooms.insert(1);
+        }
+        let sources = self.effect_sources();
+        loop {
+            let before = (
+                reachable.rooms.len(),
+                reachable.items.len(),
+                reachable.subjects.len(),
+                reachable.events.len(),
+                reachable.sources.len(),
+            );
+            for room in &self.room_blueprints {
+                if !reachable.rooms.contains(&room.id) {
+                    continue;
+                }
+                reachable
+                    .rooms
+                    .extend(room.exits.iter().map(|exit| exit.room_id));
+                reachable.subjects.extend(room.subject_ids.iter().copied());
+                reachable.items.extend(
+                    room.item_ids
+                        .iter()
+                        .filter(|id| self.items.iter().any(|i| i.id == **id && i.can_pick)),
+                );
+            }
+            for subject in &self.subjects {
+                let wanders_in = subject.behavior.as_ref().is_some_and(|behavior| {
+                    behavior
+                        .after_event
+                        .is_none_or(|id| reachable.events.contains(&id))
+                        && behavior
+                            .rooms
+                            .iter()
+                            .any(|room_id| reachable.rooms.contains(room_id))
+                });
+                if wanders_in {
+                    reachable.subjects.insert(subject.id);
+                }
+            }
+            for event in &self.events {
+                if !reachable.events.contains(&event.id) && self.can_happen(event, &reachable) {
+                    reachable.events.insert(event.id);
+                    reachable.rooms.extend(event.destination);
+                    reachable.items.extend(event.add_item);
+                    reachable.subjects.extend(event.add_subject);
+                    if event.move_subject_to_location.is_some() {
+                        reachable.subjects.extend(event.required_subject);
+                    }
+                    for subject_move in &event.move_subjects {
+                        if subject_move.location != SubjectLocation::Nowhere {
+                            reachable.subjects.insert(subject_move.subject_id);
+                        }
+                    }
+                }
+            }
+            for (source, effects) in &sources {
+                if !reachable.sources.contains(source) && self.is_source_active(*source, &reachable)
+                {
+                    reachable.sources.insert(*source);
+                    for effect in effects {
+                        apply_effect(effect, &mut reachable);
+                    }
+                }
+            }
+            let after = (
+                reachable.rooms.len(),
+                reachable.items.len(),
+                reachable.subjects.len(),
+                reachable.events.len(),
+                reachable.sources.len(),
+            );
+            if before == after {
+                return reachable;
+            }
+        }
+    }
+
+    fn can_happen(&self, event: &Event, reachable: &Reachable) -> bool {
+        let in_game = self
+            .room_blueprints
+            .iter()
+            .any(|room| room.id == event.location);
+        let required_events = event
+            .required_events
+            .iter()
+            .all(|id| reachable.events.contains(id));
+        if !in_game || !required_events {
+            return false;
+        }
+        if event.is_timed() {
+            return event
+                .turns_after
+                .is_none_or(|delay| reachable.events.contains(&delay.event_id));
+        }
+        let verb = event
+            .required_verb
+            .is_some_and(|id| self.allowed_verbs.iter().any(|verb| verb.id == id));
+        let subject = event
+            .required_subject
+            .is_none_or(|id| reachable.subjects.contains(&id));
+        let item = event.required_item.is_none_or(|id| {
+            reachable.items.contains(&id) || self.is_in_reachable_room(id, reachable)
+        });
+        let target = event.required_subject.is_some() || event.required_item.is_some();
+        reachable.rooms.contains(&event.location) && verb && subject && item && target
+    }
+
+    fn is_in_reachable_room(&self, item_id: u16, reachable: &Reachable) -> bool {
+        self.room_blueprints
+            .iter()
+            .any(|room| reachable.rooms.contains(&room.id) && room.item_ids.contains(&item_id))
+    }
+
+    fn is_source_active(&self, source: EffectSource, reachable: &Reachable) -> bool {
+        match source {
+            EffectSource::Event(id) => reachable.events.contains(&id),
+            EffectSource::Daemon(id) => self
+                .daemons
+                .iter()
+                .find(|daemon| daemon.id == id)
+                .is_some_and(|daemon| {
+                    daemon
+                        .conditions
+                        .iter()
+                        .all(|condition| could_be_met(condition, reachable))
+                }),
+            EffectSource::Item(id) => {
+                reachable.items.contains(&id) || self.is_in_reachable_room(id, reachable)
+            }
+            EffectSource::Subject(id) => reachable.subjects.contains(&id),
+        }
+    }
+}
+
+/// Returns false for conditions that can't be met with what
+/// the player can get to. Anything the check can't work out
+/// is assumed to be met eventually.
+fn could_be_met(condition: &Condition, reachable: &Reachable) -> bool {
+    match condition {
+        Condition::PlayerInRoom { room_id } => reachable.rooms.contains(room_id),
+        Condition::EventCompleted { event_id } => reachable.events.contains(event_id),
+        Condition::HasItem { item_id } => reachable.items.contains(item_id),
+        Condition::SubjectAt {
+            subject_id,
+            location: SubjectLocation::Room(room_id),
+        } => reachable.subjects.contains(subject_id) && reachable.rooms.contains(room_id),
+        _ => true,
+    }
+}
+
+fn apply_effect(effect: &Effect, reachable: &mut Reachable) {
+    match effect {
+        Effect::AddItem { item_id } => {
+            reachable.items.insert(*item_id);
+        }
+        Effect::MovePlayer { room_id } => {
+            reachable.rooms.insert(*room_id);
+        }
+        Effect::MoveSubject {
+            subject_id,
+            location,
+        } if *location != SubjectLocation::Nowhere => {
+            reachable.subjects.insert(*subject_id);
+        }
+        Effect::CompleteEvent { event_id } => {
+            reachable.events.insert(*event_id);
+        }
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+#[path = "solvability_tests.rs"]
+mod solvability_tests;
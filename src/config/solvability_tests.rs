@@ -0,0 +1,126 @@
+use super::*;
+use crate::config::{Daemon, Item, ItemCapabilities};
+#[cfg(test)]
+use pretty_assertions::assert_eq;
+
+fn fixtures() -> Config {
+    Config::from_path("fixtures/")
+}
+
+fn end_game(won: bool, text: &str) -> Effect {
+    Effect::EndGame {
+        won,
+        text: text.to_string(),
+    }
+}
+
+#[test]
+fn it_finds_no_problems_in_a_finishable_game() {
+    let report = fixtures().check_solvability();
+    assert_eq!(report, SolvabilityReport::default());
+    assert!(report.is_solvable());
+    assert_eq!(report.to_string(), "no problems found");
+}
+
+#[test]
+fn it_finds_items_locked_behind_events_that_need_them() {
+    let mut config = fixtures();
+    // item 2 is only given by event 5, which now needs it
+    config.room_blueprints[0].item_ids.retain(|id| *id != 2);
+    let event_5 = config.events.iter_mut().find(|e| e.id == 5).unwrap();
+    event_5.required_item = Some(2);
+    let report = config.check_solvability();
+    assert_eq!(report.unreachable_events, vec![5, 6]);
+    assert_eq!(report.unobtainable_items, vec![2]);
+    assert!(!report.is_solvable());
+}
+
+#[test]
+fn it_finds_rooms_without_a_way_in() {
+    let mut config = fixtures();
+    config.room_blueprints[0].exits.clear();
+    let report = config.check_solvability();
+    assert_eq!(report.unreachable_rooms, vec![2]);
+    assert_eq!(report.unreachable_events, vec![5, 6]);
+    assert_eq!(report.unobtainable_items, vec![3]);
+
+    // an event taking the player there opens the room up again
+    let event_4 = config.events.iter_mut().find(|e| e.id == 4).unwrap();
+    event_4.destination = Some(2);
+    let report = config.check_solvability();
+    assert!(report.unreachable_rooms.is_empty());
+    assert!(report.unreachable_events.is_empty());
+}
+
+#[test]
+fn it_checks_every_ending() {
+    let mut config = fixtures();
+    let event_6 = config.events.iter_mut().find(|e| e.id == 6).unwrap();
+    event_6.effects.push(end_game(true, "You win."));
+    config.items.push(Item {
+        id: 4,
+        name: "poison".to_string(),
+        description: "poison".to_string(),
+        can_pick: true,
+        capabilities: ItemCapabilities {
+            drinkable: true,
+            effects: vec![end_game(false, "You die.")],
+            ..Default::default()
+        },
+    });
+    config.daemons.push(Daemon {
+        id: 1,
+        name: "too late".to_string(),
+        conditions: vec![Condition::EventCompleted { event_id: 42 }],
+        effects: vec![end_game(false, "Too late.")],
+    });
+    let report = config.check_solvability();
+    assert_eq!(
+        report.endings,
+        vec![
+            Ending {
+                source: EffectSource::Event(6),
+                won: true,
+                text: "You win.".to_string(),
+                reachable: true,
+            },
+            Ending {
+                source: EffectSource::Daemon(1),
+                won: false,
+                text: "Too late.".to_string(),
+                reachable: false,
+            },
+            Ending {
+                source: EffectSource::Item(4),
+                won: false,
+                text: "You die.".to_string(),
+                reachable: false,
+            },
+        ]
+    );
+    assert_eq!(report.unobtainable_items, vec![4]);
+    assert_eq!(
+        report.to_string(),
+        "items that can never be carried: 4\n\
+         unreachable losing ending in daemon 1: Too late.\n\
+         unreachable losing ending in item 4: You die."
+    );
+}
+
+#[test]
+fn it_follows_timed_events_and_effects() {
+    let mut config = fixtures();
+    config.room_blueprints[0].exits.clear();
+    let event_4 = config.events.iter_mut().find(|e| e.id == 4).unwrap();
+    event_4.effects.push(Effect::MovePlayer { room_id: 2 });
+    assert!(config.check_solvability().unreachable_rooms.is_empty());
+
+    let event_4 = config.events.iter_mut().find(|e| e.id == 4).unwrap();
+    event_4.effects.clear();
+    event_4.required_verb = None;
+    event_4.at_turn = Some(3);
+    event_4.destination = Some(2);
+    let report = config.check_solvability();
+    assert!(report.unreachable_rooms.is_empty());
+    assert!(report.unreachable_events.is_empty());
+}
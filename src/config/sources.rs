@@ -0,0 +1,164 @@
+use std::collections::BTreeMap;
+use std::io::Read;
+use std::path::Path;
+
+use serde_json::{json, Value};
+
+use super::{Config, ConfigFormat};
+use crate::parser::errors::MissingConfigFile;
+use crate::NRResult;
+
+/// The YAML for each file of a game, for games that don't
+/// live in a folder, like games embedded with `include_str!`
+/// or fetched in the browser. Each field holds the contents
+/// of the file with the same name in `Config::from_path`.
+///
+/// # Examples:
+/// ```rust
+/// # use nightrunner_lib::config::{Config, YamlSections};
+/// let sections = YamlSections {
+///     narratives: include_str!("../../fixtures/narratives.yml").to_string(),
+///     items: include_str!("../../fixtures/items.yml").to_string(),
+///     rooms: include_str!("../../fixtures/rooms.yml").to_string(),
+///     verbs: include_str!("../../fixtures/verbs.yml").to_string(),
+///     subjects: include_str!("../../fixtures/subjects.yml").to_string(),
+///     events: include_str!("../../fixtures/events.yml").to_string(),
+///     intro: include_str!("../../fixtures/intro.yml").to_string(),
+///     ..YamlSections::default()
+/// };
+/// let config = Config::from_yaml_sections(&sections).unwrap();
+/// assert_eq!(config, Config::from_path("fixtures/"));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct YamlSections {
+    /// `narratives.yml`
+    pub narratives: String,
+    /// `items.yml`
+    pub items: String,
+    /// `rooms.yml`
+    pub rooms: String,
+    /// `verbs.yml`
+    pub verbs: String,
+    /// `subjects.yml`
+    pub subjects: String,
+    /// `events.yml`
+    pub events: String,
+    /// `intro.yml`
+    pub intro: String,
+    /// `settings.yml`, which is optional.
+    pub settings: Option<String>,
+    /// `daemons.yml`, which is optional.
+    pub daemons: Option<String>,
+    /// `variables.yml`, which is optional.
+    pub variables: Option<String>,
+    /// `stats.yml`, which is optional.
+    pub stats: Option<String>,
+    /// `format_version.yml`, which is optional. Games
+    /// without it are read as format version 1.
+    pub format_version: Option<String>,
+}
+
+impl YamlSections {
+    /// Takes the sections from a map of file names to their
+    /// contents, with the same names as the files read by
+    /// `Config::from_path`. Fails when a required file is
+    /// missing from the map.
+    pub fn from_files(files: &BTreeMap<String, String>) -> NRResult<YamlSections> {
+        YamlSections::read(|file_name| files.get(file_name).cloned())
+    }
+    /// Reads the sections from the files in a folder.
+    pub(crate) fn from_folder(path: &str) -> NRResult<YamlSections> {
+        let folder = Path::new(path);
+        YamlSections::read(|file_name| std::fs::read_to_string(folder.join(file_name)).ok())
+    }
+    fn read(file: impl Fn(&str) -> Option<String>) -> NRResult<YamlSections> {
+        let required = |file_name: &str| file(file_name).ok_or(MissingConfigFile);
+        Ok(YamlSections {
+            narratives: required("narratives.yml")?,
+            items: required("items.yml")?,
+            rooms: required("rooms.yml")?,
+            verbs: required("verbs.yml")?,
+            subjects: required("subjects.yml")?,
+            events: required("events.yml")?,
+            intro: required("intro.yml")?,
+            settings: file("settings.yml"),
+            daemons: file("daemons.yml"),
+            variables: file("variables.yml"),
+            stats: file("stats.yml"),
+            format_version: file("format_version.yml"),
+        })
+    }
+    /// Returns the game data in the layout of the JSON data.
+    pub(crate) fn to_value(&self) -> NRResult<Value> {
+        let mut data = json!({
+            "narratives": serde_yaml::from_str::<Value>(&self.narratives)?,
+            "items": serde_yaml::from_str::<Value>(&self.items)?,
+            "room_blueprints": serde_yaml::from_str::<Value>(&self.rooms)?,
+            "allowed_verbs": serde_yaml::from_str::<Value>(&self.verbs)?,
+            "subjects": serde_yaml::from_str::<Value>(&self.subjects)?,
+            "events": serde_yaml::from_str::<Value>(&self.events)?,
+            "intro": serde_yaml::from_str::<Value>(&self.intro)?,
+        });
+        for (key, section) in [
+            ("settings", &self.settings),
+            ("daemons", &self.daemons),
+            ("variables", &self.variables),
+            ("stats", &self.stats),
+            ("format_version", &self.format_version),
+        ] {
+            if let Some(section) = section {
+                data[key] = serde_yaml::from_str(section)?;
+            }
+        }
+        Ok(data)
+    }
+}
+
+impl Config {
+    /// Loads config from the YAML for each file of the game.
+    /// See [YamlSections].
+    pub fn from_yaml_sections(sections: &YamlSections) -> NRResult<Config> {
+        Config::from_value(sections.to_value()?)
+    }
+    /// Loads config from a map of file names to their
+    /// contents, laid out like the folder read by
+    /// `Config::from_path`, so the YAML files can be used
+    /// without a filesystem.
+    ///
+    /// ## Example:
+    /// ```rust
+    /// # use std::collections::BTreeMap;
+    /// # use nightrunner_lib::config::Config;
+    /// let mut files = BTreeMap::new();
+    /// for file_name in ["narratives", "items", "rooms", "verbs", "subjects", "events", "intro"] {
+    ///     let file_name = format!("{}.yml", file_name);
+    ///     let data = std::fs::read_to_string(format!("fixtures/{}", file_name)).unwrap();
+    ///     files.insert(file_name, data);
+    /// }
+    /// let config = Config::from_files(&files).unwrap();
+    /// assert_eq!(config, Config::from_path("fixtures/"));
+    /// ```
+    pub fn from_files(files: &BTreeMap<String, String>) -> NRResult<Config> {
+        Config::from_yaml_sections(&YamlSections::from_files(files)?)
+    }
+    /// Loads the whole game from a reader, in the layout of
+    /// the JSON data written in the given format. Unlike
+    /// `Config::from_file`, other files can't be included.
+    ///
+    /// ## Example:
+    /// ```rust
+    /// # use nightrunner_lib::config::{Config, ConfigFormat};
+    /// let file = std::fs::File::open("fixtures/single_file/game.toml").unwrap();
+    /// let config = Config::from_reader(file, ConfigFormat::Toml).unwrap();
+    /// assert_eq!(config, Config::from_path("fixtures/"));
+    /// ```
+    pub fn from_reader(mut reader: impl Read, format: ConfigFormat) -> NRResult<Config> {
+        let mut data = String::new();
+        reader.read_to_string(&mut data)?;
+        Config::from_value(format.parse(&data)?)
+    }
+}
+
+#[cfg(test)]
+#[path = "sources_tests.rs"]
+mod sources_tests;
@@ -0,0 +1,94 @@
+use super::*;
+#[cfg(test)]
+use pretty_assertions::assert_eq;
+
+fn fixture_files() -> BTreeMap<String, String> {
+    let mut files = BTreeMap::new();
+    for file_name in [
+        "narratives.yml",
+        "items.yml",
+        "rooms.yml",
+        "verbs.yml",
+        "subjects.yml",
+        "events.yml",
+        "intro.yml",
+    ] {
+        let data = std::fs::read_to_string(format!("fixtures/{}", file_name)).unwrap();
+        files.insert(file_name.to_string(), data);
+    }
+    files
+}
+
+#[test]
+fn it_loads_yaml_files_from_a_map() {
+    let config = Config::from_files(&fixture_files()).unwrap();
+    assert_eq!(config, Config::from_path("fixtures/"));
+}
+
+#[test]
+fn it_loads_optional_files_from_a_map() {
+    let mut files = fixture_files();
+    files.insert("variables.yml".to_string(), "chimes: 2\n".to_string());
+    files.insert(
+        "settings.yml".to_string(),
+        "informational_commands_take_turn: false\n".to_string(),
+    );
+    let config = Config::from_files(&files).unwrap();
+    assert_eq!(config.variables.get("chimes"), Some(&2));
+    assert!(!config.settings.informational_commands_take_turn);
+    assert!(config.daemons.is_empty());
+}
+
+#[test]
+fn it_fails_when_a_required_file_is_missing() {
+    let mut files = fixture_files();
+    files.remove("rooms.yml");
+    let error = Config::from_files(&files).unwrap_err();
+    assert_eq!(error.to_string(), MissingConfigFile.to_string());
+}
+
+#[test]
+fn it_loads_yaml_sections() {
+    let files = fixture_files();
+    let sections = YamlSections::from_files(&files).unwrap();
+    assert_eq!(sections.rooms, files["rooms.yml"]);
+    assert_eq!(sections.settings, None);
+    assert_eq!(
+        Config::from_yaml_sections(&sections).unwrap(),
+        Config::from_path("fixtures/")
+    );
+}
+
+#[test]
+fn it_fails_on_invalid_yaml_sections() {
+    let sections = YamlSections {
+        rooms: "- id: one\n".to_string(),
+        ..YamlSections::from_files(&fixture_files()).unwrap()
+    };
+    assert!(Config::from_yaml_sections(&sections).is_err());
+}
+
+#[test]
+fn it_loads_games_from_readers() {
+    let config = Config::from_path("fixtures/");
+    for (data, format) in [
+        (config.to_json(), ConfigFormat::Json),
+        (config.to_yaml(), ConfigFormat::Yaml),
+        (config.to_toml(), ConfigFormat::Toml),
+    ] {
+        assert_eq!(
+            Config::from_reader(data.as_bytes(), format).unwrap(),
+            config
+        );
+    }
+}
+
+#[test]
+fn it_reads_folders_with_or_without_a_trailing_separator() {
+    let config = Config::try_from_path("fixtures").unwrap();
+    assert_eq!(config, Config::from_path("fixtures/"));
+    assert_eq!(
+        Config::lint_path("fixtures").unwrap(),
+        Config::lint_path("fixtures/").unwrap()
+    );
+}
@@ -0,0 +1,107 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use super::{Config, Effect};
+
+/// The stats the player starts the game with.
+///
+/// These are read from the optional `stats.yml` file or
+/// the `stats` field in JSON data. When missing, health
+/// isn't tracked and the player can't die.
+///
+/// # Examples:
+/// ```rust
+/// # use nightrunner_lib::config::PlayerStats;
+/// let yaml = r#"
+/// health: 10
+/// death_text: You collapse from your wounds.
+/// custom:
+///   strength: 3
+/// "#;
+/// let stats: PlayerStats = serde_yaml::from_str(yaml).unwrap();
+/// assert_eq!(stats.health, Some(10));
+/// assert_eq!(stats.custom["strength"], 3);
+/// ```
+#[derive(Debug, Clone, Eq, PartialEq, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case", default)]
+pub struct PlayerStats {
+    /// The health the player starts with. The game is
+    /// lost when it drops to zero or below. When `None`
+    /// health isn't tracked.
+    pub health: Option<i32>,
+    /// Shown when the player's health drops to zero.
+    /// Default is "You have died."
+    pub death_text: String,
+    /// Any other stats the game tracks, and the values
+    /// the player starts with.
+    pub custom: BTreeMap<String, i32>,
+}
+
+impl Default for PlayerStats {
+    fn default() -> Self {
+        PlayerStats {
+            health: None,
+            death_text: "You have died.".to_string(),
+            custom: BTreeMap::new(),
+        }
+    }
+}
+
+/// Points awarded to the player, and what they were
+/// awarded for. Each reason is only awarded once.
+#[derive(Debug, Clone, Eq, Ord, PartialEq, PartialOrd, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub struct Award {
+    /// What the points were awarded for.
+    pub reason: String,
+    /// How many points were awarded.
+    pub points: i32,
+}
+
+/// How the game ended.
+#[derive(Debug, Clone, Eq, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub struct GameOver {
+    /// Whether the player won the game.
+    pub won: bool,
+    /// The text shown to the player when the game ends.
+    pub text: String,
+}
+
+impl Config {
+    /// Returns every effect in the game, from events,
+    /// daemons, subjects, and items.
+    pub fn effects(&self) -> Vec<&Effect> {
+        self.effect_sources()
+            .into_iter()
+            .flat_map(|(_, effects)| effects)
+            .collect()
+    }
+    /// Returns the points that can be awarded in the game,
+    /// one per reason, in the order they appear in the
+    /// configuration.
+    pub fn awards(&self) -> Vec<Award> {
+        let mut awards: Vec<Award> = vec![];
+        for effect in self.effects() {
+            if let Effect::AwardPoints { reason, points } = effect {
+                if !awards.iter().any(|award| award.reason == *reason) {
+                    awards.push(Award {
+                        reason: reason.clone(),
+                        points: *points,
+                    });
+                }
+            }
+        }
+        awards
+    }
+    /// Returns the highest score the player can get, which
+    /// leaves out the awards that take points away.
+    pub fn max_score(&self) -> i32 {
+        self.awards()
+            .iter()
+            .map(|award| award.points.max(0))
+            .fold(0, i32::saturating_add)
+    }
+}
@@ -0,0 +1,164 @@
+use std::collections::BTreeMap;
+
+use serde_json::Value;
+
+use super::ConfigSection;
+use crate::parser::errors::{TooManyIds, UnknownSymbol};
+use crate::NRResult;
+
+/// The fields that reference entries of a section, wherever
+/// they appear in the game data.
+pub(crate) const REFERENCES: [(&str, ConfigSection); 22] = [
+    ("location", ConfigSection::Rooms),
+    ("destination", ConfigSection::Rooms),
+    ("move_subject_to_location", ConfigSection::Rooms),
+    ("room_id", ConfigSection::Rooms),
+    ("narrative_after_room", ConfigSection::Rooms),
+    ("rooms", ConfigSection::Rooms),
+    ("narrative", ConfigSection::Narratives),
+    ("narrative_after", ConfigSection::Narratives),
+    ("required_verb", ConfigSection::Verbs),
+    ("required_subject", ConfigSection::Subjects),
+    ("add_subject", ConfigSection::Subjects),
+    ("subject_id", ConfigSection::Subjects),
+    ("subject_ids", ConfigSection::Subjects),
+    ("required_item", ConfigSection::Items),
+    ("add_item", ConfigSection::Items),
+    ("remove_item", ConfigSection::Items),
+    ("item_id", ConfigSection::Items),
+    ("item_ids", ConfigSection::Items),
+    ("key", ConfigSection::Items),
+    ("required_events", ConfigSection::Events),
+    ("event_id", ConfigSection::Events),
+    ("after_event", ConfigSection::Events),
+];
+
+/// Subject locations that aren't rooms.
+const LOCATION_KEYWORDS: [&str; 2] = ["nowhere", "following_player"];
+
+/// The numeric ids given to entries with symbolic ids, like
+/// `id: brass_key`, so other entries can reference them by
+/// name.
+#[derive(Debug, Default)]
+pub(crate) struct Symbols {
+    ids: BTreeMap<ConfigSection, BTreeMap<String, u16>>,
+}
+
+impl Symbols {
+    /// Gives every entry with a symbolic id an id after the
+    /// highest numeric id of its section, in the order the
+    /// entries are written. Entries with the same symbolic
+    /// id get the same id. Fails when there are more ids than
+    /// fit in a `u16`.
+    pub(crate) fn collect(data: &Value) -> NRResult<Symbols> {
+        let mut symbols = Symbols::default();
+        for section in ConfigSection::ALL {
+            let entries = match data[section.json_key()].as_array() {
+                Some(entries) => entries,
+                None => continue,
+            };
+            let mut next_id = entries
+                .iter()
+                .filter_map(|entry| entry["id"].as_u64())
+                .max()
+                .map_or(1, |id| id.saturating_add(1));
+            let ids = symbols.ids.entry(section).or_default();
+            for symbol in entries.iter().filter_map(|entry| entry["id"].as_str()) {
+                if !ids.contains_key(symbol) {
+                    let id = u16::try_from(next_id).map_err(|_| TooManyIds {
+                        kind: section.entry_name().to_string(),
+                    })?;
+                    ids.insert(symbol.to_string(), id);
+                    next_id += 1;
+                }
+            }
+        }
+        Ok(symbols)
+    }
+    /// Returns the numeric id given to a symbolic id.
+    pub(crate) fn id(&self, section: ConfigSection, symbol: &str) -> Option<u16> {
+        self.ids.get(&section)?.get(symbol).copied()
+    }
+    /// Replaces the symbolic ids, and every reference to
+    /// them, with their numeric ids.
+    pub(crate) fn resolve(&self, data: &mut Value) -> NRResult<()> {
+        for section in ConfigSection::ALL {
+            let entries = match data
+                .get_mut(section.json_key())
+                .and_then(Value::as_array_mut)
+            {
+                Some(entries) => entries,
+                None => continue,
+            };
+            for entry in entries {
+                if let Some(symbol) = entry["id"].as_str() {
+                    let id = self.lookup(section, "id", symbol)?;
+                    entry["id"] = id;
+                }
+                self.resolve_references(entry)?;
+            }
+        }
+        Ok(())
+    }
+    fn resolve_references(&self, value: &mut Value) -> NRResult<()> {
+        match value {
+            Value::Object(fields) => {
+                for (field, value) in fields.iter_mut() {
+                    match REFERENCES.iter().find(|(name, _)| name == field) {
+                        Some((_, section)) => self.resolve_reference(*section, field, value)?,
+                        None => self.resolve_references(value)?,
+                    }
+                }
+            }
+            Value::Array(values) => {
+                for value in values {
+                    self.resolve_references(value)?;
+                }
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+    fn resolve_reference(
+        &self,
+        section: ConfigSection,
+        field: &str,
+        value: &mut Value,
+    ) -> NRResult<()> {
+        match value {
+            Value::String(symbol)
+                if !(field == "location" && LOCATION_KEYWORDS.contains(&symbol.as_str())) =>
+            {
+                *value = self.lookup(section, field, symbol)?;
+            }
+            Value::Array(values) => {
+                for value in values {
+                    self.resolve_reference(section, field, value)?;
+                }
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+    fn lookup(&self, section: ConfigSection, field: &str, symbol: &str) -> NRResult<Value> {
+        match self.id(section, symbol) {
+            Some(id) => Ok(Value::from(id)),
+            None => Err(UnknownSymbol {
+                field: field.to_string(),
+                kind: section.entry_name().to_string(),
+                symbol: symbol.to_string(),
+            }
+            .into()),
+        }
+    }
+}
+
+/// Replaces the symbolic ids in game data, in the layout of
+/// the JSON data, with numeric ids.
+pub(crate) fn resolve_symbols(data: &mut Value) -> NRResult<()> {
+    Symbols::collect(data)?.resolve(data)
+}
+
+#[cfg(test)]
+#[path = "symbols_tests.rs"]
+mod symbols_tests;
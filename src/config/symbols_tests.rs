@@ -0,0 +1,191 @@
+use super::*;
+use crate::config::{Config, SubjectLocation};
+#[cfg(test)]
+use pretty_assertions::assert_eq;
+
+const GAME: &str = r#"
+intro: A long night.
+allowed_verbs:
+  - id: 1
+    names: [look]
+    verb_function: look
+  - id: open
+    names: [open]
+    verb_function: normal
+items:
+  - id: brass_key
+    name: key
+    description: a brass key
+    can_pick: true
+  - id: door
+    name: door
+    description: a heavy door
+    can_pick: false
+    capabilities:
+      key: brass_key
+subjects:
+  - id: guard
+    name: guard
+    description: a sleepy guard
+    default_text: zzz
+narratives:
+  - id: hall
+    text: a long hall
+    description: hall
+  - id: cellar
+    text: a damp cellar
+    description: cellar
+  - id: door_opens
+    text: the door creaks open
+    description: door opens
+room_blueprints:
+  - id: hall
+    name: hall
+    description: a hall
+    exits:
+      - room_id: cellar
+        direction: south
+    item_ids: [brass_key, door]
+    narrative: hall
+    subject_ids: [guard]
+  - id: cellar
+    name: cellar
+    description: a cellar
+    exits:
+      - room_id: hall
+        direction: north
+    item_ids: []
+    narrative: cellar
+    subject_ids: []
+events:
+  - id: open_door
+    name: open door
+    description: open the door with the key
+    location: hall
+    destination: cellar
+    narrative: door_opens
+    required_verb: open
+    required_subject: ~
+    required_item: brass_key
+    completed: false
+    add_item: ~
+    remove_item: ~
+    required_events: []
+    add_subject: ~
+    move_subject_to_location: ~
+    move_subjects:
+      - subject_id: guard
+        location: following_player
+daemons:
+  - id: 1
+    name: wake
+    conditions:
+      - type: event_completed
+        event_id: open_door
+    effects:
+      - type: move_subject
+        subject_id: guard
+        location: cellar
+"#;
+
+#[test]
+fn it_resolves_symbolic_ids() {
+    let config = Config::from_yaml(GAME);
+    let item_ids: Vec<u16> = config.items.iter().map(|item| item.id).collect();
+    assert_eq!(item_ids, vec![1, 2]);
+    let verb_ids: Vec<u16> = config.allowed_verbs.iter().map(|verb| verb.id).collect();
+    assert_eq!(verb_ids, vec![1, 2]);
+    assert_eq!(config.items[1].capabilities.key, Some(1));
+    let event = &config.events[0];
+    assert_eq!(event.id, 1);
+    assert_eq!(event.location, 1);
+    assert_eq!(event.destination, Some(2));
+    assert_eq!(event.narrative, Some(3));
+    assert_eq!(event.required_verb, Some(2));
+    assert_eq!(event.required_item, Some(1));
+    assert_eq!(event.move_subjects[0].subject_id, 1);
+    assert_eq!(
+        event.move_subjects[0].location,
+        SubjectLocation::FollowingPlayer
+    );
+    let room = &config.room_blueprints[0];
+    assert_eq!(room.exits[0].room_id, 2);
+    assert_eq!(room.item_ids, vec![1, 2]);
+    assert_eq!(room.subject_ids, vec![1]);
+    assert_eq!(room.narrative, 1);
+}
+
+#[test]
+fn it_resolves_symbols_in_conditions_and_effects() {
+    let config = Config::from_yaml(GAME);
+    let daemon = serde_json::to_value(&config.daemons[0]).unwrap();
+    assert_eq!(daemon["conditions"][0]["event_id"], 1);
+    assert_eq!(daemon["effects"][0]["subject_id"], 1);
+    assert_eq!(daemon["effects"][0]["location"], 2);
+}
+
+#[test]
+fn it_numbers_symbols_after_numeric_ids() {
+    let mut data: Value = serde_yaml::from_str(GAME).unwrap();
+    data["items"] = serde_json::json!([
+        { "id": "lamp" },
+        { "id": 7 },
+        { "id": "rope" },
+        { "id": "lamp" },
+    ]);
+    let symbols = Symbols::collect(&data).unwrap();
+    assert_eq!(symbols.id(ConfigSection::Items, "lamp"), Some(8));
+    assert_eq!(symbols.id(ConfigSection::Items, "rope"), Some(9));
+    assert_eq!(symbols.id(ConfigSection::Rooms, "lamp"), None);
+}
+
+#[test]
+fn it_keeps_numeric_ids() {
+    let config = Config::from_path("fixtures/");
+    let mut data: Value = serde_json::from_str(&config.to_json()).unwrap();
+    let before = data.clone();
+    resolve_symbols(&mut data).unwrap();
+    assert_eq!(data, before);
+}
+
+#[test]
+fn it_reports_unknown_symbols() {
+    let game = GAME.replace("required_item: brass_key", "required_item: bras_key");
+    let mut data: Value = serde_yaml::from_str(&game).unwrap();
+    let error = resolve_symbols(&mut data).unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "The required_item \"bras_key\" doesn't match the id of any item."
+    );
+}
+
+#[test]
+fn it_looks_up_symbols_in_the_referenced_section() {
+    // rooms and narratives can share names, but an item
+    // reference can't use a room's symbol
+    let game = GAME.replace("item_ids: [brass_key, door]", "item_ids: [hall]");
+    let mut data: Value = serde_yaml::from_str(&game).unwrap();
+    let error = resolve_symbols(&mut data).unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "The item_ids \"hall\" doesn't match the id of any item."
+    );
+}
+
+#[test]
+fn it_reports_symbols_past_the_largest_id() {
+    let mut data: Value = serde_yaml::from_str(GAME).unwrap();
+    data["items"] = serde_json::json!([
+        { "id": u16::MAX - 1 },
+        { "id": "lamp" },
+        { "id": "rope" },
+    ]);
+    let error = Symbols::collect(&data).unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "There are too many items to give them all an id, ids can't be larger than 65535."
+    );
+    data["items"].as_array_mut().unwrap().pop();
+    let symbols = Symbols::collect(&data).unwrap();
+    assert_eq!(symbols.id(ConfigSection::Items, "lamp"), Some(u16::MAX));
+}
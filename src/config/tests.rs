@@ -33,7 +33,8 @@ fn it_creates_items() {
             id: 1,
             name: String::from("sword"),
             description: String::from("a rusty sword"),
-            can_pick: true
+            can_pick: true,
+            capabilities: ItemCapabilities::default(),
         }],
         serde_yaml::from_str::<Vec<Item>>(items_config).unwrap()
     );
@@ -66,7 +67,17 @@ fn it_creates_subjects() {
             id: 1,
             name: String::from("text"),
             description: String::from("text"),
-            default_text: String::from("text")
+            default_text: String::from("text"),
+            behavior: None,
+            dialogue: None,
+            topics: vec![],
+            unknown_topic_text: None,
+            accepts: vec![],
+            refusal_text: None,
+            disposition: 0,
+            flags: vec![],
+            disposition_texts: vec![],
+            item_ids: vec![],
         }],
         serde_yaml::from_str::<Vec<Subject>>(subject_config).unwrap()
     );
@@ -154,7 +165,13 @@ fn it_creates_events() {
             add_subject: None,
             remove_subject: false,
             move_subject_to_location: None,
+            move_subjects: vec![],
+            at_turn: None,
+            turns_after: None,
+            effects: vec![],
             narrative_after: None,
+            narrative_after_room: None,
+            narrative_changes: vec![],
         },],
         serde_yaml::from_str::<Vec<Event>>(events_config).unwrap()
     );
@@ -182,3 +199,113 @@ fn it_creates_state() {
         "state2 and state_object should be the same"
     );
 }
+#[test]
+fn it_sets_narratives_in_any_room() {
+    let mut state = mock_state();
+    assert!(state.set_room_narrative(2, 3).is_ok());
+    assert_eq!(state.rooms[1].narrative, 3);
+    assert_eq!(state.rooms[0].narrative, 1, "current room shouldn't change");
+    assert_eq!(
+        state.set_room_narrative(42, 3).unwrap_err().to_string(),
+        InvalidRoom.to_string()
+    );
+    assert_eq!(
+        state.set_room_narrative(2, 42).unwrap_err().to_string(),
+        InvalidNarrative.to_string()
+    );
+    state.set_narrative(4);
+    assert_eq!(state.get_narrative().id, 4);
+}
+#[test]
+fn it_tracks_subject_locations() {
+    let mut state = mock_state();
+    assert_eq!(state.subject_location(1), Some(SubjectLocation::Room(1)));
+    assert_eq!(state.subject_location(2), Some(SubjectLocation::Room(2)));
+    assert_eq!(state.subject_location(42), None);
+
+    // subject2 lives in room 2 while the player is in room 1,
+    // moving it shouldn't leave a copy behind.
+    state.rooms[1].subjects[0].description = "changed at runtime".to_string();
+    state.move_subject(2, 1).unwrap();
+    assert_eq!(state.subject_location(2), Some(SubjectLocation::Room(1)));
+    assert!(state.rooms[1].subjects.is_empty());
+    assert_eq!(
+        state
+            .subjects_in_room(1)
+            .iter()
+            .map(|s| s.id)
+            .collect::<Vec<u16>>(),
+        vec![1, 2]
+    );
+    assert_eq!(
+        state.subjects_in_room(1)[1].description,
+        "changed at runtime",
+        "the subject should keep its runtime state"
+    );
+
+    state
+        .set_subject_location(2, SubjectLocation::FollowingPlayer)
+        .unwrap();
+    assert_eq!(
+        state.subject_location(2),
+        Some(SubjectLocation::FollowingPlayer)
+    );
+    state.current_room = 2;
+    assert_eq!(state.subjects_in_room(2)[0].id, 2);
+    assert!(state.subjects_in_room(1).iter().all(|s| s.id != 2));
+
+    state.remove_subject(2).unwrap();
+    assert_eq!(state.subject_location(2), Some(SubjectLocation::Nowhere));
+    assert!(state.subjects_in_room(2).is_empty());
+    state.add_subject_by_id(2).unwrap();
+    assert_eq!(state.subject_location(2), Some(SubjectLocation::Room(2)));
+    let mut subject = state.subject(2).unwrap().clone();
+    subject.disposition = 3;
+    state.current_room = 1;
+    state.add_subject(subject).unwrap();
+    assert_eq!(state.subject_location(2), Some(SubjectLocation::Room(1)));
+    assert_eq!(state.subject(2).unwrap().disposition, 3);
+    assert!(state.subjects_in_room(2).is_empty());
+    state.move_subject(2, 2).unwrap();
+
+    assert_eq!(
+        state.move_subject(2, 42).unwrap_err().to_string(),
+        InvalidRoom.to_string()
+    );
+    assert_eq!(
+        state.subject_location(2),
+        Some(SubjectLocation::Room(2)),
+        "a failed move shouldn't lose the subject"
+    );
+    assert_eq!(
+        state.move_subject(42, 1).unwrap_err().to_string(),
+        InvalidSubject.to_string()
+    );
+}
+#[test]
+fn it_deserializes_subject_locations() {
+    let moves_yaml = r"---
+          - subject_id: 1
+            location: 2
+          - subject_id: 2
+            location: nowhere
+          - subject_id: 3
+            location: following_player";
+    assert_eq!(
+        vec![
+            SubjectMove {
+                subject_id: 1,
+                location: SubjectLocation::Room(2),
+            },
+            SubjectMove {
+                subject_id: 2,
+                location: SubjectLocation::Nowhere,
+            },
+            SubjectMove {
+                subject_id: 3,
+                location: SubjectLocation::FollowingPlayer,
+            },
+        ],
+        serde_yaml::from_str::<Vec<SubjectMove>>(moves_yaml).unwrap()
+    );
+}
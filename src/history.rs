@@ -0,0 +1,342 @@
+use std::collections::{BTreeMap, VecDeque};
+use std::sync::Arc;
+
+use rand::rngs::StdRng;
+
+use crate::config::rooms::Room;
+use crate::config::{
+    ActiveDialogue, Config, Event, GameOver, ItemState, Player, State, Storage, Subject,
+};
+
+/// How many commands can be undone by default.
+pub const DEFAULT_HISTORY_LIMIT: usize = 100;
+
+/// The states the game went through, used to undo and
+/// redo commands.
+///
+/// Each entry only holds the parts of the state that change
+/// as the game is played, like the player, the variables,
+/// and the items, subjects, narrative, and completed events
+/// of each room. Subjects only keep their disposition, flags,
+/// and items. The rest, such as the config, the names,
+/// descriptions, and exits of the rooms, and the dialogue of
+/// the subjects, is taken from the current state and the
+/// config when a command is undone or redone. Once
+/// the limit is reached, the oldest state is dropped to
+/// make room for the newest one.
+///
+/// # Examples:
+/// ```rust
+/// # use nightrunner_lib::config::{Config, State};
+/// # use nightrunner_lib::history::History;
+/// let state = State::init(Config::from_path("./fixtures/"));
+/// let mut history = History::new(2);
+/// for turn in 1..=3 {
+///     let mut next = state.clone();
+///     next.turn = turn;
+///     history.push(next);
+/// }
+/// // only the last two states are kept
+/// assert_eq!(history.len(), 2);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct History {
+    limit: usize,
+    past: VecDeque<Snapshot>,
+    future: Vec<Snapshot>,
+}
+
+/// The parts of a room that change as the game is played.
+#[derive(Debug, Clone, PartialEq)]
+struct RoomSnapshot {
+    stash: Storage,
+    narrative: u16,
+    subjects: Vec<SubjectSnapshot>,
+    completed_events: Vec<u16>,
+}
+
+/// A subject in a snapshot. Subjects that are still as the
+/// config describes them, apart from what changes as the
+/// game is played, only keep those changes.
+#[derive(Debug, Clone, PartialEq)]
+enum SubjectSnapshot {
+    Changes {
+        id: u16,
+        disposition: i32,
+        flags: Vec<String>,
+        item_ids: Vec<u16>,
+    },
+    Full(Box<Subject>),
+}
+
+impl SubjectSnapshot {
+    fn take(config: &Config, subject: Subject) -> SubjectSnapshot {
+        // every field is listed, so a new one has to be
+        // sorted into the ones that change or not
+        let Subject {
+            id,
+            name: _,
+            description: _,
+            default_text: _,
+            behavior: _,
+            dialogue: _,
+            topics: _,
+            unknown_topic_text: _,
+            accepts: _,
+            refusal_text: _,
+            disposition,
+            flags,
+            disposition_texts: _,
+            item_ids,
+        } = subject.clone();
+        let snapshot = SubjectSnapshot::Changes {
+            id,
+            disposition,
+            flags,
+            item_ids,
+        };
+        match snapshot.restore(config) {
+            Some(restored) if restored == subject => snapshot,
+            _ => SubjectSnapshot::Full(Box::new(subject)),
+        }
+    }
+    fn restore(&self, config: &Config) -> Option<Subject> {
+        match self {
+            SubjectSnapshot::Changes {
+                id,
+                disposition,
+                flags,
+                item_ids,
+            } => {
+                let subject = config.subjects.iter().find(|s| s.id == *id)?;
+                Some(Subject {
+                    disposition: *disposition,
+                    flags: flags.clone(),
+                    item_ids: item_ids.clone(),
+                    ..subject.clone()
+                })
+            }
+            SubjectSnapshot::Full(subject) => Some(*subject.clone()),
+        }
+    }
+}
+
+fn take_subjects(config: &Config, subjects: Vec<Subject>) -> Vec<SubjectSnapshot> {
+    subjects
+        .into_iter()
+        .map(|subject| SubjectSnapshot::take(config, subject))
+        .collect()
+}
+
+fn restore_subjects(config: &Config, subjects: Vec<SubjectSnapshot>) -> Vec<Subject> {
+    subjects
+        .iter()
+        .filter_map(|subject| subject.restore(config))
+        .collect()
+}
+
+/// The parts of the state that change as the game is played.
+#[derive(Debug, Clone, PartialEq)]
+struct Snapshot {
+    input: String,
+    current_room: u16,
+    player: Player,
+    rooms: Vec<RoomSnapshot>,
+    offstage_subjects: Vec<SubjectSnapshot>,
+    followers: Vec<SubjectSnapshot>,
+    turn: u32,
+    event_turns: BTreeMap<u16, u32>,
+    turns_in_room: u32,
+    variables: BTreeMap<String, i32>,
+    item_states: BTreeMap<u16, ItemState>,
+    dialogue: Option<ActiveDialogue>,
+    game_over: Option<GameOver>,
+    seed: u64,
+    rng: StdRng,
+}
+
+impl Snapshot {
+    fn take(state: State) -> Snapshot {
+        // every field is listed, so a new one can't be left
+        // out of undoing by mistake
+        let State {
+            input,
+            current_room,
+            player,
+            rooms,
+            offstage_subjects,
+            followers,
+            turn,
+            event_turns,
+            turns_in_room,
+            variables,
+            item_states,
+            dialogue,
+            game_over,
+            seed,
+            rng,
+            config,
+        } = state;
+        let rooms = rooms
+            .into_iter()
+            .map(|room| {
+                let Room {
+                    id: _,
+                    name: _,
+                    description: _,
+                    exits: _,
+                    stash,
+                    events,
+                    narrative,
+                    subjects,
+                } = room;
+                RoomSnapshot {
+                    stash,
+                    narrative,
+                    subjects: take_subjects(&config, subjects),
+                    completed_events: events
+                        .iter()
+                        .filter(|event| event.is_completed())
+                        .map(|event| event.id)
+                        .collect(),
+                }
+            })
+            .collect();
+        Snapshot {
+            input,
+            current_room,
+            player,
+            rooms,
+            offstage_subjects: take_subjects(&config, offstage_subjects),
+            followers: take_subjects(&config, followers),
+            turn,
+            event_turns,
+            turns_in_room,
+            variables,
+            item_states,
+            dialogue,
+            game_over,
+            seed,
+            rng,
+        }
+    }
+    /// Rebuilds the state the snapshot was taken from, using
+    /// `current` for everything that doesn't change.
+    fn restore(self, current: &State) -> State {
+        let config = &current.config;
+        let rooms = current
+            .rooms
+            .iter()
+            .zip(self.rooms)
+            .map(|(room, snapshot)| Room {
+                id: room.id,
+                name: room.name.clone(),
+                description: room.description.clone(),
+                exits: room.exits.clone(),
+                stash: snapshot.stash,
+                events: room
+                    .events
+                    .iter()
+                    .map(|event| Event {
+                        completed: snapshot.completed_events.contains(&event.id),
+                        ..event.clone()
+                    })
+                    .collect(),
+                narrative: snapshot.narrative,
+                subjects: restore_subjects(config, snapshot.subjects),
+            })
+            .collect();
+        State {
+            input: self.input,
+            current_room: self.current_room,
+            player: self.player,
+            rooms,
+            offstage_subjects: restore_subjects(config, self.offstage_subjects),
+            followers: restore_subjects(config, self.followers),
+            turn: self.turn,
+            event_turns: self.event_turns,
+            turns_in_room: self.turns_in_room,
+            variables: self.variables,
+            item_states: self.item_states,
+            dialogue: self.dialogue,
+            game_over: self.game_over,
+            seed: self.seed,
+            rng: self.rng,
+            config: Arc::clone(config),
+        }
+    }
+}
+
+impl History {
+    /// Creates an empty history keeping at most `limit`
+    /// states. A limit of `0` disables undoing.
+    pub fn new(limit: usize) -> History {
+        History {
+            limit,
+            past: VecDeque::new(),
+            future: Vec::new(),
+        }
+    }
+    /// Returns the maximum number of states kept.
+    pub fn limit(&self) -> usize {
+        self.limit
+    }
+    /// Changes the maximum number of states kept, dropping
+    /// the oldest ones if there are more than that.
+    pub fn set_limit(&mut self, limit: usize) {
+        self.limit = limit;
+        self.truncate();
+    }
+    /// Returns the number of states that can be undone.
+    pub fn len(&self) -> usize {
+        self.past.len()
+    }
+    /// Returns true if there are no states to undo.
+    pub fn is_empty(&self) -> bool {
+        self.past.is_empty()
+    }
+    /// Remembers the state before a command, so the
+    /// command can be undone. Since the command starts a
+    /// new timeline, the states that could be redone are
+    /// forgotten.
+    pub fn push(&mut self, state: State) {
+        self.past.push_back(Snapshot::take(state));
+        self.future.clear();
+        self.truncate();
+    }
+    /// Returns the state before the last command, keeping
+    /// `current` so it can be redone.
+    pub fn undo(&mut self, current: State) -> Option<State> {
+        let previous = self.past.pop_back()?.restore(&current);
+        self.future.push(Snapshot::take(current));
+        Some(previous)
+    }
+    /// Returns the state the last undone command led to,
+    /// keeping `current` so it can be undone again.
+    pub fn redo(&mut self, current: State) -> Option<State> {
+        let next = self.future.pop()?.restore(&current);
+        self.past.push_back(Snapshot::take(current));
+        self.truncate();
+        Some(next)
+    }
+    /// Forgets every state.
+    pub fn clear(&mut self) {
+        self.past.clear();
+        self.future.clear();
+    }
+    fn truncate(&mut self) {
+        while self.past.len() > self.limit {
+            self.past.pop_front();
+        }
+    }
+}
+
+impl Default for History {
+    fn default() -> Self {
+        History::new(DEFAULT_HISTORY_LIMIT)
+    }
+}
+
+#[cfg(test)]
+#[path = "history_tests.rs"]
+mod history_tests;
@@ -0,0 +1,122 @@
+use super::*;
+use crate::config::{Config, SubjectLocation};
+#[cfg(test)]
+use pretty_assertions::assert_eq;
+use std::sync::Arc;
+
+fn state_at_turn(state: &State, turn: u32) -> State {
+    let mut state = state.clone();
+    state.turn = turn;
+    state
+}
+
+#[test]
+fn it_undoes_and_redoes_states() {
+    let state = State::init(Config::from_path("fixtures/"));
+    let mut history = History::new(10);
+    history.push(state_at_turn(&state, 0));
+    history.push(state_at_turn(&state, 1));
+    let current = state_at_turn(&state, 2);
+
+    let previous = history.undo(current).unwrap();
+    assert_eq!(previous.turn, 1);
+    let previous = history.undo(previous).unwrap();
+    assert_eq!(previous.turn, 0);
+    assert!(history.undo(previous.clone()).is_none());
+
+    let next = history.redo(previous).unwrap();
+    assert_eq!(next.turn, 1);
+    let next = history.redo(next).unwrap();
+    assert_eq!(next.turn, 2);
+    assert!(history.redo(next).is_none());
+    assert_eq!(history.len(), 2);
+}
+
+#[test]
+fn it_keeps_only_the_newest_states() {
+    let state = State::init(Config::from_path("fixtures/"));
+    let mut history = History::new(3);
+    for turn in 0..5 {
+        history.push(state_at_turn(&state, turn));
+    }
+    assert_eq!(history.len(), 3);
+    history.set_limit(1);
+    assert_eq!(history.len(), 1);
+    assert_eq!(history.undo(state.clone()).unwrap().turn, 4);
+
+    let mut history = History::new(0);
+    history.push(state.clone());
+    assert!(history.is_empty());
+}
+
+#[test]
+fn it_shares_the_config_between_states() {
+    let state = State::init(Config::from_path("fixtures/"));
+    let mut history = History::default();
+    history.push(state.clone());
+    let previous = history.undo(state.clone()).unwrap();
+    assert!(Arc::ptr_eq(&previous.config, &state.config));
+}
+
+#[test]
+fn it_forgets_undone_states_on_a_new_command() {
+    let state = State::init(Config::from_path("fixtures/"));
+    let mut history = History::default();
+    history.push(state_at_turn(&state, 0));
+    let previous = history.undo(state_at_turn(&state, 1)).unwrap();
+    history.push(previous);
+    assert!(history.redo(state_at_turn(&state, 2)).is_none());
+}
+
+#[test]
+fn it_restores_the_state_of_every_room() {
+    let state = State::init(Config::from_path("fixtures/"));
+    let mut history = History::default();
+    history.push(state.clone());
+
+    let mut changed = state.clone();
+    let item = changed.rooms[0].stash.items[0].clone();
+    changed.rooms[0].stash.remove_item(item.clone()).unwrap();
+    changed.player.inventory.add_item(item);
+    changed.set_narrative(4);
+    changed.complete_event(1);
+    changed
+        .set_subject_location(2, SubjectLocation::Nowhere)
+        .unwrap();
+    changed.variables.insert("fuel".to_string(), 3);
+    changed.turn = 1;
+
+    let previous = history.undo(changed.clone()).unwrap();
+    assert_eq!(previous, state);
+    let next = history.redo(previous).unwrap();
+    assert_eq!(next, changed);
+}
+
+#[test]
+fn it_keeps_only_what_changed_in_subjects() {
+    let mut state = State::init(Config::from_path("fixtures/"));
+    let mut history = History::default();
+    history.push(state.clone());
+
+    let mut changed = state.clone();
+    let subject = changed.subject_mut(2).unwrap();
+    subject.disposition = -4;
+    subject.flags.push("angry".to_string());
+    let mut renamed = changed.subject(1).unwrap().clone();
+    renamed.name = "renamed".to_string();
+    changed.add_subject(renamed.clone()).unwrap();
+
+    assert!(matches!(
+        SubjectSnapshot::take(&state.config, changed.subject(2).unwrap().clone()),
+        SubjectSnapshot::Changes { .. }
+    ));
+    assert_eq!(
+        SubjectSnapshot::take(&state.config, renamed.clone()),
+        SubjectSnapshot::Full(Box::new(renamed))
+    );
+
+    let previous = history.undo(changed.clone()).unwrap();
+    assert_eq!(previous, state);
+    state = history.redo(previous).unwrap();
+    assert_eq!(state, changed);
+}
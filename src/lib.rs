@@ -36,21 +36,33 @@
 //! for examples of valid YAML and JSON data, see the documentation for
 //! the `config` module.
 #![warn(missing_docs)]
-use config::{Config, State};
+use config::{Config, ConfigFormat, State, YamlSections};
+use history::History;
+use parser::dialogue::DialogueMessage;
 use parser::interpreter::EventMessage;
 use serde::{Deserialize, Serialize};
 use std::{
+    collections::BTreeMap,
     error::Error,
     fmt::{self, Display, Formatter},
+    sync::Arc,
 };
+use transcript::{Command, Transcript};
 extern crate console_error_panic_hook;
 use util::parse_room_text;
 /// Module containing the configuration code for this
 /// library.
 pub mod config;
+/// Undo and redo history for the game.
+pub mod history;
 /// The parser module contains a single function that
 /// parses the input string and returns a `ParsingResult`.
 pub mod parser;
+/// Scripted walkthroughs for testing games.
+#[cfg(not(target_arch = "wasm32"))]
+pub mod testing;
+/// Recording and replaying game sessions.
+pub mod transcript;
 /// Helper functions.
 pub mod util;
 extern crate wasm_bindgen;
@@ -90,9 +102,53 @@ pub enum ParsingResult {
     /// a subject without a current event associated with it. The value
     /// is the default text for the subject.
     SubjectNoEvent(String),
+    /// Returned when the player uses one of the built-in item verbs,
+    /// like eating, opening, or wearing an item. The value describes
+    /// what happened to the item.
+    ItemUsed(String),
+    /// Returned when the player gives an item to a subject who
+    /// accepts it. The item goes to the subject, and the value is
+    /// what the subject says followed by the effects' messages.
+    Give(String),
     /// Returned when an event is triggered by the player's command. The
     /// returned struct contains the text to be displayed to the player.
     EventSuccess(EventMessage),
+    /// Returned when the player talks to a subject with a dialogue,
+    /// or picks a choice in the current conversation. The returned
+    /// struct contains what the subject says and the numbered
+    /// choices the player can pick from next.
+    Dialogue(DialogueMessage),
+    /// Returned when the player issues a command with a verb that
+    /// has VerbFunction::Score as its verb_function. The value lists
+    /// the player's score, every award in the game marked with whether
+    /// the player earned it, and the player's other stats.
+    Score(String),
+    /// Returned when the game ends, either because the player won,
+    /// or because the player lost, for example when their health
+    /// dropped to zero. The text contains the result of the command
+    /// that ended the game, followed by the ending text. After this
+    /// the game refuses any command other than quitting, restarting,
+    /// undoing, or redoing.
+    GameOver {
+        /// Whether the player won the game.
+        won: bool,
+        /// The text to display to the player.
+        text: String,
+    },
+    /// Returned when the player issues a command with a verb that
+    /// has VerbFunction::Restart as its verb_function. The game
+    /// starts over and the value is the text of the first room, so
+    /// the front-end can clear the screen and display it.
+    Restart(EventMessage),
+    /// Returned when the player takes back the last command with a
+    /// verb that has VerbFunction::Undo as its verb_function. The
+    /// value is the text of the room the player is in after undoing.
+    Undo(EventMessage),
+    /// Returned when the player plays again a command that was taken
+    /// back, with a verb that has VerbFunction::Redo as its
+    /// verb_function. The value is the text of the room the player is
+    /// in after redoing.
+    Redo(EventMessage),
     /// Returned when the player issues a command with a verb that has
     /// VerbFunction::Quit as its verb_function. This variant is used
     /// to indicate to the front-end that the game should be quit.
@@ -109,6 +165,8 @@ impl Display for ParsingResult {
             ParsingResult::DropItem(msg) => write!(f, "{}", msg),
             ParsingResult::Inventory(msg) => write!(f, "{}", msg),
             ParsingResult::SubjectNoEvent(msg) => write!(f, "{}", msg),
+            ParsingResult::ItemUsed(msg) => write!(f, "{}", msg),
+            ParsingResult::Give(msg) => write!(f, "{}", msg),
             ParsingResult::EventSuccess(event_msg) => {
                 let EventMessage {
                     message,
@@ -121,6 +179,18 @@ impl Display for ParsingResult {
                 }
                 Ok(())
             }
+            ParsingResult::Dialogue(dialogue_msg) => {
+                write!(f, "{}", dialogue_msg.text)?;
+                for choice in &dialogue_msg.choices {
+                    write!(f, "\n{}. {}", choice.number, choice.text)?;
+                }
+                Ok(())
+            }
+            ParsingResult::Score(msg) => write!(f, "{}", msg),
+            ParsingResult::GameOver { won: _, text } => write!(f, "{}", text),
+            ParsingResult::Restart(event_msg) => write!(f, "{}", event_msg.message),
+            ParsingResult::Undo(event_msg) => write!(f, "{}", event_msg.message),
+            ParsingResult::Redo(event_msg) => write!(f, "{}", event_msg.message),
             ParsingResult::Quit => write!(f, "Quitting game"),
         }
     }
@@ -134,8 +204,8 @@ impl Display for ParsingResult {
 #[derive(Debug, PartialEq)]
 pub struct NightRunner {
     state: State,
-    previous_states: Vec<State>,
-    future_states: Vec<State>,
+    history: History,
+    transcript: Transcript,
 }
 
 /// You can use this to build a NightRunner
@@ -156,6 +226,8 @@ pub struct NightRunner {
 #[derive(Debug, PartialEq, Eq)]
 pub struct NightRunnerBuilder {
     config: Config,
+    seed: Option<u64>,
+    history_limit: usize,
 }
 impl NightRunnerBuilder {
     /// Creates a new empty NightRunnerBuilder
@@ -163,6 +235,8 @@ impl NightRunnerBuilder {
     pub fn new() -> NightRunnerBuilder {
         NightRunnerBuilder {
             config: Config::default(),
+            seed: None,
+            history_limit: history::DEFAULT_HISTORY_LIMIT,
         }
     }
     /// Creates a new NightRunnerBuilder with YAML
@@ -179,15 +253,178 @@ impl NightRunnerBuilder {
         self.config = Config::from_json(data);
         self
     }
+    /// Creates a new NightRunnerBuilder with the whole
+    /// game in one JSON, YAML or TOML file, picking the
+    /// format from the file extension. See
+    /// `Config::from_file` for the layout of the file and
+    /// how to include other files.
+    ///
+    /// # Panics
+    /// Panics when the game can't be loaded. Use
+    /// `Config::from_file` with `with_config` to handle
+    /// the error instead.
+    ///
+    /// # Examples:
+    /// ```rust
+    /// # use nightrunner_lib::NightRunnerBuilder;
+    /// let nr = NightRunnerBuilder::new()
+    ///     .with_file("fixtures/single_file/game.toml")
+    ///     .build();
+    /// ```
+    pub fn with_file(mut self, path: impl AsRef<std::path::Path>) -> NightRunnerBuilder {
+        let path = path.as_ref();
+        self.config = Config::from_file(path).unwrap_or_else(|error| {
+            panic!("Could not load the game at {}: {}", path.display(), error)
+        });
+        self
+    }
+    /// Creates a new NightRunnerBuilder with the YAML for
+    /// each file of the game, like files embedded with
+    /// `include_str!`. See `YamlSections`.
+    ///
+    /// # Panics
+    /// Panics when the YAML can't be read. Use
+    /// `Config::from_yaml_sections` with `with_config` to
+    /// handle the error instead.
+    pub fn with_yaml_sections(mut self, sections: &YamlSections) -> NightRunnerBuilder {
+        self.config = Config::from_yaml_sections(sections)
+            .unwrap_or_else(|error| panic!("Could not load the game: {}", error));
+        self
+    }
+    /// Creates a new NightRunnerBuilder with a map of file
+    /// names to their contents, laid out like the folder
+    /// read by `with_path_for_config`, so the YAML files can
+    /// be used without a filesystem.
+    ///
+    /// # Panics
+    /// Panics when a required file is missing or can't be
+    /// read. Use `Config::from_files` with `with_config` to
+    /// handle the error instead.
+    ///
+    /// # Examples:
+    /// ```rust
+    /// # use std::collections::BTreeMap;
+    /// # use nightrunner_lib::NightRunnerBuilder;
+    /// let mut files = BTreeMap::new();
+    /// files.insert("narratives.yml".to_string(), include_str!("../fixtures/narratives.yml").to_string());
+    /// files.insert("items.yml".to_string(), include_str!("../fixtures/items.yml").to_string());
+    /// files.insert("rooms.yml".to_string(), include_str!("../fixtures/rooms.yml").to_string());
+    /// files.insert("verbs.yml".to_string(), include_str!("../fixtures/verbs.yml").to_string());
+    /// files.insert("subjects.yml".to_string(), include_str!("../fixtures/subjects.yml").to_string());
+    /// files.insert("events.yml".to_string(), include_str!("../fixtures/events.yml").to_string());
+    /// files.insert("intro.yml".to_string(), include_str!("../fixtures/intro.yml").to_string());
+    /// let nr = NightRunnerBuilder::new().with_files(&files).build();
+    /// ```
+    pub fn with_files(mut self, files: &BTreeMap<String, String>) -> NightRunnerBuilder {
+        self.config = Config::from_files(files)
+            .unwrap_or_else(|error| panic!("Could not load the game: {}", error));
+        self
+    }
+    /// Creates a new NightRunnerBuilder with the whole game
+    /// read from a reader, in the layout of the JSON data
+    /// written in the given format.
+    ///
+    /// # Panics
+    /// Panics when the game can't be read. Use
+    /// `Config::from_reader` with `with_config` to handle
+    /// the error instead.
+    ///
+    /// # Examples:
+    /// ```rust
+    /// # use nightrunner_lib::NightRunnerBuilder;
+    /// # use nightrunner_lib::config::ConfigFormat;
+    /// let data = include_str!("../fixtures/single_file/game.toml");
+    /// let nr = NightRunnerBuilder::new()
+    ///     .with_reader(data.as_bytes(), ConfigFormat::Toml)
+    ///     .build();
+    /// ```
+    pub fn with_reader(
+        mut self,
+        reader: impl std::io::Read,
+        format: ConfigFormat,
+    ) -> NightRunnerBuilder {
+        self.config = Config::from_reader(reader, format)
+            .unwrap_or_else(|error| panic!("Could not load the game: {}", error));
+        self
+    }
+    /// Creates a new NightRunnerBuilder with a config
+    /// that was already loaded.
+    pub fn with_config(mut self, config: Config) -> NightRunnerBuilder {
+        self.config = config;
+        self
+    }
+    /// Sets the seed used for everything random in the
+    /// game, like subjects wandering between rooms. Games
+    /// built with the same seed play out the same way for
+    /// the same commands. When no seed is set a random one
+    /// is used.
+    pub fn with_seed(mut self, seed: u64) -> NightRunnerBuilder {
+        self.seed = Some(seed);
+        self
+    }
+    /// Sets how many commands can be undone. Once the limit
+    /// is reached, the oldest states are forgotten. A limit
+    /// of `0` disables undoing. The default is
+    /// `history::DEFAULT_HISTORY_LIMIT`.
+    pub fn with_history_limit(mut self, limit: usize) -> NightRunnerBuilder {
+        self.history_limit = limit;
+        self
+    }
     /// Creates a new NightRunner struct. This will fail
     /// if the config is invalid or missing.
     pub fn build(self) -> NightRunner {
-        let state = State::init(self.config);
+        let seed = self.seed.unwrap_or_else(rand::random);
+        let state = State::init_with_seed(self.config, seed);
         NightRunner {
             state,
-            previous_states: vec![],
-            future_states: vec![],
+            history: History::new(self.history_limit),
+            transcript: Transcript::new(seed),
+        }
+    }
+    /// Creates a new NightRunner struct and plays a recorded
+    /// session on it, using the seed the session was started
+    /// with. When built from the same config, the game ends
+    /// up in the same state, and its transcript matches the
+    /// recorded one.
+    pub fn replay(self, transcript: &Transcript) -> NightRunner {
+        let mut night_runner = self.with_seed(transcript.seed).build();
+        for entry in &transcript.entries {
+            // errors are part of the session and are recorded
+            // in the new transcript like any other outcome
+            night_runner.execute(entry.command.clone()).ok();
+        }
+        night_runner
+    }
+}
+
+impl NightRunner {
+    /// Runs a command on the game, and records it along
+    /// with its outcome in the transcript.
+    fn execute(&mut self, command: Command) -> NRResult<ParsingResult> {
+        let turn = self.state.turn;
+        let result = match &command {
+            Command::Input { text } => {
+                parser::parse_with_history(&self.state, &mut self.history, text)
+            }
+            Command::Rewind => parser::undo(&self.state, &mut self.history),
+            Command::FastForward => parser::redo(&self.state, &mut self.history),
+            Command::Reset { keep_history } => {
+                let state = State::init_shared(Arc::clone(&self.state.config), self.state.seed);
+                if *keep_history {
+                    self.history.push(self.state.clone());
+                } else {
+                    self.history.clear();
+                }
+                util::current_room_text(&state)
+                    .map(|room_text| (state, ParsingResult::Restart(room_text)))
+            }
         }
+        .map(|(state, parsing_result)| {
+            self.state = state;
+            parsing_result
+        });
+        self.transcript.record(turn, command, &result);
+        result
     }
 }
 
@@ -208,10 +445,9 @@ impl NightRunner {
     /// a result that can be used on the front-end to display
     /// the game to the user.
     pub fn parse_input(&mut self, input: &str) -> NRResult<ParsingResult> {
-        let (new_state, parsing_result) = parser::parse(&self.state, input)?;
-        self.previous_states.push(self.state.clone());
-        self.state = new_state;
-        Ok(parsing_result)
+        self.execute(Command::Input {
+            text: input.to_string(),
+        })
     }
     /// This is the main function that executes the game. Pass
     /// the input string to this function and it will return
@@ -221,13 +457,8 @@ impl NightRunner {
     /// return the result in JSON format. This is useful for
     /// front-ends that can't integrate with a rust library.
     pub fn json_parse_input(&mut self, input: &str) -> String {
-        let result = parser::parse(&self.state, input);
-        match result {
-            Ok((new_state, ok)) => {
-                self.previous_states.push(self.state.clone());
-                self.state = new_state;
-                serde_json::to_string(&ok).unwrap()
-            }
+        match self.parse_input(input) {
+            Ok(ok) => serde_json::to_string(&ok).unwrap(),
             Err(err) => format!(
                 "{{\"error\":{}}}",
                 serde_json::to_string(&err.to_string()).unwrap()
@@ -237,30 +468,55 @@ impl NightRunner {
     /// Rewinds the game state to the previous state.
     /// This is useful for undoing actions in the game.
     /// The state is saved in a stack, so you can rewind
-    /// multiple times.
+    /// multiple times. This is the same as a command with
+    /// a verb that has VerbFunction::Undo as its verb_function,
+    /// and returns `ParsingResult::Undo` with the text of the
+    /// room the player is in after rewinding.
     pub fn rewind_state(&mut self) -> NRResult<ParsingResult> {
-        if let Some(state) = self.previous_states.pop() {
-            self.future_states.push(self.state.clone());
-            self.state = state;
-            Ok(ParsingResult::Look("Rewound to previous state".to_string()))
-        } else {
-            Err("No previous state to rewind to".into())
-        }
+        self.execute(Command::Rewind)
     }
     /// Fast forwards the game state to the next state.
     /// This is useful for redoing actions in the game.
     /// The state is saved in a stack, so you can fast
-    /// forward multiple times.
+    /// forward multiple times. This is the same as a command
+    /// with a verb that has VerbFunction::Redo as its
+    /// verb_function, and returns `ParsingResult::Redo`.
+    /// Any command other than undoing forgets the states
+    /// that can be fast forwarded to.
     pub fn fast_forward_state(&mut self) -> NRResult<ParsingResult> {
-        if let Some(state) = self.future_states.pop() {
-            self.previous_states.push(self.state.clone());
-            self.state = state;
-            Ok(ParsingResult::Look(
-                "Fast forwarded to next state".to_string(),
-            ))
-        } else {
-            Err("No future state to fast forward to".into())
-        }
+        self.execute(Command::FastForward)
+    }
+    /// Changes how many commands can be undone, forgetting
+    /// the oldest states if there are more than that.
+    pub fn set_history_limit(&mut self, limit: usize) {
+        self.history.set_limit(limit);
+    }
+    /// Starts the game over, rebuilding the state from the
+    /// game's config with the same seed, and returns
+    /// `ParsingResult::Restart` with the text of the first room.
+    ///
+    /// When `keep_history` is true the state before the reset
+    /// can still be rewound to, otherwise the rewind history
+    /// is cleared.
+    ///
+    /// # Examples:
+    /// ```rust
+    /// use nightrunner_lib::NightRunnerBuilder;
+    /// let mut nr = NightRunnerBuilder::new()
+    ///     .with_path_for_config("fixtures/")
+    ///     .build();
+    /// nr.parse_input("south").unwrap();
+    /// nr.reset(false).unwrap();
+    /// assert!(nr.rewind_state().is_err());
+    /// ```
+    pub fn reset(&mut self, keep_history: bool) -> NRResult<ParsingResult> {
+        self.execute(Command::Reset { keep_history })
+    }
+    /// Returns the record of everything that happened in
+    /// the game so far. See [Transcript] for exporting and
+    /// replaying it.
+    pub fn transcript(&self) -> &Transcript {
+        &self.transcript
     }
     /// Returns the string with the game intro text. This can
     /// be used to display the game intro to the user, but isn't
@@ -321,13 +577,72 @@ pub enum JsMessage {
     /// a subject without a current event associated with it. The value
     /// is the default text for the subject.
     SubjectNoEvent(String),
+    /// Returned when the player uses one of the built-in item verbs.
+    ItemUsed(String),
+    /// Returned when the player gives an item to a subject who
+    /// accepts it.
+    Give(String),
     /// Returned when an event is triggered by the player's command. The
     /// returned struct contains the text to be displayed to the player.
     EventSuccess(EventMessage),
+    /// Returned when the player talks to a subject with a dialogue,
+    /// or picks a choice in the current conversation.
+    Dialogue(DialogueMessage),
+    /// Returned when the player checks the score.
+    Score(String),
+    /// Returned when the game ends.
+    GameOver {
+        /// Whether the player won the game.
+        won: bool,
+        /// The text to display to the player.
+        text: String,
+    },
+    /// Returned when the game starts over.
+    Restart(EventMessage),
+    /// Returned when the last command is taken back.
+    Undo(EventMessage),
+    /// Returned when a command that was taken back is played again.
+    Redo(EventMessage),
     /// Returned when a parser result isn't applicable to the wasm library
     NoOp,
 }
 
+#[cfg(any(target_arch = "wasm32", doc))]
+impl From<ParsingResult> for JsMessage {
+    fn from(parsing_result: ParsingResult) -> Self {
+        match parsing_result {
+            ParsingResult::Look(msg) => JsMessage::Look(msg),
+            ParsingResult::Help(msg) => JsMessage::Help(msg),
+            ParsingResult::NewItem(msg) => JsMessage::NewItem(msg),
+            ParsingResult::DropItem(msg) => JsMessage::DropItem(msg),
+            ParsingResult::Inventory(msg) => JsMessage::Inventory(msg),
+            ParsingResult::SubjectNoEvent(msg) => JsMessage::SubjectNoEvent(msg),
+            ParsingResult::ItemUsed(msg) => JsMessage::ItemUsed(msg),
+            ParsingResult::Give(msg) => JsMessage::Give(msg),
+            ParsingResult::EventSuccess(event_msg) => JsMessage::EventSuccess(event_msg),
+            ParsingResult::Dialogue(dialogue_msg) => JsMessage::Dialogue(dialogue_msg),
+            ParsingResult::Score(msg) => JsMessage::Score(msg),
+            ParsingResult::GameOver { won, text } => JsMessage::GameOver { won, text },
+            ParsingResult::Restart(event_msg) => JsMessage::Restart(event_msg),
+            ParsingResult::Undo(event_msg) => JsMessage::Undo(event_msg),
+            ParsingResult::Redo(event_msg) => JsMessage::Redo(event_msg),
+            ParsingResult::Quit => JsMessage::NoOp,
+        }
+    }
+}
+
+#[cfg(any(target_arch = "wasm32", doc))]
+impl NightRunner {
+    /// Runs a command on the game, and converts its result
+    /// to the message sent to JavaScript.
+    fn execute_for_js(&mut self, command: Command) -> Result<JsValue, JsError> {
+        match self.execute(command) {
+            Ok(ok) => Ok(serde_wasm_bindgen::to_value(&JsMessage::from(ok))?),
+            Err(err) => Err(JsError::new(&err.to_string())),
+        }
+    }
+}
+
 #[cfg(any(target_arch = "wasm32", doc))]
 #[wasm_bindgen]
 /// # Nightrunner Wasm Library
@@ -345,13 +660,34 @@ impl NightRunner {
     pub fn new(config: &str) -> NightRunner {
         console_error_panic_hook::set_once();
         let config = Config::from_json(config);
-        let state = State::init(config);
+        let seed = rand::random();
+        let state = State::init_with_seed(config, seed);
         NightRunner {
             state,
-            previous_states: vec![],
-            future_states: vec![],
+            history: History::default(),
+            transcript: Transcript::new(seed),
         }
     }
+    /// Creates a new game from an object mapping file names
+    /// to their contents, laid out like the folder of YAML
+    /// files for a game, such as `{"rooms.yml": "..."}`.
+    pub fn from_files(files: JsValue) -> Result<NightRunner, JsError> {
+        console_error_panic_hook::set_once();
+        let files: BTreeMap<String, String> = serde_wasm_bindgen::from_value(files)?;
+        let config = Config::from_files(&files).map_err(|err| JsError::new(&err.to_string()))?;
+        Ok(NightRunnerBuilder::new().with_config(config).build())
+    }
+    /// Creates a new game from the JSON config, and plays the
+    /// session recorded in the JSON transcript on it. See
+    /// `NightRunnerBuilder::replay`.
+    pub fn replay(config: &str, transcript: &str) -> Result<NightRunner, JsError> {
+        console_error_panic_hook::set_once();
+        let transcript =
+            Transcript::from_json(transcript).map_err(|err| JsError::new(&err.to_string()))?;
+        Ok(NightRunnerBuilder::new()
+            .with_json_data(config)
+            .replay(&transcript))
+    }
     /// This is the main function that executes the game. Pass
     /// the input string to this function and it will return
     /// a result that can be used on the front-end to display
@@ -361,25 +697,9 @@ impl NightRunner {
     /// to JSON is done by the `JsValue::from_serde` function from
     /// wasm_bindgen.
     pub fn parse(&mut self, input: &str) -> Result<JsValue, JsError> {
-        let result = parser::parse(&self.state, input);
-        match result {
-            Ok((new_state, ok)) => {
-                self.previous_states.push(self.state.clone());
-                self.state = new_state;
-                let message = match ok {
-                    ParsingResult::Look(msg) => JsMessage::Look(msg),
-                    ParsingResult::Help(msg) => JsMessage::Help(msg),
-                    ParsingResult::NewItem(msg) => JsMessage::NewItem(msg),
-                    ParsingResult::DropItem(msg) => JsMessage::DropItem(msg),
-                    ParsingResult::Inventory(msg) => JsMessage::Inventory(msg),
-                    ParsingResult::SubjectNoEvent(msg) => JsMessage::SubjectNoEvent(msg),
-                    ParsingResult::EventSuccess(event_msg) => JsMessage::EventSuccess(event_msg),
-                    ParsingResult::Quit => JsMessage::NoOp,
-                };
-                Ok(serde_wasm_bindgen::to_value(&message)?)
-            }
-            Err(err) => Err(JsError::new(&err.to_string())),
-        }
+        self.execute_for_js(Command::Input {
+            text: input.to_string(),
+        })
     }
 
     /// Rewinds the game state to the previous state.
@@ -387,16 +707,7 @@ impl NightRunner {
     /// The state is saved in a stack, so you can rewind
     /// multiple times.
     pub fn rewind_state(&mut self) -> Result<JsValue, JsError> {
-        match self.previous_states.pop() {
-            Some(state) => {
-                self.future_states.push(self.state.clone());
-                self.state = state;
-                Ok(serde_wasm_bindgen::to_value(&JsMessage::Look(
-                    "Rewound to previous state".to_string(),
-                ))?)
-            }
-            None => Err(JsError::new("No previous state to rewind to")),
-        }
+        self.execute_for_js(Command::Rewind)
     }
 
     /// Fast forwards the game state to the next state.
@@ -404,16 +715,34 @@ impl NightRunner {
     /// The state is saved in a stack, so you can fast
     /// forward multiple times.
     pub fn fast_forward_state(&mut self) -> Result<JsValue, JsError> {
-        match self.future_states.pop() {
-            Some(state) => {
-                self.previous_states.push(self.state.clone());
-                self.state = state;
-                Ok(serde_wasm_bindgen::to_value(&JsMessage::Look(
-                    "Fast forwarded to next state".to_string(),
-                ))?)
-            }
-            None => Err(JsError::new("No future state to fast forward to")),
-        }
+        self.execute_for_js(Command::FastForward)
+    }
+
+    /// Changes how many commands can be undone, forgetting
+    /// the oldest states if there are more than that.
+    pub fn set_history_limit(&mut self, limit: usize) {
+        self.history.set_limit(limit);
+    }
+    /// Starts the game over, rebuilding the state from the
+    /// game's config with the same seed.
+    ///
+    /// When `keep_history` is true the state before the reset
+    /// can still be rewound to, otherwise the rewind history
+    /// is cleared.
+    pub fn reset(&mut self, keep_history: bool) -> Result<JsValue, JsError> {
+        self.execute_for_js(Command::Reset { keep_history })
+    }
+
+    /// Returns the record of everything that happened in the
+    /// game so far as JSON, which can be passed to
+    /// `NightRunner.replay`.
+    pub fn transcript_json(&self) -> String {
+        self.transcript.to_json()
+    }
+    /// Returns the record of everything that happened in the
+    /// game so far as plain text.
+    pub fn transcript_text(&self) -> String {
+        self.transcript.to_text()
     }
 
     /// Returns the string with the game intro text. This can
@@ -60,9 +60,21 @@ pub enum ActionType {
 ///         name: "subject1".to_string(),
 ///         description: "some verb text".to_string(),
 ///         default_text: "default text".to_string(),
+///         behavior: None,
+///         dialogue: None,
+///         topics: vec![],
+///         unknown_topic_text: None,
+///         accepts: vec![],
+///         refusal_text: None,
+///         disposition: 0,
+///         flags: vec![],
+///         disposition_texts: vec![],
+///         item_ids: vec![],
 ///     }),
 ///     item: None,
 ///     movement: None,
+///     topic: None,
+///     second_item: None,
 ///     command_tokens: vec!["look".to_string(), "subject1".to_string()],
 ///     input: "look subject1".to_string(),
 /// };
@@ -88,6 +100,16 @@ pub struct Action {
     /// direction and all other fields will
     /// be set to None.
     pub movement: Option<Directions>,
+    /// If the input contains "about", this field will be
+    /// set with the words after it, as in
+    /// "ask guard about the key".
+    #[serde(default)]
+    pub topic: Option<String>,
+    /// If the input names a second item, as in
+    /// "put coin in box" or "unlock door with key",
+    /// this field will be set with it.
+    #[serde(default)]
+    pub second_item: Option<Item>,
     /// The tokens from the user input.
     /// This is created by spliting the string
     /// on spaces.
@@ -133,19 +155,37 @@ impl Action {
     /// or determiners arrays.
     /// If after filtering the input string nothing is left,
     /// it returns an invalid action with all fields set to None.
+    ///
+    /// Anything after the word "about" is kept as the topic
+    /// of the action instead of being parsed as an item or
+    /// subject.
     pub fn parse(state: &State, input: &str) -> Action {
+        let (command, topic) = split_topic(state, input);
+        let mut action = Action::parse_command(state, &command);
+        if topic.is_some() {
+            action.topic = topic;
+            action.input = input.to_string();
+        }
+        action
+    }
+
+    fn parse_command(state: &State, input: &str) -> Action {
         let prepositions = state.config.allowed_prepositions.clone().prepositions;
         let determiners = state.config.allowed_determiners.clone().determiners;
 
-        let command_tokens: Vec<String> = input
-            .split(' ')
-            .collect::<Vec<&str>>()
-            .iter()
-            .filter(|w| {
-                let word: String = w.to_string().to_lowercase();
-                !prepositions.contains(&word) && !determiners.contains(&word)
-            })
-            .map(|word| word.to_string())
+        let (verb_name, rest) = split_multi_word_verb(state, input);
+        let command_tokens: Vec<String> = verb_name
+            .into_iter()
+            .chain(
+                rest.split(' ')
+                    .collect::<Vec<&str>>()
+                    .iter()
+                    .filter(|w| {
+                        let word: String = w.to_string().to_lowercase();
+                        !prepositions.contains(&word) && !determiners.contains(&word)
+                    })
+                    .map(|word| word.to_string()),
+            )
             .collect::<Vec<String>>();
         if command_tokens.is_empty() {
             Action {
@@ -153,6 +193,8 @@ impl Action {
                 movement: None,
                 subject: None,
                 verb: None,
+                topic: None,
+                second_item: None,
                 command_tokens: vec!["".to_string()],
                 input: input.to_string(),
             }
@@ -174,6 +216,14 @@ impl std::fmt::Display for Action {
                         self.item.clone().unwrap(),
                         self.subject.clone().unwrap().name
                     )
+                } else if self.verb.is_some() && self.subject.is_some() && self.topic.is_some() {
+                    write!(
+                        f,
+                        "{} {} about {}",
+                        self.verb.clone().unwrap(),
+                        self.subject.clone().unwrap().name,
+                        self.topic.clone().unwrap()
+                    )
                 } else if self.verb.is_some() && self.subject.is_some() {
                     write!(
                         f,
@@ -216,16 +266,82 @@ fn parse_action(state: &State, command_tokens: Vec<String>, input: &str) -> Acti
     let movement = extract_movement(state, &command_tokens);
     let subject = extract_subject(state, &command_tokens);
     let item = extract_item(state, &command_tokens, input);
+    let second_item = extract_second_item(state, &item, input);
     Action {
         verb,
         movement,
         item,
         subject,
+        topic: None,
+        second_item,
         command_tokens,
         input: input.to_string(),
     }
 }
 
+/// Verb names can have more than one word, such as "turn on".
+/// Since words like "on" would be dropped as prepositions, the
+/// start of the input is matched against those names first,
+/// returning the verb name and the rest of the input.
+fn split_multi_word_verb(state: &State, input: &str) -> (Option<String>, String) {
+    let lowercase_input = input.to_lowercase();
+    let verb_name = state
+        .config
+        .allowed_verbs
+        .iter()
+        .flat_map(|verb| verb.names.iter())
+        .filter(|name| name.contains(' '))
+        .filter(|name| {
+            lowercase_input == **name || lowercase_input.starts_with(&format!("{} ", name))
+        })
+        .max_by_key(|name| name.len());
+    match verb_name {
+        Some(name) => (
+            Some(name.clone()),
+            input[name.len()..].trim_start().to_string(),
+        ),
+        None => (None, input.to_string()),
+    }
+}
+
+/// Finds an item named in the input after the first one.
+fn extract_second_item(state: &State, item: &Option<Item>, input: &str) -> Option<Item> {
+    let item = item.as_ref()?;
+    state
+        .config
+        .items
+        .iter()
+        .filter(|other| other.id != item.id)
+        .filter_map(|other| {
+            input
+                .find(other.name.as_str())
+                .map(|position| (position, other))
+        })
+        .min_by_key(|(position, _)| *position)
+        .map(|(_, other)| other.clone())
+}
+
+/// Splits the input on the first "about", returning the
+/// command before it and the topic after it, without
+/// determiners.
+fn split_topic(state: &State, input: &str) -> (String, Option<String>) {
+    let words = input.split(' ').collect::<Vec<&str>>();
+    match words.iter().position(|word| word.to_lowercase() == "about") {
+        Some(index) => {
+            let determiners = &state.config.allowed_determiners.determiners;
+            let topic = words[index + 1..]
+                .iter()
+                .map(|word| word.to_lowercase())
+                .filter(|word| !word.is_empty() && !determiners.contains(word))
+                .collect::<Vec<String>>()
+                .join(" ");
+            let topic = if topic.is_empty() { None } else { Some(topic) };
+            (words[..index].join(" "), topic)
+        }
+        None => (input.to_string(), None),
+    }
+}
+
 fn extract_verb(state: &State, command_tokens: &[String]) -> Option<Verb> {
     let verbs = state.config.allowed_verbs.clone();
     verbs
@@ -88,3 +88,19 @@ fn it_parses_action() {
     assert_eq!(action.action_type(), ActionType::VerbItem);
     assert_eq!(format!("{}", action), "take item1");
 }
+#[test]
+fn it_parses_topics() {
+    let config = Config::from_path("fixtures/");
+    let state = State::init(config);
+    let action = Action::parse(&state, "talk to subject1 about the old item2");
+    assert_eq!(action.subject.clone().unwrap().name, "subject1");
+    assert_eq!(action.item, None);
+    assert_eq!(action.topic, Some("old item2".to_string()));
+    assert_eq!(action.action_type(), ActionType::VerbSubject);
+    assert_eq!(format!("{}", action), "talk subject1 about old item2");
+    assert_eq!(action.input, "talk to subject1 about the old item2");
+
+    let action = Action::parse(&state, "talk to subject1 about");
+    assert_eq!(action.topic, None);
+    assert_eq!(action.subject.unwrap().name, "subject1");
+}
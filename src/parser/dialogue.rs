@@ -0,0 +1,154 @@
+//! Conversations with subjects. Talking to a subject with a
+//! dialogue starts a conversation, and while it lasts the
+//! player answers by typing the number of a choice.
+
+use serde::{Deserialize, Serialize};
+
+use super::errors::{InvalidChoice, InvalidDialogue, InvalidSubject};
+use crate::config::{ActiveDialogue, Dialogue, DialogueChoice, State, Subject};
+use crate::NRResult;
+use crate::ParsingResult;
+
+/// A line of a conversation and the answers the player can
+/// pick from, returned in `ParsingResult::Dialogue`.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub struct DialogueMessage {
+    /// The name of the subject the player is talking to.
+    pub subject: String,
+    /// What the subject says, followed by any messages
+    /// produced by the effects of the conversation.
+    pub text: String,
+    /// The answers the player can pick from. When this
+    /// is empty the conversation is over.
+    pub choices: Vec<DialogueOption>,
+}
+
+/// An answer the player can pick by typing its number.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub struct DialogueOption {
+    /// The number the player types to pick this answer.
+    pub number: usize,
+    /// What the player says.
+    pub text: String,
+}
+
+/// Starts the conversation with the subject, if it has one.
+pub(crate) fn start_dialogue(
+    state: &State,
+    subject: &Subject,
+) -> Option<NRResult<(State, ParsingResult)>> {
+    let dialogue = subject_dialogue(state, subject.id)?;
+    Some(enter_node(state, subject, dialogue, dialogue.start, vec![]))
+}
+
+/// Picks the choice with the given number in the current
+/// conversation, out of the choices that were offered.
+pub(crate) fn choose(state: &State, number: usize) -> NRResult<(State, ParsingResult)> {
+    let active = state.dialogue.as_ref().ok_or(InvalidChoice)?;
+    let subject = state
+        .config
+        .subjects
+        .iter()
+        .find(|subject| subject.id == active.subject_id)
+        .ok_or(InvalidSubject)?;
+    let dialogue = subject_dialogue(state, subject.id).ok_or(InvalidDialogue)?;
+    let node = dialogue.node(active.node_id).ok_or(InvalidDialogue)?;
+    let index = active
+        .choices
+        .get(number.checked_sub(1).ok_or(InvalidChoice)?)
+        .ok_or(InvalidChoice)?;
+    let choice = node.choices.get(*index).ok_or(InvalidDialogue)?;
+    let mut new_state = state.clone();
+    let messages = new_state.apply_effects(&choice.effects)?;
+    match choice.next {
+        Some(node_id) => enter_node(&new_state, subject, dialogue, node_id, messages),
+        None => {
+            new_state.dialogue = None;
+            Ok((
+                new_state,
+                ParsingResult::Dialogue(DialogueMessage {
+                    subject: subject.name.clone(),
+                    text: messages.join("\n"),
+                    choices: vec![],
+                }),
+            ))
+        }
+    }
+}
+
+/// Whether the subject of the current conversation is still
+/// in the room with the player.
+pub(crate) fn subject_is_here(state: &State) -> bool {
+    state.dialogue.as_ref().is_some_and(|active| {
+        state
+            .subjects_in_room(state.current_room)
+            .iter()
+            .any(|subject| subject.id == active.subject_id)
+    })
+}
+
+fn subject_dialogue(state: &State, subject_id: u16) -> Option<&Dialogue> {
+    state
+        .config
+        .subjects
+        .iter()
+        .find(|subject| subject.id == subject_id)?
+        .dialogue
+        .as_ref()
+}
+
+/// Returns the positions of the choices whose conditions are met.
+fn available_choices(state: &State, choices: &[DialogueChoice]) -> Vec<usize> {
+    choices
+        .iter()
+        .enumerate()
+        .filter(|(_, choice)| state.conditions_met(&choice.conditions))
+        .map(|(index, _)| index)
+        .collect()
+}
+
+fn enter_node(
+    state: &State,
+    subject: &Subject,
+    dialogue: &Dialogue,
+    node_id: u16,
+    mut messages: Vec<String>,
+) -> NRResult<(State, ParsingResult)> {
+    let node = dialogue.node(node_id).ok_or(InvalidDialogue)?;
+    let mut new_state = state.clone();
+    let mut lines = vec![node.text.clone()];
+    messages.extend(new_state.apply_effects(&node.effects)?);
+    lines.extend(messages);
+    let offered = available_choices(&new_state, &node.choices);
+    let choices = offered
+        .iter()
+        .enumerate()
+        .map(|(position, index)| DialogueOption {
+            number: position + 1,
+            text: node.choices[*index].text.clone(),
+        })
+        .collect::<Vec<DialogueOption>>();
+    new_state.dialogue = if offered.is_empty() {
+        None
+    } else {
+        Some(ActiveDialogue {
+            subject_id: subject.id,
+            node_id,
+            choices: offered,
+        })
+    };
+    Ok((
+        new_state,
+        ParsingResult::Dialogue(DialogueMessage {
+            subject: subject.name.clone(),
+            text: lines.join("\n"),
+            choices,
+        }),
+    ))
+}
+
+#[cfg(test)]
+#[path = "dialogue_tests.rs"]
+mod dialogue_tests;
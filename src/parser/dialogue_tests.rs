@@ -0,0 +1,198 @@
+use super::*;
+use crate::config::{Condition, Config, DialogueNode, Effect, SubjectLocation};
+use crate::parser::parse;
+#[cfg(test)]
+use pretty_assertions::assert_eq;
+
+fn state_with_dialogue() -> State {
+    let mut config = Config::from_path("fixtures/");
+    config.subjects[1].dialogue = Some(Dialogue {
+        start: 1,
+        nodes: vec![
+            DialogueNode {
+                id: 1,
+                text: "Hello there.".to_string(),
+                effects: vec![],
+                choices: vec![
+                    DialogueChoice {
+                        text: "Who are you?".to_string(),
+                        conditions: vec![],
+                        effects: vec![],
+                        next: Some(2),
+                    },
+                    DialogueChoice {
+                        text: "I have your item.".to_string(),
+                        conditions: vec![Condition::HasItem { item_id: 3 }],
+                        effects: vec![Effect::RemoveItem { item_id: 3 }],
+                        next: Some(3),
+                    },
+                    DialogueChoice {
+                        text: "Goodbye.".to_string(),
+                        conditions: vec![],
+                        effects: vec![Effect::Message {
+                            text: "subject2 waves.".to_string(),
+                        }],
+                        next: None,
+                    },
+                ],
+            },
+            DialogueNode {
+                id: 2,
+                text: "Nobody important.".to_string(),
+                effects: vec![],
+                choices: vec![DialogueChoice {
+                    text: "Back to the start.".to_string(),
+                    conditions: vec![],
+                    effects: vec![],
+                    next: Some(1),
+                }],
+            },
+            DialogueNode {
+                id: 3,
+                text: "Thank you!".to_string(),
+                effects: vec![Effect::SetVariable {
+                    name: "gratitude".to_string(),
+                    value: 1,
+                }],
+                choices: vec![],
+            },
+        ],
+    });
+    let mut state = State::init(config);
+    state.current_room = 2;
+    // talking to subject2 in room 2 triggers event 5 first
+    state.complete_event(5);
+    state
+}
+
+fn option(number: usize, text: &str) -> DialogueOption {
+    DialogueOption {
+        number,
+        text: text.to_string(),
+    }
+}
+
+#[test]
+fn it_starts_dialogue_when_talking() {
+    let state = state_with_dialogue();
+    let (state, result) = parse(&state, "talk to subject2").unwrap();
+    assert_eq!(
+        result,
+        ParsingResult::Dialogue(DialogueMessage {
+            subject: "subject2".to_string(),
+            text: "Hello there.".to_string(),
+            choices: vec![option(1, "Who are you?"), option(2, "Goodbye.")],
+        })
+    );
+    assert_eq!(
+        state.dialogue,
+        Some(ActiveDialogue {
+            subject_id: 2,
+            node_id: 1,
+            choices: vec![0, 2],
+        })
+    );
+}
+
+#[test]
+fn it_picks_choices_by_number() {
+    let state = state_with_dialogue();
+    let (state, _) = parse(&state, "talk to subject2").unwrap();
+    let (state, result) = parse(&state, "1").unwrap();
+    assert_eq!(
+        result,
+        ParsingResult::Dialogue(DialogueMessage {
+            subject: "subject2".to_string(),
+            text: "Nobody important.".to_string(),
+            choices: vec![option(1, "Back to the start.")],
+        })
+    );
+    let (state, _) = parse(&state, "1").unwrap();
+    assert!(parse(&state, "3").is_err());
+    assert!(parse(&state, "0").is_err());
+    let (state, result) = parse(&state, "2").unwrap();
+    assert_eq!(
+        result,
+        ParsingResult::Dialogue(DialogueMessage {
+            subject: "subject2".to_string(),
+            text: "subject2 waves.".to_string(),
+            choices: vec![],
+        })
+    );
+    assert_eq!(state.dialogue, None);
+}
+
+#[test]
+fn it_applies_choice_conditions_and_effects() {
+    let mut state = state_with_dialogue();
+    let item3 = state.config.items[2].clone();
+    state.player.inventory.add_item(item3);
+    let (state, result) = parse(&state, "talk to subject2").unwrap();
+    match result {
+        ParsingResult::Dialogue(dialogue_message) => {
+            assert_eq!(dialogue_message.choices[1], option(2, "I have your item."))
+        }
+        _ => panic!("talking should start the dialogue"),
+    }
+    let (state, result) = parse(&state, "2").unwrap();
+    assert_eq!(
+        result,
+        ParsingResult::Dialogue(DialogueMessage {
+            subject: "subject2".to_string(),
+            text: "Thank you!".to_string(),
+            choices: vec![],
+        })
+    );
+    assert!(state.player.inventory.items.is_empty());
+    assert_eq!(state.variable("gratitude"), 1);
+    assert_eq!(state.dialogue, None);
+}
+
+#[test]
+fn it_leaves_dialogue_on_other_commands() {
+    let state = state_with_dialogue();
+    let (state, _) = parse(&state, "talk to subject2").unwrap();
+    let (state, result) = parse(&state, "look").unwrap();
+    assert_eq!(state.dialogue, None);
+    assert!(matches!(result, ParsingResult::Look(_)));
+}
+
+#[test]
+fn it_prefers_events_over_dialogue() {
+    let mut state = state_with_dialogue();
+    state.rooms[1].events[0].completed = false;
+    let (_, result) = parse(&state, "talk to subject2").unwrap();
+    assert!(matches!(result, ParsingResult::EventSuccess(_)));
+}
+
+#[test]
+fn it_picks_from_the_choices_that_were_offered() {
+    let state = state_with_dialogue();
+    let (mut state, _) = parse(&state, "talk to subject2").unwrap();
+    // the item choice wasn't offered, so picking up the item
+    // after the choices were shown doesn't shift the numbers.
+    let item3 = state.config.items[2].clone();
+    state.player.inventory.add_item(item3);
+    let (state, result) = parse(&state, "2").unwrap();
+    assert_eq!(
+        result,
+        ParsingResult::Dialogue(DialogueMessage {
+            subject: "subject2".to_string(),
+            text: "subject2 waves.".to_string(),
+            choices: vec![],
+        })
+    );
+    assert_eq!(state.player.inventory.items.len(), 1);
+}
+
+#[test]
+fn it_ends_dialogue_when_the_subject_leaves() {
+    let state = state_with_dialogue();
+    let (mut state, _) = parse(&state, "talk to subject2").unwrap();
+    state
+        .set_subject_location(2, SubjectLocation::Nowhere)
+        .unwrap();
+    assert!(parse(&state, "1").is_err());
+    let (state, _) = parse(&state, "look").unwrap();
+    assert_eq!(state.dialogue, None);
+}
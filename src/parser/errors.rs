@@ -30,8 +30,8 @@ impl std::fmt::Display for RequiredEventNotCompleted {
 }
 
 impl error::Error for RequiredEventNotCompleted {}
-impl From<&std::boxed::Box<(dyn std::error::Error + 'static)>> for RequiredEventNotCompleted {
-    fn from(_: &std::boxed::Box<(dyn std::error::Error + 'static)>) -> Self {
+impl From<&std::boxed::Box<dyn std::error::Error + 'static>> for RequiredEventNotCompleted {
+    fn from(_: &std::boxed::Box<dyn std::error::Error + 'static>) -> Self {
         RequiredEventNotCompleted
     }
 }
@@ -92,8 +92,8 @@ impl std::fmt::Display for InvalidEvent {
 }
 
 impl error::Error for InvalidEvent {}
-impl From<&std::boxed::Box<(dyn std::error::Error + 'static)>> for InvalidEvent {
-    fn from(_: &std::boxed::Box<(dyn std::error::Error + 'static)>) -> Self {
+impl From<&std::boxed::Box<dyn std::error::Error + 'static>> for InvalidEvent {
+    fn from(_: &std::boxed::Box<dyn std::error::Error + 'static>) -> Self {
         InvalidEvent
     }
 }
@@ -108,8 +108,8 @@ impl std::fmt::Display for InvalidAction {
 }
 
 impl error::Error for InvalidAction {}
-impl From<&std::boxed::Box<(dyn std::error::Error + 'static)>> for InvalidAction {
-    fn from(_: &std::boxed::Box<(dyn std::error::Error + 'static)>) -> Self {
+impl From<&std::boxed::Box<dyn std::error::Error + 'static>> for InvalidAction {
+    fn from(_: &std::boxed::Box<dyn std::error::Error + 'static>) -> Self {
         InvalidAction
     }
 }
@@ -127,8 +127,8 @@ impl std::fmt::Display for InvalidItem {
 }
 
 impl error::Error for InvalidItem {}
-impl From<&std::boxed::Box<(dyn std::error::Error + 'static)>> for InvalidItem {
-    fn from(_: &std::boxed::Box<(dyn std::error::Error + 'static)>) -> Self {
+impl From<&std::boxed::Box<dyn std::error::Error + 'static>> for InvalidItem {
+    fn from(_: &std::boxed::Box<dyn std::error::Error + 'static>) -> Self {
         InvalidItem
     }
 }
@@ -146,8 +146,8 @@ impl std::fmt::Display for InvalidSubject {
 }
 
 impl error::Error for InvalidSubject {}
-impl From<&std::boxed::Box<(dyn std::error::Error + 'static)>> for InvalidSubject {
-    fn from(_: &std::boxed::Box<(dyn std::error::Error + 'static)>) -> Self {
+impl From<&std::boxed::Box<dyn std::error::Error + 'static>> for InvalidSubject {
+    fn from(_: &std::boxed::Box<dyn std::error::Error + 'static>) -> Self {
         InvalidSubject
     }
 }
@@ -165,8 +165,8 @@ impl std::fmt::Display for InvalidVerb {
 }
 
 impl error::Error for InvalidVerb {}
-impl From<&std::boxed::Box<(dyn std::error::Error + 'static)>> for InvalidVerb {
-    fn from(_: &std::boxed::Box<(dyn std::error::Error + 'static)>) -> Self {
+impl From<&std::boxed::Box<dyn std::error::Error + 'static>> for InvalidVerb {
+    fn from(_: &std::boxed::Box<dyn std::error::Error + 'static>) -> Self {
         InvalidVerb
     }
 }
@@ -184,8 +184,8 @@ impl std::fmt::Display for InvalidMovement {
 }
 
 impl error::Error for InvalidMovement {}
-impl From<&std::boxed::Box<(dyn std::error::Error + 'static)>> for InvalidMovement {
-    fn from(_: &std::boxed::Box<(dyn std::error::Error + 'static)>) -> Self {
+impl From<&std::boxed::Box<dyn std::error::Error + 'static>> for InvalidMovement {
+    fn from(_: &std::boxed::Box<dyn std::error::Error + 'static>) -> Self {
         InvalidMovement
     }
 }
@@ -203,8 +203,8 @@ impl std::fmt::Display for InvalidDirection {
 }
 
 impl error::Error for InvalidDirection {}
-impl From<&std::boxed::Box<(dyn std::error::Error + 'static)>> for InvalidDirection {
-    fn from(_: &std::boxed::Box<(dyn std::error::Error + 'static)>) -> Self {
+impl From<&std::boxed::Box<dyn std::error::Error + 'static>> for InvalidDirection {
+    fn from(_: &std::boxed::Box<dyn std::error::Error + 'static>) -> Self {
         InvalidDirection
     }
 }
@@ -222,8 +222,8 @@ impl std::fmt::Display for InvalidRoom {
 }
 
 impl error::Error for InvalidRoom {}
-impl From<&std::boxed::Box<(dyn std::error::Error + 'static)>> for InvalidRoom {
-    fn from(_: &std::boxed::Box<(dyn std::error::Error + 'static)>) -> Self {
+impl From<&std::boxed::Box<dyn std::error::Error + 'static>> for InvalidRoom {
+    fn from(_: &std::boxed::Box<dyn std::error::Error + 'static>) -> Self {
         InvalidRoom
     }
 }
@@ -239,8 +239,8 @@ impl std::fmt::Display for InvalidVerbItemSubject {
 }
 
 impl error::Error for InvalidVerbItemSubject {}
-impl From<&std::boxed::Box<(dyn std::error::Error + 'static)>> for InvalidVerbItemSubject {
-    fn from(_: &std::boxed::Box<(dyn std::error::Error + 'static)>) -> Self {
+impl From<&std::boxed::Box<dyn std::error::Error + 'static>> for InvalidVerbItemSubject {
+    fn from(_: &std::boxed::Box<dyn std::error::Error + 'static>) -> Self {
         InvalidVerbItemSubject
     }
 }
@@ -256,8 +256,8 @@ impl std::fmt::Display for InvalidVerbSubject {
 }
 
 impl error::Error for InvalidVerbSubject {}
-impl From<&std::boxed::Box<(dyn std::error::Error + 'static)>> for InvalidVerbSubject {
-    fn from(_: &std::boxed::Box<(dyn std::error::Error + 'static)>) -> Self {
+impl From<&std::boxed::Box<dyn std::error::Error + 'static>> for InvalidVerbSubject {
+    fn from(_: &std::boxed::Box<dyn std::error::Error + 'static>) -> Self {
         InvalidVerbSubject
     }
 }
@@ -273,8 +273,8 @@ impl std::fmt::Display for InvalidVerbItem {
 }
 
 impl error::Error for InvalidVerbItem {}
-impl From<&std::boxed::Box<(dyn std::error::Error + 'static)>> for InvalidVerbItem {
-    fn from(_: &std::boxed::Box<(dyn std::error::Error + 'static)>) -> Self {
+impl From<&std::boxed::Box<dyn std::error::Error + 'static>> for InvalidVerbItem {
+    fn from(_: &std::boxed::Box<dyn std::error::Error + 'static>) -> Self {
         InvalidVerbItem
     }
 }
@@ -290,8 +290,8 @@ impl std::fmt::Display for ParsingRoomText {
 }
 
 impl error::Error for ParsingRoomText {}
-impl From<&std::boxed::Box<(dyn std::error::Error + 'static)>> for ParsingRoomText {
-    fn from(_: &std::boxed::Box<(dyn std::error::Error + 'static)>) -> Self {
+impl From<&std::boxed::Box<dyn std::error::Error + 'static>> for ParsingRoomText {
+    fn from(_: &std::boxed::Box<dyn std::error::Error + 'static>) -> Self {
         ParsingRoomText
     }
 }
@@ -307,8 +307,8 @@ impl std::fmt::Display for NoRoom {
 }
 
 impl error::Error for NoRoom {}
-impl From<&std::boxed::Box<(dyn std::error::Error + 'static)>> for NoRoom {
-    fn from(_: &std::boxed::Box<(dyn std::error::Error + 'static)>) -> Self {
+impl From<&std::boxed::Box<dyn std::error::Error + 'static>> for NoRoom {
+    fn from(_: &std::boxed::Box<dyn std::error::Error + 'static>) -> Self {
         NoRoom
     }
 }
@@ -324,12 +324,29 @@ impl std::fmt::Display for CantPick {
 }
 
 impl error::Error for CantPick {}
-impl From<&std::boxed::Box<(dyn std::error::Error + 'static)>> for CantPick {
-    fn from(_: &std::boxed::Box<(dyn std::error::Error + 'static)>) -> Self {
+impl From<&std::boxed::Box<dyn std::error::Error + 'static>> for CantPick {
+    fn from(_: &std::boxed::Box<dyn std::error::Error + 'static>) -> Self {
         CantPick
     }
 }
 
+#[derive(Debug, Clone)]
+/// Error returned when trying to put a container inside
+/// itself, or inside an item it holds.
+pub struct ContainerCycle;
+impl std::fmt::Display for ContainerCycle {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "You can't put something inside itself.")
+    }
+}
+
+impl error::Error for ContainerCycle {}
+impl From<&std::boxed::Box<dyn std::error::Error + 'static>> for ContainerCycle {
+    fn from(_: &std::boxed::Box<dyn std::error::Error + 'static>) -> Self {
+        ContainerCycle
+    }
+}
+
 #[derive(Debug, Clone)]
 /// Error returned when trying to remove an item from the player
 /// that is not in their inventory.
@@ -341,8 +358,8 @@ impl std::fmt::Display for NoItem {
 }
 
 impl error::Error for NoItem {}
-impl From<&std::boxed::Box<(dyn std::error::Error + 'static)>> for NoItem {
-    fn from(_: &std::boxed::Box<(dyn std::error::Error + 'static)>) -> Self {
+impl From<&std::boxed::Box<dyn std::error::Error + 'static>> for NoItem {
+    fn from(_: &std::boxed::Box<dyn std::error::Error + 'static>) -> Self {
         NoItem
     }
 }
@@ -358,8 +375,8 @@ impl std::fmt::Display for ItemNotFound {
 }
 
 impl error::Error for ItemNotFound {}
-impl From<&std::boxed::Box<(dyn std::error::Error + 'static)>> for ItemNotFound {
-    fn from(_: &std::boxed::Box<(dyn std::error::Error + 'static)>) -> Self {
+impl From<&std::boxed::Box<dyn std::error::Error + 'static>> for ItemNotFound {
+    fn from(_: &std::boxed::Box<dyn std::error::Error + 'static>) -> Self {
         ItemNotFound
     }
 }
@@ -376,8 +393,8 @@ impl std::fmt::Display for EmptyInput {
 }
 
 impl error::Error for EmptyInput {}
-impl From<&std::boxed::Box<(dyn std::error::Error + 'static)>> for EmptyInput {
-    fn from(_: &std::boxed::Box<(dyn std::error::Error + 'static)>) -> Self {
+impl From<&std::boxed::Box<dyn std::error::Error + 'static>> for EmptyInput {
+    fn from(_: &std::boxed::Box<dyn std::error::Error + 'static>) -> Self {
         EmptyInput
     }
 }
@@ -395,8 +412,271 @@ impl std::fmt::Display for InvalidNarrative {
 }
 
 impl error::Error for InvalidNarrative {}
-impl From<&std::boxed::Box<(dyn std::error::Error + 'static)>> for InvalidNarrative {
-    fn from(_: &std::boxed::Box<(dyn std::error::Error + 'static)>) -> Self {
+impl From<&std::boxed::Box<dyn std::error::Error + 'static>> for InvalidNarrative {
+    fn from(_: &std::boxed::Box<dyn std::error::Error + 'static>) -> Self {
         InvalidNarrative
     }
 }
+
+#[derive(Debug, Clone)]
+/// Error returned when the player picks a dialogue
+/// choice that isn't one of the available options.
+pub struct InvalidChoice;
+impl std::fmt::Display for InvalidChoice {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "That isn't one of the options.")
+    }
+}
+
+impl error::Error for InvalidChoice {}
+impl From<&std::boxed::Box<dyn std::error::Error + 'static>> for InvalidChoice {
+    fn from(_: &std::boxed::Box<dyn std::error::Error + 'static>) -> Self {
+        InvalidChoice
+    }
+}
+
+#[derive(Debug, Clone)]
+/// Error returned when a dialogue points to a node
+/// that doesn't exist. This will likely be an issue
+/// in the configuration passed to nightrunner_lib
+/// when initializing the parser.
+pub struct InvalidDialogue;
+impl std::fmt::Display for InvalidDialogue {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "The dialogue is invalid.")
+    }
+}
+
+impl error::Error for InvalidDialogue {}
+impl From<&std::boxed::Box<dyn std::error::Error + 'static>> for InvalidDialogue {
+    fn from(_: &std::boxed::Box<dyn std::error::Error + 'static>) -> Self {
+        InvalidDialogue
+    }
+}
+
+#[derive(Debug, Clone)]
+/// Error returned when the player issues a command
+/// after the game is over.
+pub struct GameIsOver;
+impl std::fmt::Display for GameIsOver {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "The game is over.")
+    }
+}
+
+impl error::Error for GameIsOver {}
+impl From<&std::boxed::Box<dyn std::error::Error + 'static>> for GameIsOver {
+    fn from(_: &std::boxed::Box<dyn std::error::Error + 'static>) -> Self {
+        GameIsOver
+    }
+}
+
+#[derive(Debug, Clone)]
+/// Error returned when the player tries to undo a
+/// command and there is no earlier state to go back to.
+pub struct NothingToUndo;
+impl std::fmt::Display for NothingToUndo {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "There is nothing to undo.")
+    }
+}
+
+impl error::Error for NothingToUndo {}
+impl From<&std::boxed::Box<dyn std::error::Error + 'static>> for NothingToUndo {
+    fn from(_: &std::boxed::Box<dyn std::error::Error + 'static>) -> Self {
+        NothingToUndo
+    }
+}
+
+#[derive(Debug, Clone)]
+/// Error returned when the player tries to redo a
+/// command and no command was undone.
+pub struct NothingToRedo;
+impl std::fmt::Display for NothingToRedo {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "There is nothing to redo.")
+    }
+}
+
+impl error::Error for NothingToRedo {}
+impl From<&std::boxed::Box<dyn std::error::Error + 'static>> for NothingToRedo {
+    fn from(_: &std::boxed::Box<dyn std::error::Error + 'static>) -> Self {
+        NothingToRedo
+    }
+}
+
+#[derive(Debug, Clone)]
+/// Error returned when a walkthrough is run without
+/// saying which game it plays.
+pub struct NoWalkthroughGame;
+impl std::fmt::Display for NoWalkthroughGame {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "The walkthrough doesn't say which game to play.")
+    }
+}
+
+impl error::Error for NoWalkthroughGame {}
+impl From<&std::boxed::Box<dyn std::error::Error + 'static>> for NoWalkthroughGame {
+    fn from(_: &std::boxed::Box<dyn std::error::Error + 'static>) -> Self {
+        NoWalkthroughGame
+    }
+}
+
+#[derive(Debug, Clone)]
+/// Error returned when a game file doesn't end in
+/// `.json`, `.yml`, `.yaml` or `.toml`.
+pub struct UnknownConfigFormat;
+impl std::fmt::Display for UnknownConfigFormat {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "The game file should be a .json, .yml, .yaml or .toml file."
+        )
+    }
+}
+
+impl error::Error for UnknownConfigFormat {}
+impl From<&std::boxed::Box<dyn std::error::Error + 'static>> for UnknownConfigFormat {
+    fn from(_: &std::boxed::Box<dyn std::error::Error + 'static>) -> Self {
+        UnknownConfigFormat
+    }
+}
+
+#[derive(Debug, Clone)]
+/// Error returned when a game file includes itself,
+/// directly or through other included files.
+pub struct IncludeCycle;
+impl std::fmt::Display for IncludeCycle {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "A game file includes itself.")
+    }
+}
+
+impl error::Error for IncludeCycle {}
+impl From<&std::boxed::Box<dyn std::error::Error + 'static>> for IncludeCycle {
+    fn from(_: &std::boxed::Box<dyn std::error::Error + 'static>) -> Self {
+        IncludeCycle
+    }
+}
+
+#[derive(Debug, Clone)]
+/// Error returned when the intro, settings or stats
+/// are set in more than one included game file.
+pub struct DuplicateSection;
+impl std::fmt::Display for DuplicateSection {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "The intro, settings and stats can only be set in one game file."
+        )
+    }
+}
+
+impl error::Error for DuplicateSection {}
+impl From<&std::boxed::Box<dyn std::error::Error + 'static>> for DuplicateSection {
+    fn from(_: &std::boxed::Box<dyn std::error::Error + 'static>) -> Self {
+        DuplicateSection
+    }
+}
+
+#[derive(Debug, Clone)]
+/// Error returned when one of the required game files,
+/// like `rooms.yml`, is missing.
+pub struct MissingConfigFile;
+impl std::fmt::Display for MissingConfigFile {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "One of the required game files is missing.")
+    }
+}
+
+impl error::Error for MissingConfigFile {}
+impl From<&std::boxed::Box<dyn std::error::Error + 'static>> for MissingConfigFile {
+    fn from(_: &std::boxed::Box<dyn std::error::Error + 'static>) -> Self {
+        MissingConfigFile
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// Error returned when game data references a symbolic id,
+/// like `required_item: brass_key`, that no entry has.
+pub struct UnknownSymbol {
+    /// The field holding the reference.
+    pub field: String,
+    /// What the field references, like `item`.
+    pub kind: String,
+    /// The symbolic id that wasn't found.
+    pub symbol: String,
+}
+impl std::fmt::Display for UnknownSymbol {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "The {} \"{}\" doesn't match the id of any {}.",
+            self.field, self.symbol, self.kind
+        )
+    }
+}
+
+impl error::Error for UnknownSymbol {}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// Error returned when the entries with symbolic ids can't
+/// all be given an id, because ids can't be larger than
+/// 65535.
+pub struct TooManyIds {
+    /// The kind of entry, like `item`.
+    pub kind: String,
+}
+impl std::fmt::Display for TooManyIds {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "There are too many {}s to give them all an id, ids can't be larger than {}.",
+            self.kind,
+            u16::MAX
+        )
+    }
+}
+
+impl error::Error for TooManyIds {}
+
+#[derive(Debug, Clone)]
+/// Error returned when the `format_version` of game data
+/// isn't a number, or is newer than this version of the
+/// library can read.
+pub struct UnsupportedFormatVersion;
+impl std::fmt::Display for UnsupportedFormatVersion {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "The game's format_version isn't supported by this version of the library."
+        )
+    }
+}
+
+impl error::Error for UnsupportedFormatVersion {}
+impl From<&std::boxed::Box<dyn std::error::Error + 'static>> for UnsupportedFormatVersion {
+    fn from(_: &std::boxed::Box<dyn std::error::Error + 'static>) -> Self {
+        UnsupportedFormatVersion
+    }
+}
+
+#[derive(Debug, Clone)]
+/// Error returned when included game files set different
+/// format versions.
+pub struct ConflictingFormatVersions;
+impl std::fmt::Display for ConflictingFormatVersions {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "The game files set different format versions, they need to use the same one."
+        )
+    }
+}
+
+impl error::Error for ConflictingFormatVersions {}
+impl From<&std::boxed::Box<dyn std::error::Error + 'static>> for ConflictingFormatVersions {
+    fn from(_: &std::boxed::Box<dyn std::error::Error + 'static>) -> Self {
+        ConflictingFormatVersions
+    }
+}
@@ -1,13 +1,16 @@
 use std::collections::HashMap;
+use std::sync::Arc;
 
 use crate::config::directions::Directions;
 use crate::config::rooms::Room;
 use crate::config::{Event, Item, State, Subject, VerbFunction};
 use crate::parser::action::{Action, ActionType};
+use crate::parser::dialogue::start_dialogue;
 use crate::parser::errors::*;
+use crate::parser::item_verbs::{describe_contents, handle_item_verb, take_from_container};
 use crate::util::{
-    display_help, move_to_direction, parse_room_text, player_get_item, player_receive_item,
-    player_remove_item, MoveSuccess,
+    current_room_text, display_help, move_to_direction, parse_room_text, player_get_item,
+    player_receive_item, player_remove_item, MoveSuccess,
 };
 use crate::NRResult;
 use crate::ParsingResult;
@@ -29,6 +32,9 @@ pub enum MessageParts {
     /// A string containing all of the current room's exits and the
     /// description of the room they lead to.
     Exits,
+    /// The text produced by daemons, the rules that run at the
+    /// end of every turn, such as a lamp running out of fuel.
+    DaemonText,
 }
 
 /// Represents the result of parsing an event.
@@ -84,10 +90,19 @@ fn handle_verb(state: &State, action: Action) -> NRResult<(State, ParsingResult)
                 Ok(parsing_result) => Ok((new_state, parsing_result)),
                 Err(error) => Err(error),
             },
+            VerbFunction::Restart => restart_game(&new_state),
+            VerbFunction::Undo => Err(NothingToUndo.into()),
+            VerbFunction::Redo => Err(NothingToRedo.into()),
+            VerbFunction::Score => match show_score(&new_state) {
+                Ok(parsing_result) => Ok((new_state, parsing_result)),
+                Err(error) => Err(error),
+            },
             _ => match &verb.verb_function {
                 VerbFunction::Take => handle_verb_item(state, action),
                 VerbFunction::Drop => handle_verb_item(state, action),
-                VerbFunction::Talk => handle_verb_subject(state, action),
+                VerbFunction::Talk | VerbFunction::Ask | VerbFunction::Tell => {
+                    handle_verb_subject(state, action)
+                }
                 VerbFunction::Normal => handle_event(state, action),
                 _ => Err(InvalidVerb.into()),
             },
@@ -136,7 +151,24 @@ fn handle_verb_item(state: &State, action: Action) -> NRResult<(State, ParsingRe
                     Ok(parsing_result) => Ok((state.clone(), parsing_result)),
                     Err(error) => Err(error),
                 },
-                VerbFunction::Normal => handle_event(state, action),
+                VerbFunction::Normal | VerbFunction::Give | VerbFunction::Show => {
+                    handle_event(state, action)
+                }
+                VerbFunction::Eat
+                | VerbFunction::Drink
+                | VerbFunction::Read
+                | VerbFunction::Wear
+                | VerbFunction::Remove
+                | VerbFunction::Open
+                | VerbFunction::Close
+                | VerbFunction::Lock
+                | VerbFunction::Unlock
+                | VerbFunction::TurnOn
+                | VerbFunction::TurnOff
+                | VerbFunction::Put => match handle_item_verb(state, &action) {
+                    Some(result) => result,
+                    None => handle_event(state, action),
+                },
                 _ => Err(InvalidVerb.into()),
             },
             None => Err(NoItem.into()),
@@ -192,11 +224,46 @@ fn handle_event(state: &State, action: Action) -> NRResult<(State, ParsingResult
     let (inventory_item, subject) = extract_item_subject(state, &action);
     let events = filter_events(current_room, &action, &inventory_item, &subject);
 
+    let no_pending_events = events.iter().all(|event| event.is_completed());
+    let is_conversing = action.verb.as_ref().is_some_and(|verb| {
+        matches!(
+            verb.verb_function,
+            VerbFunction::Talk | VerbFunction::Ask | VerbFunction::Tell
+        )
+    });
+    if let (true, true, Some(subject), Some(topic)) =
+        (is_conversing, no_pending_events, &subject, &action.topic)
+    {
+        return answer_topic(state, subject, topic);
+    }
+    let is_handing_item = action
+        .verb
+        .as_ref()
+        .is_some_and(|verb| matches!(verb.verb_function, VerbFunction::Give | VerbFunction::Show));
+    if let (true, true, Some(subject)) = (is_handing_item, no_pending_events, &subject) {
+        let item = inventory_item.as_ref().ok_or(NoItem)?;
+        return hand_item(state, &action, subject, item);
+    }
+    let is_talking = action
+        .verb
+        .as_ref()
+        .is_some_and(|verb| verb.verb_function == VerbFunction::Talk);
+    if is_talking && no_pending_events {
+        if let Some(result) = subject
+            .as_ref()
+            .and_then(|subject| start_dialogue(state, subject))
+        {
+            return result;
+        }
+    }
+
     if events.is_empty() {
         if let Some(subject) = subject {
+            // the subject in the game holds its current disposition
+            let subject = state.subject(subject.id).unwrap_or(&subject);
             return Ok((
                 state.clone(),
-                ParsingResult::SubjectNoEvent(subject.default_text.clone()),
+                ParsingResult::SubjectNoEvent(subject.current_default_text(&state.config.settings)),
             ));
         } else {
             return Err(InvalidEvent.into());
@@ -226,6 +293,87 @@ fn handle_event(state: &State, action: Action) -> NRResult<(State, ParsingResult
     Ok((new_state, event_message))
 }
 
+/// Gives or shows an item to a subject. Accepted items given
+/// to a subject leave the player's inventory, while shown items
+/// stay with the player. Items the subject doesn't accept are
+/// refused with the subject's refusal text.
+fn hand_item(
+    state: &State,
+    action: &Action,
+    subject: &Subject,
+    item: &Item,
+) -> NRResult<(State, ParsingResult)> {
+    let subject = state.subject(subject.id).ok_or(InvalidSubject)?;
+    let is_giving = action
+        .verb
+        .as_ref()
+        .is_some_and(|verb| verb.verb_function == VerbFunction::Give);
+    let accepted = subject
+        .accepts
+        .iter()
+        .find(|accepted| accepted.item_id == item.id && state.conditions_met(&accepted.conditions));
+    let refusal = || {
+        subject
+            .refusal_text
+            .clone()
+            .unwrap_or_else(|| format!("{} doesn't want the {}.", subject.name, item.name))
+    };
+    match accepted {
+        Some(accepted) if is_giving => {
+            let (mut new_state, item_message) = player_remove_item(state, item.clone())?;
+            new_state
+                .subject_mut(subject.id)
+                .ok_or(InvalidSubject)?
+                .item_ids
+                .push(item.id);
+            let mut lines = vec![accepted.text.clone()];
+            lines.extend(new_state.apply_effects(&accepted.effects)?);
+            lines.push(item_message.trim().to_string());
+            Ok((new_state, ParsingResult::Give(lines.join("\n"))))
+        }
+        Some(accepted) => {
+            let text = accepted
+                .show_text
+                .clone()
+                .unwrap_or_else(|| format!("{} looks at the {}.", subject.name, item.name));
+            Ok((state.clone(), ParsingResult::SubjectNoEvent(text)))
+        }
+        None => Ok((state.clone(), ParsingResult::SubjectNoEvent(refusal()))),
+    }
+}
+
+/// Returns the subject's response when the player asks or tells
+/// it about a topic, or the subject's fallback text when it
+/// doesn't know about the topic.
+fn answer_topic(state: &State, subject: &Subject, topic: &str) -> NRResult<(State, ParsingResult)> {
+    let subject = state.subject(subject.id).ok_or(InvalidSubject)?;
+    let known_topic = subject
+        .topics
+        .iter()
+        .find(|t| t.matches(topic) && state.conditions_met(&t.conditions));
+    match known_topic {
+        Some(known_topic) => {
+            let narrative = state
+                .config
+                .narratives
+                .iter()
+                .find(|narrative| narrative.id == known_topic.narrative)
+                .ok_or(InvalidNarrative)?;
+            let mut new_state = state.clone();
+            let mut lines = vec![narrative.text.clone()];
+            lines.extend(new_state.apply_effects(&known_topic.effects)?);
+            Ok((new_state, ParsingResult::SubjectNoEvent(lines.join("\n"))))
+        }
+        None => {
+            let text = subject
+                .unknown_topic_text
+                .clone()
+                .unwrap_or_else(|| format!("{} doesn't know anything about that.", subject.name));
+            Ok((state.clone(), ParsingResult::SubjectNoEvent(text)))
+        }
+    }
+}
+
 fn filter_events<'a>(
     room: &'a Room,
     action: &Action,
@@ -266,7 +414,7 @@ fn are_required_events_completed(state: &State, event: &Event) -> NRResult<bool>
     Ok(required_events_completed)
 }
 
-fn process_event(
+pub(super) fn process_event(
     state: &State,
     event: &Event,
     subject: &Option<Subject>,
@@ -294,12 +442,22 @@ fn process_event(
     new_state = state;
     let (state, _) = process_subject_addition(&new_state, event)?;
     new_state = state;
+    for subject_move in &event.move_subjects {
+        new_state.set_subject_location(subject_move.subject_id, subject_move.location)?;
+    }
 
     if event.remove_old_narrative {
         if let Some(narrative_after) = event.narrative_after {
-            new_state.set_narrative(narrative_after);
+            let room_id = event.narrative_after_room.unwrap_or(event.location);
+            new_state.set_room_narrative(room_id, narrative_after)?;
         }
     }
+    for change in &event.narrative_changes {
+        new_state.set_room_narrative(change.room_id, change.narrative)?;
+    }
+    for message in new_state.apply_effects(&event.effects)? {
+        event_messages.push(format!("\n{}\n", message));
+    }
 
     Ok((new_state, event_messages))
 }
@@ -324,13 +482,7 @@ fn process_subject_movement(
 fn process_subject_addition(state: &State, event: &Event) -> NRResult<(State, ())> {
     let mut new_state = state.clone();
     if let Some(new_subject_id) = event.add_subject {
-        let new_subject = new_state
-            .config
-            .subjects
-            .iter()
-            .find(|s| s.id == new_subject_id)
-            .ok_or(InvalidEvent)?;
-        new_state.add_subject(new_subject.clone())?;
+        new_state.add_subject_by_id(new_subject_id)?;
     }
     Ok((new_state, ()))
 }
@@ -359,7 +511,7 @@ fn return_formated_message(
         .config
         .narratives
         .iter()
-        .find(|narrative| event.narrative.map_or(false, |id| narrative.id == id))
+        .find(|narrative| event.narrative == Some(narrative.id))
         .ok_or(InvalidNarrative)?;
 
     let room_text = if event.remove_old_narrative {
@@ -386,11 +538,10 @@ fn return_formated_message(
 
 fn extract_item_subject(state: &State, action: &Action) -> (Option<Item>, Option<Subject>) {
     let current_room_id = state.current_room;
-    let state_rooms = state.rooms.clone();
-    let current_room = match state_rooms.iter().find(|room| room.id == current_room_id) {
-        Some(room) => room,
-        None => return (None, None),
-    };
+    if !state.rooms.iter().any(|room| room.id == current_room_id) {
+        return (None, None);
+    }
+    let room_subjects = state.subjects_in_room(current_room_id);
     let inventory_item = action.item.clone().filter(|item| {
         state
             .player
@@ -399,12 +550,10 @@ fn extract_item_subject(state: &State, action: &Action) -> (Option<Item>, Option
             .iter()
             .any(|player_item| player_item.id == item.id)
     });
-    let subject = action.subject.clone().filter(|action_subject| {
-        current_room
-            .subjects
-            .iter()
-            .any(|s| s.id == action_subject.id)
-    });
+    let subject = action
+        .subject
+        .clone()
+        .filter(|action_subject| room_subjects.iter().any(|s| s.id == action_subject.id));
     (inventory_item, subject)
 }
 
@@ -434,6 +583,39 @@ fn show_inventory(state: &State) -> NRResult<ParsingResult> {
     }
 }
 
+fn restart_game(state: &State) -> NRResult<(State, ParsingResult)> {
+    let new_state = State::init_shared(Arc::clone(&state.config), state.seed);
+    let room_text = current_room_text(&new_state)?;
+    Ok((new_state, ParsingResult::Restart(room_text)))
+}
+
+fn show_score(state: &State) -> NRResult<ParsingResult> {
+    let player = &state.player;
+    let mut lines = vec![format!(
+        "You have scored {} out of a possible {} points.",
+        player.score(),
+        state.config.max_score()
+    )];
+    lines.extend(state.config.awards().iter().map(|award| {
+        let earned = if player.awards.iter().any(|a| a.reason == award.reason) {
+            "x"
+        } else {
+            " "
+        };
+        format!("  [{}] {}: {}", earned, award.reason, award.points)
+    }));
+    if let Some(health) = player.health {
+        lines.push(format!("\nHealth: {}", health));
+    }
+    lines.extend(
+        player
+            .stats
+            .iter()
+            .map(|(name, value)| format!("{}: {}", name, value)),
+    );
+    Ok(ParsingResult::Score(lines.join("\n")))
+}
+
 fn pick_item(state: &State, item: Item) -> NRResult<(State, ParsingResult)> {
     let current_room_id = state.current_room;
     let current_room = match state.rooms.iter().find(|room| room.id == current_room_id) {
@@ -447,6 +629,11 @@ fn pick_item(state: &State, item: Item) -> NRResult<(State, ParsingResult)> {
         } else {
             Err(CantPick.into())
         }
+    } else if let Some(new_state) = take_from_container(state, &item) {
+        Ok((
+            new_state,
+            ParsingResult::NewItem(format!("\nYou now have a {}\n", item.name)),
+        ))
     } else {
         Err(NoItem.into())
     }
@@ -481,7 +668,13 @@ fn look_item(state: &State, item: Item) -> NRResult<ParsingResult> {
     let inventory_items = &inventory.items;
 
     if room_items.contains(&item) || inventory_items.contains(&item) {
-        Ok(ParsingResult::Look(item.description))
+        match describe_contents(state, &item) {
+            Some(contents) => Ok(ParsingResult::Look(format!(
+                "{}\n{}",
+                item.description, contents
+            ))),
+            None => Ok(ParsingResult::Look(item.description)),
+        }
     } else {
         Ok(ParsingResult::Look("I can't see that here".to_string()))
     }
@@ -489,14 +682,15 @@ fn look_item(state: &State, item: Item) -> NRResult<ParsingResult> {
 
 fn look_subject(state: &State, subject: Subject) -> NRResult<ParsingResult> {
     let current_room_id = state.current_room;
-    let current_room = match state.rooms.iter().find(|room| room.id == current_room_id) {
-        Some(room) => room,
-        None => return Err(NoRoom {}.into()),
-    };
-    let room_subjects = &current_room.subjects;
+    if !state.rooms.iter().any(|room| room.id == current_room_id) {
+        return Err(NoRoom {}.into());
+    }
+    let room_subjects = state.subjects_in_room(current_room_id);
 
-    if room_subjects.contains(&subject) {
-        Ok(ParsingResult::Look(subject.description))
+    if let Some(room_subject) = room_subjects.iter().find(|s| s.id == subject.id) {
+        Ok(ParsingResult::Look(
+            room_subject.current_description(&state.config.settings),
+        ))
     } else {
         Ok(ParsingResult::Look("I can't see that here".to_string()))
     }
@@ -509,9 +703,8 @@ fn look_room(state: &State) -> NRResult<ParsingResult> {
         Some(room) => room,
         None => return Err(NoRoom.into()),
     };
-    let room_subjects = current_room
-        .subjects
-        .clone()
+    let room_subjects = state
+        .subjects_in_room(current_room_id)
         .iter()
         .map(|subject| subject.name.clone())
         .collect::<Vec<String>>()
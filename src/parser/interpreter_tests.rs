@@ -1,5 +1,9 @@
 use super::super::interpreter::*;
-use crate::config::{Config, Verb};
+use crate::config::{
+    AcceptedItem, Condition, Config, DispositionBand, DispositionText, Effect, ItemCapabilities,
+    NarrativeChange, SubjectLocation, SubjectMove, Topic, Verb,
+};
+use crate::parser::parse;
 #[cfg(test)]
 use pretty_assertions::assert_eq;
 #[test]
@@ -29,6 +33,16 @@ fn it_looks_at_subject() {
         name: "subject2".to_string(),
         description: "a non-existing subject".to_string(),
         default_text: "".to_string(),
+        behavior: None,
+        dialogue: None,
+        topics: vec![],
+        unknown_topic_text: None,
+        accepts: vec![],
+        refusal_text: None,
+        disposition: 0,
+        flags: vec![],
+        disposition_texts: vec![],
+        item_ids: vec![],
     };
     let look_result1 = look_subject(&state, subject1);
     let look_result2 = look_subject(&state, subject2);
@@ -52,6 +66,7 @@ fn it_looks_at_item() {
         name: "item2".to_string(),
         description: "a non-existing item".to_string(),
         can_pick: false,
+        capabilities: ItemCapabilities::default(),
     };
     let look_result1 = look_item(&state, item1);
     let look_result2 = look_item(&state, item2);
@@ -85,6 +100,7 @@ fn it_picks_items() {
         name: "item3".to_string(),
         description: "a non-existing item".to_string(),
         can_pick: false,
+        capabilities: ItemCapabilities::default(),
     };
     let pick_result1 = pick_item(&state, item1);
     let pick_result2 = pick_item(&state, item2);
@@ -151,6 +167,7 @@ fn it_shows_inventory() {
         name: "item1".to_string(),
         description: "item 1 description".to_string(),
         can_pick: false,
+        capabilities: ItemCapabilities::default(),
     });
     let inventory_result2 = show_inventory(&state);
     assert_eq!(
@@ -162,6 +179,7 @@ fn it_shows_inventory() {
         name: "item2".to_string(),
         description: "item 2 description".to_string(),
         can_pick: false,
+        capabilities: ItemCapabilities::default(),
     });
     let inventory_result3 = show_inventory(&state);
     assert_eq!(
@@ -179,12 +197,23 @@ fn it_extracts_item_and_subject() {
         name: "subject1".to_string(),
         description: "a subject description".to_string(),
         default_text: "a subject default text".to_string(),
+        behavior: None,
+        dialogue: None,
+        topics: vec![],
+        unknown_topic_text: None,
+        accepts: vec![],
+        refusal_text: None,
+        disposition: 0,
+        flags: vec![],
+        disposition_texts: vec![],
+        item_ids: vec![],
     };
     let item1 = Item {
         id: 1,
         name: "item1".to_string(),
         description: "item 1 description".to_string(),
         can_pick: false,
+        capabilities: ItemCapabilities::default(),
     };
     state.player.inventory.items.push(item1.clone());
     let item2 = Item {
@@ -192,6 +221,7 @@ fn it_extracts_item_and_subject() {
         name: "item2".to_string(),
         description: "item 2 description".to_string(),
         can_pick: false,
+        capabilities: ItemCapabilities::default(),
     };
     let verb = Verb {
         id: 1,
@@ -203,6 +233,8 @@ fn it_extracts_item_and_subject() {
         subject: Some(subject.clone()),
         item: Some(item1.clone()),
         movement: None,
+        topic: None,
+        second_item: None,
         command_tokens: vec![
             "go".to_string(),
             "subject1".to_string(),
@@ -215,6 +247,8 @@ fn it_extracts_item_and_subject() {
         subject: Some(subject.clone()),
         item: Some(item2),
         movement: None,
+        topic: None,
+        second_item: None,
         command_tokens: vec![
             "go".to_string(),
             "subject1".to_string(),
@@ -227,6 +261,8 @@ fn it_extracts_item_and_subject() {
         subject: None,
         item: None,
         movement: Some(Directions::North),
+        topic: None,
+        second_item: None,
         command_tokens: vec!["north".to_string()],
         input: "north".to_string(),
     };
@@ -293,7 +329,7 @@ fn it_handles_verbs() {
         ParsingResult::Inventory(String::from("You are not carrying anything."))
     );
     assert_eq!(verb_result4.unwrap().1, ParsingResult::Quit);
-    assert_eq!(verb_result5.unwrap().1, ParsingResult::Help(String::from("\nTo play this game you type your commands and hit enter to execute them. Typically a command has at most three parts: a verb, a subject, and an item. A verb indicates an action you, the player, wants to execute. Many commands can be executed with just a verb such as look, help, quit. For more complex commands you will also need verb and either a subject or an item. A command can also have a verb, item, and subject. A complex command can be: look at dog, talk to person, pick the box, give the box to the dog.\n\nThe game will ignore words like 'to', 'the', 'at', 'from', so using them is optional. A valid command can be: talk person, pick box, go south, climb tree, use axe tree.\n\nValid verbs: quit, help, look, inventory, pick, drop, give, talk, hug")));
+    assert_eq!(verb_result5.unwrap().1, ParsingResult::Help(String::from("\nTo play this game you type your commands and hit enter to execute them. Typically a command has at most three parts: a verb, a subject, and an item. A verb indicates an action you, the player, wants to execute. Many commands can be executed with just a verb such as look, help, quit. For more complex commands you will also need verb and either a subject or an item. A command can also have a verb, item, and subject. A complex command can be: look at dog, talk to person, pick the box, give the box to the dog.\n\nThe game will ignore words like 'to', 'the', 'at', 'from', so using them is optional. A valid command can be: talk person, pick box, go south, climb tree, use axe tree.\n\nValid verbs: quit, help, look, inventory, pick, drop, give, talk, hug, undo, redo")));
 }
 
 #[test]
@@ -631,3 +667,234 @@ fn it_process_action() {
     );
     assert_eq!(format!("{}", action_give_item.subject.unwrap()), "subject1");
 }
+
+#[test]
+fn it_changes_narratives_in_other_rooms() {
+    let config = Config::from_path("fixtures/");
+    let state = State::init(config);
+    let mut event = state.config.events[3].clone();
+    event.narrative_changes = vec![NarrativeChange {
+        room_id: 2,
+        narrative: 5,
+    }];
+    event.narrative_after = Some(3);
+    event.narrative_after_room = Some(2);
+    let (new_state, _) = process_event(&state, &event, &None).unwrap();
+    // narrative_changes are applied after narrative_after
+    assert_eq!(new_state.rooms[1].narrative, 5);
+    assert_eq!(new_state.rooms[0].narrative, 1);
+
+    event.narrative_changes = vec![];
+    let (new_state, _) = process_event(&state, &event, &None).unwrap();
+    assert_eq!(new_state.rooms[1].narrative, 3);
+    assert_eq!(new_state.rooms[0].narrative, 1);
+
+    event.narrative_changes = vec![NarrativeChange {
+        room_id: 42,
+        narrative: 5,
+    }];
+    assert_eq!(
+        process_event(&state, &event, &None)
+            .unwrap_err()
+            .to_string(),
+        InvalidRoom.to_string()
+    );
+}
+
+#[test]
+fn it_moves_subjects_between_rooms() {
+    let config = Config::from_path("fixtures/");
+    let state = State::init(config);
+    let mut event = state.config.events[3].clone();
+    // the player is in room 1, subject2 is in room 2
+    event.move_subjects = vec![SubjectMove {
+        subject_id: 2,
+        location: SubjectLocation::FollowingPlayer,
+    }];
+    let (new_state, _) = process_event(&state, &event, &None).unwrap();
+    assert_eq!(
+        new_state.subject_location(2),
+        Some(SubjectLocation::FollowingPlayer)
+    );
+    assert!(new_state.rooms[1].subjects.is_empty());
+    assert_eq!(
+        look_room(&new_state).unwrap(),
+        ParsingResult::Look(
+            "first room\n\nHere you see: \nan item1\nan item2\nsubject1\nsubject2".to_string()
+        )
+    );
+    let (new_state, _) = handle_movement(&new_state, Some(Directions::South)).unwrap();
+    let (_, talk_result) = handle_event(&new_state, Action::parse(&new_state, "talk subject2"))
+        .expect("subject2 should have followed the player");
+    assert!(matches!(talk_result, ParsingResult::EventSuccess(_)));
+}
+#[test]
+fn it_answers_questions_about_topics() {
+    let mut config = Config::from_path("fixtures/");
+    config.allowed_verbs.push(Verb {
+        id: 20,
+        names: vec!["ask".to_string()],
+        verb_function: VerbFunction::Ask,
+    });
+    config.allowed_verbs.push(Verb {
+        id: 21,
+        names: vec!["tell".to_string()],
+        verb_function: VerbFunction::Tell,
+    });
+    config.subjects[1].topics = vec![
+        Topic {
+            keywords: vec!["item3".to_string(), "thing".to_string()],
+            narrative: 3,
+            conditions: vec![Condition::EventNotCompleted { event_id: 1 }],
+            effects: vec![Effect::SetVariable {
+                name: "asked".to_string(),
+                value: 1,
+            }],
+        },
+        Topic {
+            keywords: vec!["item3".to_string()],
+            narrative: 4,
+            conditions: vec![],
+            effects: vec![],
+        },
+    ];
+    config.subjects[1].unknown_topic_text = Some("subject2 shrugs.".to_string());
+    let mut state = State::init(config);
+    state.current_room = 2;
+    state.complete_event(5);
+
+    let (new_state, result) = parse(&state, "ask subject2 about the thing").unwrap();
+    assert_eq!(
+        result,
+        ParsingResult::SubjectNoEvent("this narrative should replace the old one.".to_string())
+    );
+    assert_eq!(new_state.variable("asked"), 1);
+
+    state.complete_event(1);
+    let (_, result) = parse(&state, "tell subject2 about item3").unwrap();
+    assert_eq!(
+        result,
+        ParsingResult::SubjectNoEvent(
+            "this narrative should be returned along with the text of room 1.".to_string()
+        )
+    );
+    let (_, result) = parse(&state, "ask subject2 about dragons").unwrap();
+    assert_eq!(
+        result,
+        ParsingResult::SubjectNoEvent("subject2 shrugs.".to_string())
+    );
+
+    // the subject as it is in the game is used, not as it
+    // was configured
+    state.subject_mut(2).unwrap().unknown_topic_text = None;
+    let (_, result) = parse(&state, "ask subject2 about dragons").unwrap();
+    assert_eq!(
+        result,
+        ParsingResult::SubjectNoEvent("subject2 doesn't know anything about that.".to_string())
+    );
+}
+#[test]
+fn it_gives_and_shows_items_to_subjects() {
+    let mut config = Config::from_path("fixtures/");
+    config
+        .allowed_verbs
+        .iter_mut()
+        .find(|verb| verb.names.contains(&"give".to_string()))
+        .unwrap()
+        .verb_function = VerbFunction::Give;
+    config.allowed_verbs.push(Verb {
+        id: 20,
+        names: vec!["show".to_string()],
+        verb_function: VerbFunction::Show,
+    });
+    config.subjects[1].accepts = vec![AcceptedItem {
+        item_id: 3,
+        text: "Thank you!".to_string(),
+        show_text: Some("What a nice item3.".to_string()),
+        conditions: vec![],
+        effects: vec![Effect::Message {
+            text: "subject2 smiles.".to_string(),
+        }],
+    }];
+    let mut state = State::init(config);
+    state.current_room = 2;
+    state.complete_event(5);
+    let item2 = state.config.items[1].clone();
+    let item3 = state.config.items[2].clone();
+
+    assert_eq!(
+        parse(&state, "give item3 to subject2")
+            .unwrap_err()
+            .to_string(),
+        NoItem.to_string()
+    );
+
+    state.player.inventory.add_item(item2);
+    state.player.inventory.add_item(item3);
+    let (_, result) = parse(&state, "show item3 to subject2").unwrap();
+    assert_eq!(
+        result,
+        ParsingResult::SubjectNoEvent("What a nice item3.".to_string())
+    );
+    let (_, result) = parse(&state, "give item2 to subject2").unwrap();
+    assert_eq!(
+        result,
+        ParsingResult::SubjectNoEvent("subject2 doesn't want the item2.".to_string())
+    );
+    let (new_state, result) = parse(&state, "give item3 to subject2").unwrap();
+    assert_eq!(
+        result,
+        ParsingResult::Give("Thank you!\nsubject2 smiles.\nYou no longer have a item3".to_string())
+    );
+    assert_eq!(new_state.player.inventory.items.len(), 1);
+    assert_eq!(new_state.rooms[1].stash, state.rooms[1].stash);
+    assert_eq!(new_state.subject(2).unwrap().item_ids, vec![3]);
+
+    // the subject as it is in the game is used, not as it
+    // was configured
+    state.subject_mut(2).unwrap().refusal_text = Some("No, thanks.".to_string());
+    let (_, result) = parse(&state, "show item2 to subject2").unwrap();
+    assert_eq!(
+        result,
+        ParsingResult::SubjectNoEvent("No, thanks.".to_string())
+    );
+    state.subject_mut(2).unwrap().accepts[0].show_text = None;
+    let (_, result) = parse(&state, "show item3 to subject2").unwrap();
+    assert_eq!(
+        result,
+        ParsingResult::SubjectNoEvent("subject2 looks at the item3.".to_string())
+    );
+}
+#[test]
+fn it_uses_texts_for_the_subject_disposition() {
+    let mut config = Config::from_path("fixtures/");
+    config.subjects[0].disposition_texts = vec![DispositionText {
+        band: DispositionBand::Hostile,
+        description: Some("subject1 glares at you.".to_string()),
+        default_text: Some("Go away.".to_string()),
+    }];
+    let state = State::init(config);
+    let mut event = state.config.events[3].clone();
+    event.effects = vec![
+        Effect::ChangeDisposition {
+            subject_id: 1,
+            by: -10,
+        },
+        Effect::Message {
+            text: "subject1 is offended.".to_string(),
+        },
+    ];
+    let (new_state, messages) = process_event(&state, &event, &None).unwrap();
+    assert_eq!(messages, vec!["\nsubject1 is offended.\n".to_string()]);
+    let subject1 = new_state.config.subjects[0].clone();
+    assert_eq!(
+        look_subject(&new_state, subject1).unwrap(),
+        ParsingResult::Look("subject1 glares at you.".to_string())
+    );
+    // no event uses this verb with subject1
+    let (_, result) = handle_event(&new_state, Action::parse(&new_state, "give subject1")).unwrap();
+    assert_eq!(
+        result,
+        ParsingResult::SubjectNoEvent("Go away.".to_string())
+    );
+}
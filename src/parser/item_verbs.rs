@@ -0,0 +1,310 @@
+//! Built-in behavior for the verbs that act on items, such as
+//! eating, wearing, or opening them. Items declare what can be
+//! done with them in their capabilities, and anything an item
+//! doesn't declare is left to the events using the verb.
+
+use super::action::Action;
+use super::errors::{ContainerCycle, InvalidVerb, NoItem, NoRoom};
+use crate::config::{Item, State, VerbFunction};
+use crate::NRResult;
+use crate::ParsingResult;
+
+/// Runs the built-in behavior of the action's verb on its item,
+/// or returns `None` if the item doesn't have the capability
+/// the verb needs.
+pub(super) fn handle_item_verb(
+    state: &State,
+    action: &Action,
+) -> Option<NRResult<(State, ParsingResult)>> {
+    let verb_function = &action.verb.as_ref()?.verb_function;
+    let item = state
+        .config
+        .items
+        .iter()
+        .find(|item| Some(item.id) == action.item.as_ref().map(|i| i.id))?;
+    let capabilities = &item.capabilities;
+    let result = match verb_function {
+        VerbFunction::Eat if capabilities.edible => consume(state, item, "eat"),
+        VerbFunction::Drink if capabilities.drinkable => consume(state, item, "drink"),
+        VerbFunction::Read if capabilities.text.is_some() => read(state, item),
+        VerbFunction::Wear if capabilities.wear_slot.is_some() => wear(state, item),
+        VerbFunction::Remove if capabilities.wear_slot.is_some() => take_off(state, item),
+        VerbFunction::Open | VerbFunction::Close if capabilities.openable => {
+            open_or_close(state, item, *verb_function == VerbFunction::Open)
+        }
+        VerbFunction::Lock | VerbFunction::Unlock if capabilities.key.is_some() => lock_or_unlock(
+            state,
+            item,
+            action.second_item.as_ref(),
+            *verb_function == VerbFunction::Lock,
+        ),
+        VerbFunction::TurnOn | VerbFunction::TurnOff if capabilities.switchable => {
+            turn_on_or_off(state, item, *verb_function == VerbFunction::TurnOn)
+        }
+        VerbFunction::Put => {
+            let container = state.config.items.iter().find(|container| {
+                Some(container.id) == action.second_item.as_ref().map(|i| i.id)
+                    && container.capabilities.container
+            })?;
+            put(state, item, container)
+        }
+        _ => return None,
+    };
+    Some(result)
+}
+
+fn is_carried(state: &State, item: &Item) -> bool {
+    state.player.inventory.items.iter().any(|i| i.id == item.id)
+}
+
+fn is_reachable(state: &State, item: &Item) -> bool {
+    is_carried(state, item)
+        || state
+            .rooms
+            .iter()
+            .find(|room| room.id == state.current_room)
+            .is_some_and(|room| room.stash.items.iter().any(|i| i.id == item.id))
+}
+
+fn item_used(state: State, text: String) -> NRResult<(State, ParsingResult)> {
+    Ok((state, ParsingResult::ItemUsed(text)))
+}
+
+/// Eats or drinks the item, using it up.
+fn consume(state: &State, item: &Item, verb: &str) -> NRResult<(State, ParsingResult)> {
+    if !is_reachable(state, item) {
+        return Err(NoItem.into());
+    }
+    let mut new_state = state.clone();
+    if is_carried(state, item) {
+        new_state.player.remove_item(item.clone())?;
+    } else {
+        new_state
+            .rooms
+            .iter_mut()
+            .find(|room| room.id == state.current_room)
+            .ok_or(NoRoom)?
+            .stash
+            .remove_item(item.clone())?;
+    }
+    let mut lines = vec![format!("You {} the {}.", verb, item.name)];
+    lines.extend(new_state.apply_effects(&item.capabilities.effects)?);
+    item_used(new_state, lines.join("\n"))
+}
+
+fn read(state: &State, item: &Item) -> NRResult<(State, ParsingResult)> {
+    if !is_reachable(state, item) {
+        return Err(NoItem.into());
+    }
+    let mut new_state = state.clone();
+    let mut lines = vec![item.capabilities.text.clone().unwrap_or_default()];
+    lines.extend(new_state.apply_effects(&item.capabilities.effects)?);
+    item_used(new_state, lines.join("\n"))
+}
+
+fn wear(state: &State, item: &Item) -> NRResult<(State, ParsingResult)> {
+    if !is_carried(state, item) {
+        return Err(NoItem.into());
+    }
+    if state.player.worn.contains(&item.id) {
+        return item_used(
+            state.clone(),
+            format!("You're already wearing the {}.", item.name),
+        );
+    }
+    let slot_taken_by = state.player.inventory.items.iter().find(|worn| {
+        state.player.worn.contains(&worn.id)
+            && worn.capabilities.wear_slot == item.capabilities.wear_slot
+    });
+    if let Some(worn) = slot_taken_by {
+        return item_used(
+            state.clone(),
+            format!("You're already wearing the {}.", worn.name),
+        );
+    }
+    let mut new_state = state.clone();
+    new_state.player.worn.push(item.id);
+    item_used(new_state, format!("You put on the {}.", item.name))
+}
+
+fn take_off(state: &State, item: &Item) -> NRResult<(State, ParsingResult)> {
+    if !state.player.worn.contains(&item.id) {
+        return item_used(
+            state.clone(),
+            format!("You're not wearing the {}.", item.name),
+        );
+    }
+    let mut new_state = state.clone();
+    new_state.player.worn.retain(|id| *id != item.id);
+    item_used(new_state, format!("You take off the {}.", item.name))
+}
+
+fn open_or_close(state: &State, item: &Item, open: bool) -> NRResult<(State, ParsingResult)> {
+    if !is_reachable(state, item) {
+        return Err(NoItem.into());
+    }
+    let item_state = state.item_state(item.id);
+    if open && item_state.locked {
+        return item_used(state.clone(), format!("The {} is locked.", item.name));
+    }
+    if item_state.open == open {
+        let status = if open { "open" } else { "closed" };
+        return item_used(
+            state.clone(),
+            format!("The {} is already {}.", item.name, status),
+        );
+    }
+    let mut new_state = state.clone();
+    new_state.item_state_mut(item.id).open = open;
+    if !open {
+        return item_used(new_state, format!("You close the {}.", item.name));
+    }
+    let mut text = format!("You open the {}.", item.name);
+    if !item_state.contents.is_empty() {
+        text.push_str(&format!(
+            "\nInside the {} you see: {}",
+            item.name,
+            content_names(&item_state.contents)
+        ));
+    }
+    item_used(new_state, text)
+}
+
+fn lock_or_unlock(
+    state: &State,
+    item: &Item,
+    key: Option<&Item>,
+    lock: bool,
+) -> NRResult<(State, ParsingResult)> {
+    if !is_reachable(state, item) {
+        return Err(NoItem.into());
+    }
+    let key_id = item.capabilities.key.ok_or(InvalidVerb)?;
+    if key.is_some_and(|key| key.id != key_id) {
+        return item_used(
+            state.clone(),
+            format!("That doesn't fit the {}.", item.name),
+        );
+    }
+    if !state.player.inventory.items.iter().any(|i| i.id == key_id) {
+        return item_used(
+            state.clone(),
+            format!("You don't have the key for the {}.", item.name),
+        );
+    }
+    let item_state = state.item_state(item.id);
+    if item_state.locked == lock {
+        let status = if lock { "locked" } else { "unlocked" };
+        return item_used(
+            state.clone(),
+            format!("The {} is already {}.", item.name, status),
+        );
+    }
+    if lock && item_state.open {
+        return item_used(
+            state.clone(),
+            format!("You need to close the {} first.", item.name),
+        );
+    }
+    let mut new_state = state.clone();
+    new_state.item_state_mut(item.id).locked = lock;
+    let verb = if lock { "lock" } else { "unlock" };
+    item_used(new_state, format!("You {} the {}.", verb, item.name))
+}
+
+fn turn_on_or_off(state: &State, item: &Item, on: bool) -> NRResult<(State, ParsingResult)> {
+    if !is_reachable(state, item) {
+        return Err(NoItem.into());
+    }
+    let status = if on { "on" } else { "off" };
+    if state.item_state(item.id).on == on {
+        return item_used(
+            state.clone(),
+            format!("The {} is already {}.", item.name, status),
+        );
+    }
+    let mut new_state = state.clone();
+    new_state.item_state_mut(item.id).on = on;
+    item_used(new_state, format!("You turn {} the {}.", status, item.name))
+}
+
+fn put(state: &State, item: &Item, container: &Item) -> NRResult<(State, ParsingResult)> {
+    if !is_carried(state, item) || !is_reachable(state, container) {
+        return Err(NoItem.into());
+    }
+    if holds(state, item.id, container.id) {
+        return Err(ContainerCycle.into());
+    }
+    if container.capabilities.openable && !state.item_state(container.id).open {
+        return item_used(state.clone(), format!("The {} is closed.", container.name));
+    }
+    let mut new_state = state.clone();
+    let item = new_state.player.remove_item(item.clone())?;
+    let text = format!("You put the {} in the {}.", item.name, container.name);
+    new_state.item_state_mut(container.id).contents.push(item);
+    item_used(new_state, text)
+}
+
+/// Whether the item is the container, or holds it somewhere
+/// inside its contents.
+fn holds(state: &State, item_id: u16, container_id: u16) -> bool {
+    item_id == container_id
+        || state
+            .item_state(item_id)
+            .contents
+            .iter()
+            .any(|inner| holds(state, inner.id, container_id))
+}
+
+/// Finds the item inside an open container the player can reach,
+/// and moves it to the player's inventory.
+pub(super) fn take_from_container(state: &State, item: &Item) -> Option<State> {
+    let container = state.config.items.iter().find(|container| {
+        is_reachable(state, container)
+            && (!container.capabilities.openable || state.item_state(container.id).open)
+            && state
+                .item_state(container.id)
+                .contents
+                .iter()
+                .any(|i| i.id == item.id)
+    })?;
+    let mut new_state = state.clone();
+    let contents = &mut new_state.item_state_mut(container.id).contents;
+    let index = contents.iter().position(|i| i.id == item.id)?;
+    let item = contents.remove(index);
+    new_state.player.inventory.add_item(item);
+    Some(new_state)
+}
+
+/// Describes what's inside an item that can hold other items,
+/// if the player can see inside it.
+pub(super) fn describe_contents(state: &State, item: &Item) -> Option<String> {
+    if !item.capabilities.container {
+        return None;
+    }
+    let item_state = state.item_state(item.id);
+    if item.capabilities.openable && !item_state.open {
+        return Some(format!("The {} is closed.", item.name));
+    }
+    if item_state.contents.is_empty() {
+        Some(format!("The {} is empty.", item.name))
+    } else {
+        Some(format!(
+            "Inside the {} you see: {}",
+            item.name,
+            content_names(&item_state.contents)
+        ))
+    }
+}
+
+fn content_names(contents: &[Item]) -> String {
+    contents
+        .iter()
+        .map(|item| item.name.clone())
+        .collect::<Vec<String>>()
+        .join(", ")
+}
+
+#[cfg(test)]
+#[path = "item_verbs_tests.rs"]
+mod item_verbs_tests;
@@ -0,0 +1,238 @@
+use super::*;
+use crate::config::{Config, Effect, ItemCapabilities, Verb};
+use crate::parser::parse;
+#[cfg(test)]
+use pretty_assertions::assert_eq;
+
+fn item(id: u16, name: &str, capabilities: ItemCapabilities) -> Item {
+    Item {
+        id,
+        name: name.to_string(),
+        description: format!("a {}", name),
+        can_pick: true,
+        capabilities,
+    }
+}
+
+fn verb(id: u16, name: &str, verb_function: VerbFunction) -> Verb {
+    Verb {
+        id,
+        names: vec![name.to_string()],
+        verb_function,
+    }
+}
+
+fn state_with_capabilities() -> State {
+    let mut config = Config::from_path("fixtures/");
+    config.allowed_verbs.extend(vec![
+        verb(12, "eat", VerbFunction::Eat),
+        verb(13, "drink", VerbFunction::Drink),
+        verb(14, "read", VerbFunction::Read),
+        verb(15, "wear", VerbFunction::Wear),
+        verb(16, "remove", VerbFunction::Remove),
+        verb(17, "open", VerbFunction::Open),
+        verb(18, "close", VerbFunction::Close),
+        verb(19, "lock", VerbFunction::Lock),
+        verb(20, "unlock", VerbFunction::Unlock),
+        verb(21, "turn on", VerbFunction::TurnOn),
+        verb(22, "turn off", VerbFunction::TurnOff),
+        verb(23, "put", VerbFunction::Put),
+        verb(24, "take", VerbFunction::Take),
+    ]);
+    config.items.extend(vec![
+        item(
+            10,
+            "apple",
+            ItemCapabilities {
+                edible: true,
+                effects: vec![Effect::ChangeVariable {
+                    name: "meals".to_string(),
+                    by: 1,
+                }],
+                ..Default::default()
+            },
+        ),
+        item(
+            11,
+            "note",
+            ItemCapabilities {
+                text: Some("Meet me at midnight.".to_string()),
+                ..Default::default()
+            },
+        ),
+        item(
+            12,
+            "hat",
+            ItemCapabilities {
+                wear_slot: Some("head".to_string()),
+                ..Default::default()
+            },
+        ),
+        item(
+            13,
+            "helmet",
+            ItemCapabilities {
+                wear_slot: Some("head".to_string()),
+                ..Default::default()
+            },
+        ),
+        item(
+            14,
+            "chest",
+            ItemCapabilities {
+                openable: true,
+                container: true,
+                key: Some(15),
+                locked: true,
+                ..Default::default()
+            },
+        ),
+        item(15, "key", ItemCapabilities::default()),
+        item(
+            16,
+            "lamp",
+            ItemCapabilities {
+                switchable: true,
+                ..Default::default()
+            },
+        ),
+    ]);
+    let mut state = State::init(config);
+    let items = state.config.items.clone();
+    for id in [10, 11, 12, 13, 15, 16] {
+        let item = items.iter().find(|item| item.id == id).unwrap().clone();
+        state.player.inventory.add_item(item);
+    }
+    let chest = items.iter().find(|item| item.id == 14).unwrap().clone();
+    state.rooms[0].stash.add_item(chest);
+    state
+}
+
+fn item_used(state: &State, input: &str) -> (State, String) {
+    match parse(state, input).unwrap() {
+        (new_state, ParsingResult::ItemUsed(text)) => (new_state, text),
+        (_, result) => panic!("expected ItemUsed, got {:?}", result),
+    }
+}
+
+#[test]
+fn it_eats_items_and_applies_their_effects() {
+    let state = state_with_capabilities();
+    let (new_state, text) = item_used(&state, "eat apple");
+    assert_eq!(text, "You eat the apple.");
+    assert!(new_state.player.inventory.items.iter().all(|i| i.id != 10));
+    assert_eq!(new_state.variable("meals"), 1);
+    assert!(parse(&new_state, "eat apple").is_err());
+}
+
+#[test]
+fn it_reads_items_with_text() {
+    let state = state_with_capabilities();
+    let (_, text) = item_used(&state, "read note");
+    assert_eq!(text, "Meet me at midnight.");
+}
+
+#[test]
+fn it_wears_one_item_per_slot() {
+    let state = state_with_capabilities();
+    let (state, text) = item_used(&state, "wear hat");
+    assert_eq!(text, "You put on the hat.");
+    assert_eq!(state.player.worn, vec![12]);
+    let (state, text) = item_used(&state, "wear helmet");
+    assert_eq!(text, "You're already wearing the hat.");
+    let (state, text) = item_used(&state, "remove hat");
+    assert_eq!(text, "You take off the hat.");
+    assert!(state.player.worn.is_empty());
+    let (state, _) = item_used(&state, "wear helmet");
+    assert_eq!(state.player.worn, vec![13]);
+}
+
+#[test]
+fn it_unlocks_opens_and_fills_containers() {
+    let state = state_with_capabilities();
+    let (state, text) = item_used(&state, "open chest");
+    assert_eq!(text, "The chest is locked.");
+    let (state, text) = item_used(&state, "unlock chest with key");
+    assert_eq!(text, "You unlock the chest.");
+    let (state, text) = item_used(&state, "put apple in chest");
+    assert_eq!(text, "The chest is closed.");
+    let (state, text) = item_used(&state, "open chest");
+    assert_eq!(text, "You open the chest.");
+    let (state, text) = item_used(&state, "put apple in chest");
+    assert_eq!(text, "You put the apple in the chest.");
+    assert!(state.player.inventory.items.iter().all(|i| i.id != 10));
+    assert_eq!(state.item_state(14).contents[0].id, 10);
+
+    let (state, text) = item_used(&state, "lock chest");
+    assert_eq!(text, "You need to close the chest first.");
+    let (state, text) = item_used(&state, "close chest");
+    assert_eq!(text, "You close the chest.");
+    let (state, text) = item_used(&state, "open chest");
+    assert_eq!(text, "You open the chest.\nInside the chest you see: apple");
+    let (state, result) = parse(&state, "take apple").unwrap();
+    assert_eq!(
+        result,
+        ParsingResult::NewItem("\nYou now have a apple\n".to_string())
+    );
+    assert!(state.item_state(14).contents.is_empty());
+    assert!(state.player.inventory.items.iter().any(|i| i.id == 10));
+}
+
+#[test]
+fn it_needs_the_key_to_unlock() {
+    let mut state = state_with_capabilities();
+    state.player.inventory.items.retain(|i| i.id != 15);
+    let (_, text) = item_used(&state, "unlock chest");
+    assert_eq!(text, "You don't have the key for the chest.");
+}
+
+#[test]
+fn it_switches_items_on_and_off() {
+    let state = state_with_capabilities();
+    let (state, text) = item_used(&state, "turn on lamp");
+    assert_eq!(text, "You turn on the lamp.");
+    assert!(state.item_state(16).on);
+    let (state, text) = item_used(&state, "turn on lamp");
+    assert_eq!(text, "The lamp is already on.");
+    let (state, text) = item_used(&state, "turn off lamp");
+    assert_eq!(text, "You turn off the lamp.");
+    assert!(!state.item_state(16).on);
+}
+
+#[test]
+fn it_falls_back_to_events_without_the_capability() {
+    let state = state_with_capabilities();
+    // the lamp isn't edible and no event uses "eat", so the
+    // verb is handled like any other event verb.
+    assert!(!matches!(
+        parse(&state, "eat lamp"),
+        Ok((_, ParsingResult::ItemUsed(_)))
+    ));
+}
+
+#[test]
+fn it_refuses_to_put_containers_inside_themselves() {
+    let mut state = state_with_capabilities();
+    let chest = state.rooms[0].stash.items.pop().unwrap();
+    state.player.inventory.add_item(chest.clone());
+    let (state, _) = item_used(&state, "unlock chest with key");
+    let (state, _) = item_used(&state, "open chest");
+    assert_eq!(
+        put(&state, &chest, &chest).unwrap_err().to_string(),
+        ContainerCycle.to_string()
+    );
+    assert!(state.item_state(14).contents.is_empty());
+
+    let (mut state, _) = item_used(&state, "put lamp in chest");
+    state.item_state_mut(16).contents.push(chest);
+    assert!(holds(&state, 14, 16));
+    assert!(!holds(&state, 10, 14));
+}
+
+#[test]
+fn it_stops_wearing_items_that_are_used_up() {
+    let mut state = state_with_capabilities();
+    state.player.worn.push(10);
+    let (state, _) = item_used(&state, "eat apple");
+    assert!(state.player.worn.is_empty());
+}
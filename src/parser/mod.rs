@@ -2,15 +2,21 @@
 /// are structs containing the important information
 /// needed to parse the user input.
 pub mod action;
+pub mod dialogue;
 pub mod errors;
 /// Module with the various functions used to parse
 /// the user input.
 pub mod interpreter;
+mod item_verbs;
+mod turn;
 
 use self::action::Action;
-use self::errors::{EmptyInput, InvalidEvent};
+use self::errors::{EmptyInput, GameIsOver, InvalidEvent, NothingToRedo, NothingToUndo};
 use self::interpreter::process_action;
-use crate::config::State;
+use self::turn::{allowed_after_game_over, end_turn, takes_turn};
+use crate::config::{State, VerbFunction};
+use crate::history::History;
+use crate::util::current_room_text;
 use crate::NRResult;
 use crate::ParsingResult;
 
@@ -21,11 +27,45 @@ use crate::ParsingResult;
 /// a `ParsingResult` which is contains the output of
 /// the game. The `ParsingResult` returned by this
 /// function that is meant to be consumed by the frontend.
+///
+/// While the player is in a conversation, a number picks
+/// one of the dialogue choices, and anything else ends
+/// the conversation and is parsed as a regular command.
+/// The conversation also ends once the subject has left
+/// the room.
+///
+/// Once the game is over, every command other than quitting,
+/// restarting, undoing, or redoing returns an error.
 pub fn parse(state: &State, input: &str) -> NRResult<(State, ParsingResult)> {
+    if state.game_over.is_some() && !allowed_after_game_over(&Action::parse(state, input)) {
+        return Err(GameIsOver.into());
+    }
+    if state.dialogue.is_some() {
+        if let (true, Ok(number)) = (
+            dialogue::subject_is_here(state),
+            input.trim().parse::<usize>(),
+        ) {
+            let (new_state, result) = dialogue::choose(state, number)?;
+            return end_turn(state, new_state, result);
+        }
+        let mut new_state = state.clone();
+        new_state.dialogue = None;
+        return parse(&new_state, input);
+    }
     if !input.is_empty() {
         let action = Action::parse(state, input);
         match action.is_valid() {
-            true => process_action(state, action),
+            true => {
+                let takes_turn = takes_turn(state, &action);
+                match process_action(state, action)? {
+                    (new_state, ParsingResult::Quit) => Ok((new_state, ParsingResult::Quit)),
+                    (new_state, ParsingResult::Restart(room_text)) => {
+                        Ok((new_state, ParsingResult::Restart(room_text)))
+                    }
+                    (new_state, result) if takes_turn => end_turn(state, new_state, result),
+                    (new_state, result) => Ok((new_state, result)),
+                }
+            }
             false => Err(InvalidEvent.into()),
         }
     } else {
@@ -33,5 +73,59 @@ pub fn parse(state: &State, input: &str) -> NRResult<(State, ParsingResult)> {
     }
 }
 
+/// Same as `parse`, but also keeps track of the game's
+/// history so commands can be undone and redone.
+///
+/// Commands using verbs with `VerbFunction::Undo` and
+/// `VerbFunction::Redo` move through the history, and
+/// any other command is remembered in it, forgetting the
+/// commands that could be redone.
+///
+/// ## Example:
+/// ```rust
+/// # use nightrunner_lib::config::{Config, State};
+/// # use nightrunner_lib::history::History;
+/// # use nightrunner_lib::parser::parse_with_history;
+/// # use nightrunner_lib::ParsingResult;
+/// // the fixtures have "undo" and "redo" verbs
+/// let state = State::init(Config::from_path("./fixtures/"));
+/// let mut history = History::default();
+/// let (moved, _) = parse_with_history(&state, &mut history, "south").unwrap();
+/// assert_eq!(moved.current_room, 2);
+/// let (undone, result) = parse_with_history(&moved, &mut history, "undo").unwrap();
+/// assert_eq!(undone.current_room, 1);
+/// assert!(matches!(result, ParsingResult::Undo(_)));
+/// ```
+pub fn parse_with_history(
+    state: &State,
+    history: &mut History,
+    input: &str,
+) -> NRResult<(State, ParsingResult)> {
+    let action = Action::parse(state, input);
+    match action.verb.as_ref().map(|verb| &verb.verb_function) {
+        Some(VerbFunction::Undo) => undo(state, history),
+        Some(VerbFunction::Redo) => redo(state, history),
+        _ => {
+            let (new_state, result) = parse(state, input)?;
+            history.push(state.clone());
+            Ok((new_state, result))
+        }
+    }
+}
+
+/// Goes back to the state before the last command.
+pub(crate) fn undo(state: &State, history: &mut History) -> NRResult<(State, ParsingResult)> {
+    let previous_state = history.undo(state.clone()).ok_or(NothingToUndo)?;
+    let room_text = current_room_text(&previous_state)?;
+    Ok((previous_state, ParsingResult::Undo(room_text)))
+}
+
+/// Goes forward to the state the last undone command led to.
+pub(crate) fn redo(state: &State, history: &mut History) -> NRResult<(State, ParsingResult)> {
+    let next_state = history.redo(state.clone()).ok_or(NothingToRedo)?;
+    let room_text = current_room_text(&next_state)?;
+    Ok((next_state, ParsingResult::Redo(room_text)))
+}
+
 #[cfg(test)]
 mod tests;
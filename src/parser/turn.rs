@@ -0,0 +1,410 @@
+use crate::config::directions::Directions;
+use crate::config::{Event, GameOver, State, SubjectLocation, SubjectMovement, VerbFunction};
+use crate::parser::action::Action;
+use crate::parser::interpreter::{process_event, MessageParts};
+use crate::util::{append_to_result, parse_room_text};
+use crate::NRResult;
+use crate::ParsingResult;
+use rand::Rng;
+use std::collections::{BTreeMap, VecDeque};
+
+/// Runs everything that happens in the game world after the
+/// player's command was processed, and adds anything the
+/// player should know about to the command's result.
+///
+/// `previous_state` is the state before the command was
+/// processed, and is used to find out where the player
+/// came from.
+pub(super) fn end_turn(
+    previous_state: &State,
+    state: State,
+    result: ParsingResult,
+) -> NRResult<(State, ParsingResult)> {
+    let mut new_state = state;
+    new_state.turn += 1;
+    if new_state.current_room == previous_state.current_room {
+        new_state.turns_in_room += 1;
+    } else {
+        new_state.turns_in_room = 0;
+    }
+    record_completed_events(&mut new_state);
+    let room_before_events = new_state.current_room;
+    let event_messages = run_timed_events(&mut new_state)?;
+    let mut room_messages = follow_player(previous_state, &mut new_state)?;
+    room_messages.extend(move_subjects(&mut new_state)?);
+    let daemon_messages = run_daemons(&mut new_state)?;
+    let moved_by_events = new_state.current_room != room_before_events;
+    let result = if moved_by_events {
+        describe_new_room(&new_state, result, &event_messages)?
+    } else {
+        append_to_result(
+            result,
+            MessageParts::EventText,
+            &event_messages.join("\n\n"),
+        )
+    };
+    let result = append_to_result(result, MessageParts::RoomText, &room_messages.join("\n"));
+    let result = append_to_result(
+        result,
+        MessageParts::DaemonText,
+        &daemon_messages.join("\n"),
+    );
+    Ok(end_game(new_state, result))
+}
+
+/// Describes the room a timed event or a daemon moved the
+/// player to, since the result of the command describes the
+/// room the player was in. What the command and the timed
+/// events said is kept as the event text, without the
+/// description of the room the player left.
+fn describe_new_room(
+    state: &State,
+    result: ParsingResult,
+    event_messages: &[String],
+) -> NRResult<ParsingResult> {
+    let command_text = match &result {
+        ParsingResult::EventSuccess(event_message) => event_message
+            .message_parts
+            .get(&MessageParts::EventText)
+            .cloned()
+            .unwrap_or_default(),
+        ParsingResult::Look(_) => String::new(),
+        result => result.to_string(),
+    };
+    let event_text = std::iter::once(command_text)
+        .chain(event_messages.iter().cloned())
+        .filter(|text| !text.is_empty())
+        .collect::<Vec<String>>()
+        .join("\n\n");
+    let narrative = state.get_narrative();
+    Ok(ParsingResult::EventSuccess(parse_room_text(
+        state,
+        narrative.text,
+        event_text,
+        None,
+    )?))
+}
+
+/// Ends the game when the player's health dropped to zero,
+/// or when an effect ended it this turn, turning the result
+/// into `ParsingResult::GameOver`.
+fn end_game(mut state: State, result: ParsingResult) -> (State, ParsingResult) {
+    if state.game_over.is_none() && state.player.health.is_some_and(|health| health <= 0) {
+        state.game_over = Some(GameOver {
+            won: false,
+            text: state.config.stats.death_text.clone(),
+        });
+    }
+    let game_over = match &state.game_over {
+        Some(game_over) => game_over.clone(),
+        None => return (state, result),
+    };
+    let text = match &result {
+        ParsingResult::EventSuccess(event_message) => event_message.message.clone(),
+        result => result.to_string(),
+    };
+    let text = [text, game_over.text]
+        .into_iter()
+        .filter(|text| !text.is_empty())
+        .collect::<Vec<String>>()
+        .join("\n\n");
+    let result = ParsingResult::GameOver {
+        won: game_over.won,
+        text,
+    };
+    (state, result)
+}
+
+/// Returns true for the commands the game still accepts
+/// once it's over.
+pub(super) fn allowed_after_game_over(action: &Action) -> bool {
+    matches!(
+        action.verb.as_ref().map(|verb| &verb.verb_function),
+        Some(VerbFunction::Quit)
+            | Some(VerbFunction::Restart)
+            | Some(VerbFunction::Undo)
+            | Some(VerbFunction::Redo)
+    )
+}
+
+/// Runs every daemon whose conditions are met, in order,
+/// and returns their messages.
+fn run_daemons(state: &mut State) -> NRResult<Vec<String>> {
+    let mut messages = vec![];
+    let daemons = state.config.daemons.clone();
+    for daemon in daemons {
+        if state.conditions_met(&daemon.conditions) {
+            messages.extend(state.apply_effects(&daemon.effects)?);
+        }
+    }
+    Ok(messages)
+}
+
+/// Returns false for commands that only give the player
+/// information when the game is configured so that those
+/// don't make time pass.
+pub(super) fn takes_turn(state: &State, action: &Action) -> bool {
+    if state.config.settings.informational_commands_take_turn {
+        return true;
+    }
+    !matches!(
+        action.verb.as_ref().map(|verb| &verb.verb_function),
+        Some(VerbFunction::Look)
+            | Some(VerbFunction::Help)
+            | Some(VerbFunction::Inventory)
+            | Some(VerbFunction::Score)
+    )
+}
+
+/// Remembers the turn in which events were completed so
+/// events timed after them know when to happen.
+fn record_completed_events(state: &mut State) {
+    let turn = state.turn;
+    let completed_events = state
+        .rooms
+        .iter()
+        .flat_map(|room| room.events.iter())
+        .filter(|event| event.completed)
+        .map(|event| event.id)
+        .collect::<Vec<u16>>();
+    for event_id in completed_events {
+        state.event_turns.entry(event_id).or_insert(turn);
+    }
+}
+
+fn is_timed_event_due(state: &State, event: &Event) -> bool {
+    event.is_timed()
+        && !event.completed
+        && event.at_turn.is_none_or(|turn| state.turn >= turn)
+        && event.turns_after.is_none_or(|delay| {
+            state
+                .event_turns
+                .get(&delay.event_id)
+                .is_some_and(|completed_turn| state.turn >= completed_turn + delay.turns)
+        })
+        && event
+            .required_events
+            .iter()
+            .all(|event_id| state.is_event_completed(*event_id))
+}
+
+/// Runs every timed event that is due and returns their
+/// messages. Events that are due because another timed
+/// event just happened run in the same turn.
+fn run_timed_events(state: &mut State) -> NRResult<Vec<String>> {
+    let mut messages = vec![];
+    loop {
+        let due_event = state
+            .rooms
+            .iter()
+            .flat_map(|room| room.events.iter())
+            .find(|event| is_timed_event_due(state, event))
+            .cloned();
+        let event = match due_event {
+            Some(event) => event,
+            None => return Ok(messages),
+        };
+        let subject = event.required_subject.and_then(|subject_id| {
+            state
+                .config
+                .subjects
+                .iter()
+                .find(|subject| subject.id == subject_id)
+                .cloned()
+        });
+        let (new_state, event_messages) = process_event(state, &event, &subject)?;
+        *state = new_state;
+        state.complete_event(event.id);
+        state.event_turns.insert(event.id, state.turn);
+        if let Some(destination) = event.destination {
+            state.current_room = destination;
+        }
+        let narrative_text = event.narrative.and_then(|narrative_id| {
+            state
+                .config
+                .narratives
+                .iter()
+                .find(|narrative| narrative.id == narrative_id)
+                .map(|narrative| narrative.text.clone())
+        });
+        let text = narrative_text
+            .into_iter()
+            .chain(
+                event_messages
+                    .iter()
+                    .map(|m| m.trim().to_string())
+                    .filter(|m| !m.is_empty()),
+            )
+            .collect::<Vec<String>>()
+            .join("\n");
+        if !text.is_empty() {
+            messages.push(text);
+        }
+    }
+}
+
+/// Subjects with a `Follow` behavior start following the player
+/// when the player leaves the room they were in together, and
+/// every subject following the player lets them know it came along.
+fn follow_player(previous_state: &State, state: &mut State) -> NRResult<Vec<String>> {
+    let previous_room = previous_state.current_room;
+    if previous_room == state.current_room {
+        return Ok(vec![]);
+    }
+    let new_followers = state
+        .config
+        .subjects
+        .iter()
+        .filter(|subject| {
+            subject.behavior.as_ref().is_some_and(|behavior| {
+                behavior.movement == SubjectMovement::Follow
+                    && behavior
+                        .after_event
+                        .is_none_or(|event_id| state.is_event_completed(event_id))
+            })
+        })
+        .filter(|subject| {
+            state.subject_location(subject.id) == Some(SubjectLocation::Room(previous_room))
+        })
+        .map(|subject| subject.id)
+        .collect::<Vec<u16>>();
+    for subject_id in new_followers {
+        state.set_subject_location(subject_id, SubjectLocation::FollowingPlayer)?;
+    }
+    Ok(state
+        .followers
+        .iter()
+        .map(|subject| format!("{} follows you.", subject.name))
+        .collect())
+}
+
+/// Moves the subjects that patrol or wander around the game
+/// and returns the messages for subjects arriving in or
+/// leaving the player's room.
+fn move_subjects(state: &mut State) -> NRResult<Vec<String>> {
+    let mut messages = vec![];
+    let subjects = state.config.subjects.clone();
+    for subject in subjects {
+        let behavior = match &subject.behavior {
+            Some(behavior) => behavior,
+            None => continue,
+        };
+        if let Some(event_id) = behavior.after_event {
+            if !state.is_event_completed(event_id) {
+                continue;
+            }
+        }
+        if behavior.every == 0 || !state.turn.is_multiple_of(behavior.every) {
+            continue;
+        }
+        let from = match state.subject_location(subject.id) {
+            Some(SubjectLocation::Room(room_id)) => room_id,
+            _ => continue,
+        };
+        let to = match behavior.movement {
+            SubjectMovement::Follow => None,
+            SubjectMovement::Patrol => next_patrol_room(state, &behavior.rooms, from),
+            SubjectMovement::Wander => random_adjacent_room(state, &behavior.rooms, from),
+        };
+        if let Some(to) = to {
+            if to == from {
+                continue;
+            }
+            state.move_subject(subject.id, to)?;
+            let name = state
+                .subjects_in_room(to)
+                .iter()
+                .find(|s| s.id == subject.id)
+                .map_or(subject.name.clone(), |s| s.name.clone());
+            if from == state.current_room {
+                messages.push(match direction_between(state, from, to) {
+                    Some(direction) => format!("{} leaves to the {}.", name, direction),
+                    None => format!("{} leaves.", name),
+                });
+            } else if to == state.current_room {
+                messages.push(match direction_between(state, to, from) {
+                    Some(direction) => format!("{} arrives from the {}.", name, direction),
+                    None => format!("{} arrives.", name),
+                });
+            }
+        }
+    }
+    Ok(messages)
+}
+
+/// Returns the next room of the route. A subject that isn't on
+/// its route walks one room closer to it, or stays put when no
+/// exits lead there.
+fn next_patrol_room(state: &State, route: &[u16], current_room: u16) -> Option<u16> {
+    match route.iter().position(|room_id| *room_id == current_room) {
+        Some(index) => route.get((index + 1) % route.len()).copied(),
+        None => step_towards(state, route, current_room),
+    }
+}
+
+/// Returns the first room on the shortest way through the exits
+/// from `from` to any of the `targets`.
+fn step_towards(state: &State, targets: &[u16], from: u16) -> Option<u16> {
+    let mut first_steps = BTreeMap::from([(from, from)]);
+    let mut queue = VecDeque::from([from]);
+    while let Some(room_id) = queue.pop_front() {
+        let room = match state.rooms.iter().find(|room| room.id == room_id) {
+            Some(room) => room,
+            None => continue,
+        };
+        for exit in &room.exits {
+            if first_steps.contains_key(&exit.room_id) {
+                continue;
+            }
+            let first_step = if room_id == from {
+                exit.room_id
+            } else {
+                first_steps[&room_id]
+            };
+            if targets.contains(&exit.room_id) {
+                return Some(first_step);
+            }
+            first_steps.insert(exit.room_id, first_step);
+            queue.push_back(exit.room_id);
+        }
+    }
+    None
+}
+
+fn random_adjacent_room(
+    state: &mut State,
+    allowed_rooms: &[u16],
+    current_room: u16,
+) -> Option<u16> {
+    let exits = state
+        .rooms
+        .iter()
+        .find(|room| room.id == current_room)?
+        .exits
+        .iter()
+        .map(|exit| exit.room_id)
+        .filter(|room_id| allowed_rooms.is_empty() || allowed_rooms.contains(room_id))
+        .collect::<Vec<u16>>();
+    if exits.is_empty() {
+        None
+    } else {
+        let index = state.rng.gen_range(0..exits.len());
+        Some(exits[index])
+    }
+}
+
+/// The direction of the exit in room `from` that leads to room `to`.
+fn direction_between(state: &State, from: u16, to: u16) -> Option<Directions> {
+    state
+        .rooms
+        .iter()
+        .find(|room| room.id == from)?
+        .exits
+        .iter()
+        .find(|exit| exit.room_id == to)
+        .map(|exit| exit.direction.clone())
+}
+
+#[cfg(test)]
+#[path = "turn_tests.rs"]
+mod turn_tests;
@@ -0,0 +1,494 @@
+use super::*;
+use crate::config::rooms::Exits;
+use crate::config::{
+    Award, Condition, Config, Daemon, Effect, Event, SubjectBehavior, TurnsAfterEvent, Verb,
+    VerbFunction,
+};
+use crate::parser::parse;
+#[cfg(test)]
+use pretty_assertions::assert_eq;
+
+fn state_with_behavior(subject_id: u16, behavior: SubjectBehavior) -> State {
+    let mut config = Config::from_path("fixtures/");
+    let subject = config
+        .subjects
+        .iter_mut()
+        .find(|subject| subject.id == subject_id)
+        .unwrap();
+    subject.behavior = Some(behavior);
+    State::init(config)
+}
+
+#[test]
+fn it_walks_patrolling_subjects_back_to_their_route() {
+    let mut state = State::init(Config::from_path("fixtures/"));
+    let mut far_room = state.rooms[1].clone();
+    far_room.id = 3;
+    far_room.exits = vec![Exits {
+        room_id: 2,
+        direction: Directions::North,
+    }];
+    state.rooms[1].exits.push(Exits {
+        room_id: 3,
+        direction: Directions::South,
+    });
+    state.rooms.push(far_room);
+    // room 3 isn't next to room 1, so the subject walks through room 2
+    assert_eq!(next_patrol_room(&state, &[3], 1), Some(2));
+    assert_eq!(next_patrol_room(&state, &[3], 2), Some(3));
+    assert_eq!(next_patrol_room(&state, &[3, 1], 3), Some(1));
+    assert_eq!(
+        next_patrol_room(&state, &[42], 1),
+        None,
+        "a subject that can't reach its route should stay put"
+    );
+}
+
+#[test]
+fn it_counts_turns() {
+    let config = Config::from_path("fixtures/");
+    let state = State::init(config);
+    let (state, _) = parse(&state, "look").unwrap();
+    let (state, _) = parse(&state, "inventory").unwrap();
+    assert_eq!(state.turn, 2);
+    let (state, _) = parse(&state, "quit").unwrap();
+    assert_eq!(state.turn, 2);
+}
+
+#[test]
+fn it_moves_patrolling_subjects() {
+    let state = state_with_behavior(
+        2,
+        SubjectBehavior {
+            movement: SubjectMovement::Patrol,
+            rooms: vec![2, 1],
+            every: 1,
+            after_event: None,
+        },
+    );
+    let (state, result) = parse(&state, "look").unwrap();
+    assert_eq!(state.subject_location(2), Some(SubjectLocation::Room(1)));
+    assert_eq!(
+        result,
+        ParsingResult::Look(
+            "first room\n\nHere you see: \nan item1\nan item2\nsubject1\n\nsubject2 arrives from the south."
+                .to_string()
+        )
+    );
+    let (state, result) = parse(&state, "look").unwrap();
+    assert_eq!(state.subject_location(2), Some(SubjectLocation::Room(2)));
+    assert_eq!(
+        result,
+        ParsingResult::Look(
+            "first room\n\nHere you see: \nan item1\nan item2\nsubject1\nsubject2\n\nsubject2 leaves to the south."
+                .to_string()
+        )
+    );
+}
+
+#[test]
+fn it_only_moves_subjects_every_few_turns() {
+    let state = state_with_behavior(
+        2,
+        SubjectBehavior {
+            movement: SubjectMovement::Wander,
+            rooms: vec![],
+            every: 2,
+            after_event: None,
+        },
+    );
+    let (state, _) = parse(&state, "look").unwrap();
+    assert_eq!(state.subject_location(2), Some(SubjectLocation::Room(2)));
+    let (state, _) = parse(&state, "look").unwrap();
+    assert_eq!(state.subject_location(2), Some(SubjectLocation::Room(1)));
+}
+
+#[test]
+fn it_makes_subjects_follow_the_player() {
+    let state = state_with_behavior(
+        1,
+        SubjectBehavior {
+            movement: SubjectMovement::Follow,
+            rooms: vec![],
+            every: 1,
+            after_event: None,
+        },
+    );
+    let (state, result) = parse(&state, "south").unwrap();
+    assert_eq!(
+        state.subject_location(1),
+        Some(SubjectLocation::FollowingPlayer)
+    );
+    match result {
+        ParsingResult::EventSuccess(event_message) => {
+            assert!(event_message.message.contains("subject1 follows you."));
+            assert!(event_message.message_parts[&MessageParts::RoomText]
+                .ends_with("subject1 follows you."));
+        }
+        _ => panic!("moving should return an event message"),
+    }
+}
+
+#[test]
+fn it_waits_for_the_event_before_following() {
+    let state = state_with_behavior(
+        1,
+        SubjectBehavior {
+            movement: SubjectMovement::Follow,
+            rooms: vec![],
+            every: 1,
+            after_event: Some(1),
+        },
+    );
+    let (state, _) = parse(&state, "south").unwrap();
+    assert_eq!(state.subject_location(1), Some(SubjectLocation::Room(1)));
+}
+
+fn timed_event(id: u16, at_turn: Option<u32>, turns_after: Option<TurnsAfterEvent>) -> Event {
+    Event {
+        id,
+        location: 2,
+        name: "timed event".to_string(),
+        description: "an event that happens on its own".to_string(),
+        destination: None,
+        narrative: Some(4),
+        required_verb: None,
+        required_subject: None,
+        required_item: None,
+        completed: false,
+        add_item: None,
+        remove_old_narrative: false,
+        narrative_after: None,
+        narrative_after_room: None,
+        narrative_changes: vec![],
+        remove_item: None,
+        required_events: vec![],
+        add_subject: None,
+        remove_subject: false,
+        move_subject_to_location: None,
+        move_subjects: vec![],
+        at_turn,
+        turns_after,
+        effects: vec![],
+    }
+}
+
+#[test]
+fn it_runs_events_at_a_given_turn() {
+    let mut config = Config::from_path("fixtures/");
+    config.events.push(timed_event(10, Some(2), None));
+    let state = State::init(config);
+    let (state, result) = parse(&state, "look").unwrap();
+    assert!(!state.is_event_completed(10));
+    assert_eq!(
+        result,
+        ParsingResult::Look(
+            "first room\n\nHere you see: \nan item1\nan item2\nsubject1".to_string()
+        )
+    );
+    let (state, result) = parse(&state, "look").unwrap();
+    assert!(state.is_event_completed(10));
+    assert_eq!(
+        result,
+        ParsingResult::Look(
+            "first room\n\nHere you see: \nan item1\nan item2\nsubject1\n\nthis narrative should be returned along with the text of room 1."
+                .to_string()
+        )
+    );
+    let (_, result) = parse(&state, "look").unwrap();
+    assert_eq!(
+        result,
+        ParsingResult::Look(
+            "first room\n\nHere you see: \nan item1\nan item2\nsubject1".to_string()
+        )
+    );
+}
+
+#[test]
+fn it_runs_events_some_turns_after_another_event() {
+    let mut config = Config::from_path("fixtures/");
+    config.events.push(timed_event(
+        10,
+        None,
+        Some(TurnsAfterEvent {
+            event_id: 1,
+            turns: 2,
+        }),
+    ));
+    let mut state = State::init(config);
+    let (new_state, _) = parse(&state, "look").unwrap();
+    assert!(!new_state.is_event_completed(10));
+    state.complete_event(1);
+    let (state, _) = parse(&state, "look").unwrap();
+    assert_eq!(state.event_turns.get(&1), Some(&1));
+    let (state, _) = parse(&state, "look").unwrap();
+    assert!(!state.is_event_completed(10));
+    let (state, result) = parse(&state, "south").unwrap();
+    assert!(state.is_event_completed(10));
+    match result {
+        ParsingResult::EventSuccess(event_message) => {
+            assert!(event_message.message_parts[&MessageParts::EventText]
+                .ends_with("this narrative should be returned along with the text of room 1."));
+        }
+        _ => panic!("moving should return an event message"),
+    }
+}
+
+#[test]
+fn it_describes_the_room_a_timed_event_moves_the_player_to() {
+    let mut config = Config::from_path("fixtures/");
+    config.events.push(Event {
+        destination: Some(2),
+        ..timed_event(10, Some(1), None)
+    });
+    let state = State::init(config);
+    let (state, result) = parse(&state, "look").unwrap();
+    assert_eq!(state.current_room, 2);
+    match result {
+        ParsingResult::EventSuccess(event_message) => {
+            assert!(event_message.message_parts[&MessageParts::RoomText]
+                .starts_with("this is a templated which exists in the game item3."));
+            let event_text = &event_message.message_parts[&MessageParts::EventText];
+            assert_eq!(
+                event_text,
+                "this narrative should be returned along with the text of room 1."
+            );
+        }
+        result => panic!("expected the new room, got {:?}", result),
+    }
+}
+
+#[test]
+fn it_can_let_informational_commands_skip_the_turn() {
+    let mut config = Config::from_path("fixtures/");
+    config.settings.informational_commands_take_turn = false;
+    let state = State::init(config);
+    let (state, _) = parse(&state, "look").unwrap();
+    let (state, _) = parse(&state, "inventory").unwrap();
+    let (state, _) = parse(&state, "help").unwrap();
+    assert_eq!(state.turn, 0);
+    let (state, _) = parse(&state, "south").unwrap();
+    assert_eq!(state.turn, 1);
+}
+
+#[test]
+fn it_runs_daemons_every_turn() {
+    let mut config = Config::from_path("fixtures/");
+    config.variables.insert("fuel".to_string(), 2);
+    config.daemons = vec![
+        Daemon {
+            id: 1,
+            name: "lamp".to_string(),
+            conditions: vec![Condition::Variable {
+                name: "fuel".to_string(),
+                at_least: Some(1),
+                at_most: None,
+            }],
+            effects: vec![Effect::ChangeVariable {
+                name: "fuel".to_string(),
+                by: -1,
+            }],
+        },
+        Daemon {
+            id: 2,
+            name: "lamp goes out".to_string(),
+            conditions: vec![Condition::Variable {
+                name: "fuel".to_string(),
+                at_least: None,
+                at_most: Some(0),
+            }],
+            effects: vec![Effect::Message {
+                text: "Your lamp goes out.".to_string(),
+            }],
+        },
+    ];
+    let state = State::init(config);
+    let (state, result) = parse(&state, "look").unwrap();
+    assert_eq!(state.variable("fuel"), 1);
+    assert_eq!(
+        result,
+        ParsingResult::Look(
+            "first room\n\nHere you see: \nan item1\nan item2\nsubject1".to_string()
+        )
+    );
+    let (state, result) = parse(&state, "south").unwrap();
+    assert_eq!(state.variable("fuel"), 0);
+    match result {
+        ParsingResult::EventSuccess(event_message) => {
+            assert_eq!(
+                event_message.message_parts[&MessageParts::DaemonText],
+                "Your lamp goes out."
+            );
+            assert!(event_message
+                .message
+                .contains("\n\nYour lamp goes out.\n\n"));
+        }
+        _ => panic!("moving should return an event message"),
+    }
+}
+
+#[test]
+fn it_counts_turns_in_the_same_room() {
+    let mut config = Config::from_path("fixtures/");
+    config.daemons = vec![Daemon {
+        id: 1,
+        name: "guard".to_string(),
+        conditions: vec![
+            Condition::PlayerInRoom { room_id: 2 },
+            Condition::TurnsInRoom { at_least: 2 },
+        ],
+        effects: vec![
+            Effect::Message {
+                text: "The guard throws you out.".to_string(),
+            },
+            Effect::MovePlayer { room_id: 1 },
+        ],
+    }];
+    let state = State::init(config);
+    let (state, _) = parse(&state, "south").unwrap();
+    assert_eq!(state.turns_in_room, 0);
+    let (state, _) = parse(&state, "look").unwrap();
+    assert_eq!(state.current_room, 2);
+    let (state, result) = parse(&state, "look").unwrap();
+    assert_eq!(state.current_room, 1);
+    match result {
+        ParsingResult::EventSuccess(event_message) => {
+            assert_eq!(event_message.message_parts[&MessageParts::RoomText], "text");
+            assert_eq!(
+                event_message.message_parts[&MessageParts::Exits],
+                "Exits:\nto the south you see second room"
+            );
+            assert_eq!(
+                event_message.message_parts[&MessageParts::DaemonText],
+                "The guard throws you out."
+            );
+            assert!(!event_message.message.contains("item3"));
+        }
+        result => panic!("expected the room the guard threw you to, got {:?}", result),
+    }
+}
+
+#[test]
+fn it_ends_the_game_when_health_runs_out() {
+    let mut config = Config::from_path("fixtures/");
+    config.stats.health = Some(2);
+    config.daemons.push(Daemon {
+        id: 1,
+        name: "poison".to_string(),
+        conditions: vec![],
+        effects: vec![
+            Effect::ChangeHealth { by: -1 },
+            Effect::Message {
+                text: "You feel weaker.".to_string(),
+            },
+        ],
+    });
+    let state = State::init(config);
+    let (state, result) = parse(&state, "look").unwrap();
+    assert_eq!(state.player.health, Some(1));
+    assert!(matches!(result, ParsingResult::Look(_)));
+    let (state, result) = parse(&state, "look").unwrap();
+    assert_eq!(
+        result,
+        ParsingResult::GameOver {
+            won: false,
+            text: "first room\n\nHere you see: \nan item1\nan item2\nsubject1\n\nYou feel weaker.\n\nYou have died.".to_string()
+        }
+    );
+    assert_eq!(
+        parse(&state, "look").unwrap_err().to_string(),
+        "The game is over."
+    );
+    assert_eq!(parse(&state, "quit").unwrap().1, ParsingResult::Quit);
+}
+
+#[test]
+fn it_ends_the_game_with_an_event_and_keeps_score() {
+    let mut config = Config::from_path("fixtures/");
+    let mut ending = timed_event(10, Some(2), None);
+    ending.location = 1;
+    ending.narrative = None;
+    ending.effects = vec![
+        Effect::AwardPoints {
+            reason: "escaping".to_string(),
+            points: 10,
+        },
+        Effect::AwardPoints {
+            reason: "leaving the others behind".to_string(),
+            points: -5,
+        },
+        Effect::EndGame {
+            won: true,
+            text: "You won!".to_string(),
+        },
+    ];
+    config.events.push(ending);
+    config.allowed_verbs.push(Verb {
+        id: 12,
+        names: vec!["score".to_string()],
+        verb_function: VerbFunction::Score,
+    });
+    let state = State::init(config);
+    let mut earned = state.clone();
+    earned.player.awards.push(Award {
+        reason: "escaping".to_string(),
+        points: 10,
+    });
+    match parse(&earned, "score").unwrap() {
+        (_, ParsingResult::Score(text)) => assert!(text.contains("\n  [x] escaping: 10\n")),
+        (_, result) => panic!("expected Score, got {:?}", result),
+    }
+    let (state, result) = parse(&state, "score").unwrap();
+    assert_eq!(
+        result,
+        ParsingResult::Score(
+            "You have scored 0 out of a possible 10 points.\n  [ ] escaping: 10\n  [ ] leaving the others behind: -5"
+                .to_string()
+        )
+    );
+    let (state, result) = parse(&state, "look").unwrap();
+    match result {
+        ParsingResult::GameOver { won, text } => {
+            assert!(won);
+            assert!(text.ends_with(
+                "Your score has gone up by 10 points.\nYour score has gone down by 5 points.\n\nYou won!"
+            ));
+        }
+        result => panic!("expected GameOver, got {:?}", result),
+    }
+    assert_eq!(state.player.score(), 5);
+    assert!(parse(&state, "score").is_err());
+}
+
+#[test]
+fn it_restarts_the_game() {
+    let mut config = Config::from_path("fixtures/");
+    config.stats.health = Some(1);
+    config.daemons.push(Daemon {
+        id: 1,
+        name: "trap".to_string(),
+        conditions: vec![Condition::PlayerInRoom { room_id: 2 }],
+        effects: vec![Effect::ChangeHealth { by: -1 }],
+    });
+    config.allowed_verbs.push(Verb {
+        id: 12,
+        names: vec!["restart".to_string()],
+        verb_function: VerbFunction::Restart,
+    });
+    let initial_state = State::init_with_seed(config, 7);
+    let (state, result) = parse(&initial_state, "south").unwrap();
+    assert!(matches!(result, ParsingResult::GameOver { won: false, .. }));
+    let (state, result) = parse(&state, "restart").unwrap();
+    assert_eq!(state, initial_state);
+    match result {
+        ParsingResult::Restart(room_text) => {
+            assert_eq!(
+                room_text.message,
+                crate::util::current_room_text(&initial_state)
+                    .unwrap()
+                    .message
+            );
+        }
+        result => panic!("expected Restart, got {:?}", result),
+    }
+}
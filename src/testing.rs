@@ -0,0 +1,393 @@
+//! Scripted walkthroughs that play a game from start to finish
+//! and check the game after each command, so game authors can
+//! test their games the same way they test code.
+//!
+//! Walkthroughs can be written in Rust, or in YAML or JSON
+//! scripts like this one:
+//!
+//! ```yaml
+//! game: ../../fixtures/
+//! seed: 1
+//! steps:
+//!   - input: take item2
+//!     output_contains: [You now have a item2]
+//!     inventory: [item2]
+//!   - input: south
+//!     room: 2
+//!   - input: fly
+//!     error: true
+//! ```
+//!
+//! The `game` is a folder with the YAML files for the game, or
+//! a JSON, YAML or TOML file with the game data, relative to the
+//! script.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::config::{Config, ConfigFormat, State};
+use crate::parser::errors::NoWalkthroughGame;
+use crate::{NRResult, NightRunner, NightRunnerBuilder};
+
+/// A list of commands to play, and what the game should
+/// look like after each of them.
+///
+/// # Examples:
+/// ```rust
+/// use nightrunner_lib::config::Config;
+/// use nightrunner_lib::testing::{Step, Walkthrough};
+/// let walkthrough = Walkthrough::new()
+///     .step(Step::input("take item2").expect_inventory(&["item2"]))
+///     .step(Step::input("south").expect_room(2))
+///     .step(Step::input("talk to subject2").expect_events(&[5]));
+/// walkthrough.assert_passes(Config::from_path("fixtures/"));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case", default, deny_unknown_fields)]
+pub struct Walkthrough {
+    /// The game to play: a folder with the YAML files for
+    /// the game, or a JSON, YAML or TOML file with the
+    /// game data, as read by `Config::from_file`. Only
+    /// needed when running the walkthrough with
+    /// `Walkthrough::run_game`.
+    pub game: Option<String>,
+    /// The seed the game is started with. Default is `0`.
+    pub seed: u64,
+    /// The commands to play, in order.
+    pub steps: Vec<Step>,
+}
+
+/// A command in a walkthrough, and what the game should look
+/// like after it. Anything left out isn't checked.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case", default, deny_unknown_fields)]
+pub struct Step {
+    /// What the player types.
+    pub input: String,
+    /// Text the game should show for the command. When the
+    /// command fails this is checked against the error.
+    pub output_contains: Vec<String>,
+    /// Whether the command should fail.
+    pub error: Option<bool>,
+    /// The id of the room the player should be in.
+    pub room: Option<u16>,
+    /// The names of every item the player should carry,
+    /// in any order.
+    pub inventory: Option<Vec<String>>,
+    /// Ids of events that should be completed.
+    pub completed_events: Vec<u16>,
+    /// Values game variables should have.
+    pub variables: BTreeMap<String, i32>,
+}
+
+/// The parts of the game a walkthrough checks, taken after
+/// a step.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct Snapshot {
+    /// The id of the room the player is in.
+    pub room: u16,
+    /// The names of the items the player carries, sorted.
+    pub inventory: Vec<String>,
+    /// Ids of the completed events, sorted.
+    pub completed_events: Vec<u16>,
+    /// The values of the game variables.
+    pub variables: BTreeMap<String, i32>,
+}
+
+/// Returned when a step of a walkthrough doesn't go as
+/// expected.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WalkthroughFailure {
+    /// The number of the failed step, starting at 1.
+    pub step: usize,
+    /// What the player typed in the failed step.
+    pub input: String,
+    /// The text the game showed for the failed step.
+    pub output: String,
+    /// Each expectation of the step that didn't hold.
+    pub mismatches: Vec<String>,
+    /// What changed in the game during the failed step.
+    pub diff: Vec<String>,
+}
+
+impl fmt::Display for WalkthroughFailure {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "step {} failed: > {}", self.step, self.input)?;
+        for mismatch in &self.mismatches {
+            writeln!(f, "  {}", mismatch)?;
+        }
+        writeln!(f, "output:")?;
+        for line in self.output.lines() {
+            writeln!(f, "  {}", line)?;
+        }
+        if self.diff.is_empty() {
+            write!(f, "the step didn't change the game")
+        } else {
+            write!(f, "changes during the step:")?;
+            for change in &self.diff {
+                write!(f, "\n  {}", change)?;
+            }
+            Ok(())
+        }
+    }
+}
+
+impl std::error::Error for WalkthroughFailure {}
+
+impl Walkthrough {
+    /// Creates an empty walkthrough.
+    pub fn new() -> Walkthrough {
+        Walkthrough::default()
+    }
+    /// Sets the seed the game is started with.
+    pub fn with_seed(mut self, seed: u64) -> Walkthrough {
+        self.seed = seed;
+        self
+    }
+    /// Adds a step to the walkthrough.
+    pub fn step(mut self, step: Step) -> Walkthrough {
+        self.steps.push(step);
+        self
+    }
+    /// Reads a walkthrough from a YAML script.
+    pub fn from_yaml(data: &str) -> NRResult<Walkthrough> {
+        Ok(serde_yaml::from_str(data)?)
+    }
+    /// Reads a walkthrough from a JSON script.
+    pub fn from_json(data: &str) -> NRResult<Walkthrough> {
+        Ok(serde_json::from_str(data)?)
+    }
+    /// Reads a walkthrough from a script file, as JSON when
+    /// the file ends with `.json` and as YAML otherwise. The
+    /// path to the game is made relative to the script.
+    pub fn from_file(path: &str) -> NRResult<Walkthrough> {
+        let data = std::fs::read_to_string(path)?;
+        let mut walkthrough = if path.ends_with(".json") {
+            Walkthrough::from_json(&data)?
+        } else {
+            Walkthrough::from_yaml(&data)?
+        };
+        if let (Some(game), Some(folder)) = (&walkthrough.game, Path::new(path).parent()) {
+            let mut game_path = folder.join(game).to_string_lossy().to_string();
+            if game.ends_with('/') && !game_path.ends_with('/') {
+                game_path.push('/');
+            }
+            walkthrough.game = Some(game_path);
+        }
+        Ok(walkthrough)
+    }
+    /// Loads the game set in `game`.
+    pub fn load_game(&self) -> NRResult<Config> {
+        let game = self.game.as_ref().ok_or(NoWalkthroughGame)?;
+        if ConfigFormat::from_path(game).is_some() {
+            Config::from_file(game)
+        } else {
+            Config::try_from_path(game)
+        }
+    }
+    /// Plays the walkthrough on the game set in `game`.
+    pub fn run_game(&self) -> NRResult<()> {
+        Ok(self.run(self.load_game()?)?)
+    }
+    /// Plays the walkthrough on a game, stopping at the first
+    /// step that doesn't go as expected.
+    pub fn run(&self, config: Config) -> Result<(), WalkthroughFailure> {
+        let mut night_runner = NightRunnerBuilder::new()
+            .with_config(config)
+            .with_seed(self.seed)
+            .build();
+        for (index, step) in self.steps.iter().enumerate() {
+            let before = Snapshot::of(&night_runner.state);
+            let (output, failed) = play(&mut night_runner, &step.input);
+            let after = Snapshot::of(&night_runner.state);
+            let mismatches = step.mismatches(&output, failed, &after);
+            if !mismatches.is_empty() {
+                return Err(WalkthroughFailure {
+                    step: index + 1,
+                    input: step.input.clone(),
+                    output,
+                    mismatches,
+                    diff: before.diff(&after),
+                });
+            }
+        }
+        Ok(())
+    }
+    /// Plays the walkthrough on a game, and panics with the
+    /// failure report if a step doesn't go as expected.
+    pub fn assert_passes(&self, config: Config) {
+        if let Err(failure) = self.run(config) {
+            panic!("{}", failure);
+        }
+    }
+}
+
+/// Runs a command and returns the text shown to the player,
+/// and whether the command failed.
+fn play(night_runner: &mut NightRunner, input: &str) -> (String, bool) {
+    let failed = night_runner.parse_input(input).is_err();
+    let output = night_runner
+        .transcript()
+        .entries
+        .last()
+        .map(|entry| entry.outcome.text())
+        .unwrap_or_default();
+    (output, failed)
+}
+
+impl Step {
+    /// Creates a step that types the input and checks nothing.
+    pub fn input(input: &str) -> Step {
+        Step {
+            input: input.to_string(),
+            ..Default::default()
+        }
+    }
+    /// Checks that the game shows the text.
+    pub fn expect_output(mut self, text: &str) -> Step {
+        self.output_contains.push(text.to_string());
+        self
+    }
+    /// Checks that the command fails.
+    pub fn expect_error(mut self) -> Step {
+        self.error = Some(true);
+        self
+    }
+    /// Checks that the player is in the room.
+    pub fn expect_room(mut self, room_id: u16) -> Step {
+        self.room = Some(room_id);
+        self
+    }
+    /// Checks that the player carries exactly these items.
+    pub fn expect_inventory(mut self, item_names: &[&str]) -> Step {
+        self.inventory = Some(item_names.iter().map(|name| name.to_string()).collect());
+        self
+    }
+    /// Checks that the events are completed.
+    pub fn expect_events(mut self, event_ids: &[u16]) -> Step {
+        self.completed_events.extend(event_ids);
+        self
+    }
+    /// Checks the value of a game variable.
+    pub fn expect_variable(mut self, name: &str, value: i32) -> Step {
+        self.variables.insert(name.to_string(), value);
+        self
+    }
+    fn mismatches(&self, output: &str, failed: bool, snapshot: &Snapshot) -> Vec<String> {
+        let mut mismatches = vec![];
+        if let Some(error) = self.error {
+            if error != failed {
+                let expected = if error { "fail" } else { "succeed" };
+                mismatches.push(format!("expected the command to {}", expected));
+            }
+        }
+        for text in &self.output_contains {
+            if !output.contains(text.as_str()) {
+                mismatches.push(format!("expected the output to contain {:?}", text));
+            }
+        }
+        if let Some(room) = self.room {
+            if room != snapshot.room {
+                mismatches.push(format!("room: expected {}, found {}", room, snapshot.room));
+            }
+        }
+        if let Some(inventory) = &self.inventory {
+            let mut expected = inventory.clone();
+            expected.sort();
+            if expected != snapshot.inventory {
+                mismatches.push(format!(
+                    "inventory: expected {:?}, found {:?}",
+                    expected, snapshot.inventory
+                ));
+            }
+        }
+        for event_id in &self.completed_events {
+            if !snapshot.completed_events.contains(event_id) {
+                mismatches.push(format!("event {} isn't completed", event_id));
+            }
+        }
+        for (name, value) in &self.variables {
+            let found = snapshot.variables.get(name).copied().unwrap_or(0);
+            if *value != found {
+                mismatches.push(format!(
+                    "variable {}: expected {}, found {}",
+                    name, value, found
+                ));
+            }
+        }
+        mismatches
+    }
+}
+
+impl Snapshot {
+    /// Takes the parts of the game a walkthrough checks.
+    pub fn of(state: &State) -> Snapshot {
+        let mut inventory: Vec<String> = state
+            .player
+            .inventory
+            .items
+            .iter()
+            .map(|item| item.name.clone())
+            .collect();
+        inventory.sort();
+        let mut completed_events: Vec<u16> = state
+            .rooms
+            .iter()
+            .flat_map(|room| room.events.iter())
+            .filter(|event| event.completed)
+            .map(|event| event.id)
+            .collect();
+        completed_events.sort_unstable();
+        completed_events.dedup();
+        Snapshot {
+            room: state.current_room,
+            inventory,
+            completed_events,
+            variables: state.variables.clone(),
+        }
+    }
+    /// Describes what changed from this snapshot to another,
+    /// one change per line.
+    pub fn diff(&self, other: &Snapshot) -> Vec<String> {
+        let mut changes = vec![];
+        if self.room != other.room {
+            changes.push(format!("room: {} -> {}", self.room, other.room));
+        }
+        for name in &other.inventory {
+            if !self.inventory.contains(name) {
+                changes.push(format!("inventory: + {}", name));
+            }
+        }
+        for name in &self.inventory {
+            if !other.inventory.contains(name) {
+                changes.push(format!("inventory: - {}", name));
+            }
+        }
+        for event_id in &other.completed_events {
+            if !self.completed_events.contains(event_id) {
+                changes.push(format!("completed events: + {}", event_id));
+            }
+        }
+        let names: BTreeSet<&String> = self
+            .variables
+            .keys()
+            .chain(other.variables.keys())
+            .collect();
+        for name in names {
+            let before = self.variables.get(name).copied().unwrap_or(0);
+            let after = other.variables.get(name).copied().unwrap_or(0);
+            if before != after {
+                changes.push(format!("variable {}: {} -> {}", name, before, after));
+            }
+        }
+        changes
+    }
+}
+
+#[cfg(test)]
+#[path = "testing_tests.rs"]
+mod testing_tests;
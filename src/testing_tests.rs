@@ -0,0 +1,99 @@
+use super::*;
+#[cfg(test)]
+use pretty_assertions::assert_eq;
+
+fn fixtures() -> Config {
+    Config::from_path("fixtures/")
+}
+
+#[test]
+fn it_passes_when_every_step_goes_as_expected() {
+    let walkthrough = Walkthrough::new()
+        .with_seed(1)
+        .step(
+            Step::input("take item2")
+                .expect_output("item2")
+                .expect_inventory(&["item2"]),
+        )
+        .step(Step::input("south").expect_room(2))
+        .step(Step::input("fly").expect_error())
+        .step(Step::input("talk to subject2").expect_events(&[5]));
+    assert_eq!(walkthrough.run(fixtures()), Ok(()));
+}
+
+#[test]
+fn it_reports_the_failing_step_with_a_diff() {
+    let walkthrough = Walkthrough::new()
+        .step(Step::input("look"))
+        .step(
+            Step::input("take item2")
+                .expect_room(2)
+                .expect_inventory(&[])
+                .expect_variable("meals", 1),
+        )
+        .step(Step::input("south"));
+    let failure = walkthrough.run(fixtures()).unwrap_err();
+    assert_eq!(failure.step, 2);
+    assert_eq!(failure.input, "take item2");
+    assert_eq!(
+        failure.mismatches,
+        vec![
+            "room: expected 2, found 1".to_string(),
+            "inventory: expected [], found [\"item2\"]".to_string(),
+            "variable meals: expected 1, found 0".to_string(),
+        ]
+    );
+    assert_eq!(failure.diff, vec!["inventory: + item2".to_string()]);
+    assert!(failure
+        .to_string()
+        .starts_with("step 2 failed: > take item2\n  room: expected 2, found 1\n"));
+}
+
+#[test]
+fn it_checks_whether_commands_fail() {
+    let walkthrough = Walkthrough::new().step(Step::input("south").expect_error());
+    let failure = walkthrough.run(fixtures()).unwrap_err();
+    assert_eq!(
+        failure.mismatches,
+        vec!["expected the command to fail".to_string()]
+    );
+    assert_eq!(failure.diff, vec!["room: 1 -> 2".to_string()]);
+}
+
+#[test]
+fn it_reads_yaml_and_json_scripts() {
+    let yaml = r#"
+seed: 3
+steps:
+  - input: south
+    room: 2
+    variables:
+      meals: 0
+"#;
+    let walkthrough = Walkthrough::from_yaml(yaml).unwrap();
+    assert_eq!(
+        walkthrough,
+        Walkthrough::new().with_seed(3).step(Step {
+            variables: BTreeMap::from([("meals".to_string(), 0)]),
+            ..Step::input("south").expect_room(2)
+        })
+    );
+    let json = serde_json::to_string(&walkthrough).unwrap();
+    assert_eq!(Walkthrough::from_json(&json).unwrap(), walkthrough);
+    assert!(Walkthrough::from_yaml("steps:\n  - input: look\n    rooom: 1\n").is_err());
+}
+
+#[test]
+fn it_needs_a_game_to_run_on_its_own() {
+    let error = Walkthrough::new().run_game().unwrap_err();
+    assert_eq!(error.to_string(), NoWalkthroughGame.to_string());
+}
+
+#[test]
+fn it_returns_an_error_for_missing_games() {
+    let walkthrough = Walkthrough {
+        game: Some("no/such/game".to_string()),
+        ..Walkthrough::new()
+    };
+    assert!(walkthrough.run_game().is_err());
+}
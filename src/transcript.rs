@@ -0,0 +1,147 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{NRResult, ParsingResult};
+
+/// A record of everything that happened in a game session,
+/// which can be exported to share it, for example in a bug
+/// report, and replayed to get back to the same state.
+///
+/// # Examples:
+/// ```rust
+/// use nightrunner_lib::NightRunnerBuilder;
+/// let mut nr = NightRunnerBuilder::new()
+///     .with_path_for_config("fixtures/")
+///     .with_seed(42)
+///     .build();
+/// nr.parse_input("south").unwrap();
+/// let json = nr.transcript().to_json();
+///
+/// // replaying the session gets the game to the same state
+/// let transcript = nightrunner_lib::transcript::Transcript::from_json(&json).unwrap();
+/// let replayed = NightRunnerBuilder::new()
+///     .with_path_for_config("fixtures/")
+///     .replay(&transcript);
+/// assert_eq!(replayed.transcript(), nr.transcript());
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct Transcript {
+    /// The seed the game was started with.
+    pub seed: u64,
+    /// Everything the player did, in order.
+    pub entries: Vec<TranscriptEntry>,
+}
+
+/// A single thing the player did, and what came of it.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct TranscriptEntry {
+    /// The turn the game was in when the player did it.
+    pub turn: u32,
+    /// What the player did.
+    pub command: Command,
+    /// What the game returned.
+    pub outcome: Outcome,
+}
+
+/// Something the player did in the game.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Command {
+    /// The player typed a command.
+    Input {
+        /// What the player typed.
+        text: String,
+    },
+    /// The game was rewound with `NightRunner::rewind_state`.
+    Rewind,
+    /// The game was fast forwarded with
+    /// `NightRunner::fast_forward_state`.
+    FastForward,
+    /// The game was started over with `NightRunner::reset`.
+    Reset {
+        /// Whether the rewind history was kept.
+        keep_history: bool,
+    },
+}
+
+/// What the game returned for a command.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", content = "data", rename_all = "snake_case")]
+pub enum Outcome {
+    /// The command succeeded with this result.
+    Result(ParsingResult),
+    /// The command failed with this error message.
+    Error(String),
+}
+
+impl Outcome {
+    /// Returns the text shown to the player for the outcome,
+    /// which is the error message for errors.
+    pub fn text(&self) -> String {
+        match self {
+            Outcome::Result(ParsingResult::EventSuccess(event_message)) => {
+                event_message.message.clone()
+            }
+            Outcome::Result(parsing_result) => parsing_result.to_string(),
+            Outcome::Error(error) => error.clone(),
+        }
+    }
+}
+
+impl Transcript {
+    /// Creates an empty transcript for a game started
+    /// with the given seed.
+    pub fn new(seed: u64) -> Transcript {
+        Transcript {
+            seed,
+            entries: vec![],
+        }
+    }
+    /// Adds a command and its result to the transcript.
+    pub fn record(&mut self, turn: u32, command: Command, result: &NRResult<ParsingResult>) {
+        let outcome = match result {
+            Ok(parsing_result) => Outcome::Result(parsing_result.clone()),
+            Err(error) => Outcome::Error(error.to_string()),
+        };
+        self.entries.push(TranscriptEntry {
+            turn,
+            command,
+            outcome,
+        });
+    }
+    /// Returns the transcript as JSON.
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).unwrap()
+    }
+    /// Reads a transcript exported with `Transcript::to_json`.
+    pub fn from_json(data: &str) -> NRResult<Transcript> {
+        Ok(serde_json::from_str(data)?)
+    }
+    /// Returns the transcript as plain text, the way the
+    /// session looked to the player.
+    pub fn to_text(&self) -> String {
+        let mut text = format!("Seed: {}\n", self.seed);
+        for entry in &self.entries {
+            let command = match &entry.command {
+                Command::Input { text } => format!("> {}", text),
+                Command::Rewind => "(rewind)".to_string(),
+                Command::FastForward => "(fast forward)".to_string(),
+                Command::Reset { keep_history: _ } => "(reset)".to_string(),
+            };
+            let outcome = match &entry.outcome {
+                Outcome::Result(_) => entry.outcome.text(),
+                Outcome::Error(error) => format!("Error: {}", error),
+            };
+            text.push_str(&format!(
+                "\n[turn {}] {}\n{}\n",
+                entry.turn, command, outcome
+            ));
+        }
+        text
+    }
+}
+
+#[cfg(test)]
+#[path = "transcript_tests.rs"]
+mod transcript_tests;
@@ -0,0 +1,111 @@
+use super::*;
+use crate::NightRunnerBuilder;
+#[cfg(test)]
+use pretty_assertions::assert_eq;
+
+fn played_game() -> crate::NightRunner {
+    let mut nr = NightRunnerBuilder::new()
+        .with_path_for_config("fixtures/")
+        .with_seed(7)
+        .build();
+    nr.parse_input("look").unwrap();
+    nr.parse_input("south").unwrap();
+    nr.parse_input("fly").ok();
+    nr.rewind_state().unwrap();
+    nr.fast_forward_state().unwrap();
+    nr.reset(true).unwrap();
+    nr
+}
+
+#[test]
+fn it_records_every_command_with_its_turn_and_outcome() {
+    let nr = played_game();
+    let transcript = nr.transcript();
+    assert_eq!(transcript.seed, 7);
+    let commands: Vec<&Command> = transcript.entries.iter().map(|e| &e.command).collect();
+    assert_eq!(
+        commands,
+        vec![
+            &Command::Input {
+                text: "look".to_string()
+            },
+            &Command::Input {
+                text: "south".to_string()
+            },
+            &Command::Input {
+                text: "fly".to_string()
+            },
+            &Command::Rewind,
+            &Command::FastForward,
+            &Command::Reset { keep_history: true },
+        ]
+    );
+    let turns: Vec<u32> = transcript.entries.iter().map(|e| e.turn).collect();
+    assert_eq!(turns, vec![0, 1, 2, 2, 1, 2]);
+    assert!(matches!(
+        transcript.entries[0].outcome,
+        Outcome::Result(ParsingResult::Look(_))
+    ));
+    assert!(matches!(transcript.entries[2].outcome, Outcome::Error(_)));
+    assert!(matches!(
+        transcript.entries[5].outcome,
+        Outcome::Result(ParsingResult::Restart(_))
+    ));
+}
+
+#[test]
+fn it_exports_and_imports_json() {
+    let nr = played_game();
+    let json = nr.transcript().to_json();
+    assert_eq!(&Transcript::from_json(&json).unwrap(), nr.transcript());
+    assert!(Transcript::from_json("not a transcript").is_err());
+}
+
+#[test]
+fn it_exports_plain_text() {
+    let mut transcript = Transcript::new(1);
+    transcript.record(
+        0,
+        Command::Input {
+            text: "look".to_string(),
+        },
+        &Ok(ParsingResult::Look("a room".to_string())),
+    );
+    transcript.record(
+        1,
+        Command::Input {
+            text: "fly".to_string(),
+        },
+        &Err(crate::parser::errors::InvalidVerb.into()),
+    );
+    transcript.record(1, Command::Rewind, &Ok(ParsingResult::Quit));
+    assert_eq!(
+        transcript.to_text(),
+        format!(
+            "Seed: 1\n\n[turn 0] > look\na room\n\n[turn 1] > fly\nError: {}\n\n[turn 1] (rewind)\n{}\n",
+            crate::parser::errors::InvalidVerb,
+            ParsingResult::Quit
+        )
+    );
+}
+
+#[test]
+fn it_replays_a_session_to_the_same_state() {
+    let nr = played_game();
+    let transcript = Transcript::from_json(&nr.transcript().to_json()).unwrap();
+    let replayed = NightRunnerBuilder::new()
+        .with_path_for_config("fixtures/")
+        .replay(&transcript);
+    // some error messages are picked at random when shown,
+    // so only the commands and the game they led to are compared
+    let commands = |transcript: &Transcript| -> Vec<(u32, Command)> {
+        transcript
+            .entries
+            .iter()
+            .map(|entry| (entry.turn, entry.command.clone()))
+            .collect()
+    };
+    assert_eq!(commands(replayed.transcript()), commands(nr.transcript()));
+    assert_eq!(replayed.state, nr.state);
+    assert_eq!(replayed.history, nr.history);
+}
@@ -132,7 +132,7 @@ pub fn player_receive_item(state: &State, item: Item) -> NRResult<(State, String
 pub fn player_remove_item(state: &State, item: Item) -> NRResult<(State, String)> {
     let mut new_state = state.clone();
     let player = &mut new_state.player;
-    let old_item = player.inventory.remove_item(item)?;
+    let old_item = player.remove_item(item)?;
     Ok((
         new_state,
         format!("\nYou no longer have a {}\n", old_item.name),
@@ -141,7 +141,6 @@ pub fn player_remove_item(state: &State, item: Item) -> NRResult<(State, String)
 
 #[derive(Debug, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
-
 /// Represents a successful movement.
 pub struct MoveSuccess;
 
@@ -263,9 +262,8 @@ pub fn parse_room_text(
         .iter()
         .map(|item| item.name.clone())
         .collect::<Vec<_>>();
-    let room_subjects = current_room
-        .subjects
-        .clone()
+    let room_subjects = state
+        .subjects_in_room(current_room.id)
         .iter()
         .map(|subject| subject.name.clone())
         .collect::<Vec<_>>();
@@ -319,10 +317,10 @@ pub fn parse_room_text(
     let (event_text, templated_words_event) =
         process_templated_text(event_message, &items_and_subjects);
     let mut message_parts = HashMap::new();
-    message_parts.insert(MessageParts::RoomText, room_text.clone());
-    message_parts.insert(MessageParts::Exits, exits_string.clone());
-    message_parts.insert(MessageParts::EventText, event_text.clone());
-    let message = room_text + "\n" + event_text.as_str() + "\n\n" + exits_string.as_str();
+    message_parts.insert(MessageParts::RoomText, room_text);
+    message_parts.insert(MessageParts::Exits, exits_string);
+    message_parts.insert(MessageParts::EventText, event_text);
+    let message = compose_message(&message_parts);
     let mut templated_words = templated_words_room
         .iter()
         .chain(templated_words_event.iter())
@@ -337,6 +335,107 @@ pub fn parse_room_text(
     })
 }
 
+/// Joins the parts of an event message into the single
+/// string used for `EventMessage.message`.
+fn compose_message(message_parts: &HashMap<MessageParts, String>) -> String {
+    let part = |message_part: MessageParts| {
+        message_parts
+            .get(&message_part)
+            .cloned()
+            .unwrap_or_default()
+    };
+    let daemon_text = part(MessageParts::DaemonText);
+    let daemon_text = if daemon_text.is_empty() {
+        daemon_text
+    } else {
+        format!("\n\n{}", daemon_text)
+    };
+    part(MessageParts::RoomText)
+        + "\n"
+        + part(MessageParts::EventText).as_str()
+        + daemon_text.as_str()
+        + "\n\n"
+        + part(MessageParts::Exits).as_str()
+}
+
+/// Appends text generated outside of the player's command,
+/// such as subjects arriving in the room, to the result of
+/// that command.
+///
+/// For `ParsingResult::EventSuccess` the text is added to the
+/// given message part, and for results carrying a plain string
+/// it is added to the end of the string.
+pub(crate) fn append_to_result(
+    result: ParsingResult,
+    message_part: MessageParts,
+    text: &str,
+) -> ParsingResult {
+    if text.is_empty() {
+        return result;
+    }
+    match result {
+        ParsingResult::EventSuccess(event_message) => {
+            ParsingResult::EventSuccess(append_to_message(event_message, message_part, text))
+        }
+        ParsingResult::Restart(event_message) => {
+            ParsingResult::Restart(append_to_message(event_message, message_part, text))
+        }
+        ParsingResult::Undo(event_message) => {
+            ParsingResult::Undo(append_to_message(event_message, message_part, text))
+        }
+        ParsingResult::Redo(event_message) => {
+            ParsingResult::Redo(append_to_message(event_message, message_part, text))
+        }
+        ParsingResult::Help(msg) => ParsingResult::Help(format!("{}\n\n{}", msg, text)),
+        ParsingResult::Look(msg) => ParsingResult::Look(format!("{}\n\n{}", msg, text)),
+        ParsingResult::NewItem(msg) => ParsingResult::NewItem(format!("{}\n{}", msg, text)),
+        ParsingResult::DropItem(msg) => ParsingResult::DropItem(format!("{}\n{}", msg, text)),
+        ParsingResult::Inventory(msg) => ParsingResult::Inventory(format!("{}\n\n{}", msg, text)),
+        ParsingResult::SubjectNoEvent(msg) => {
+            ParsingResult::SubjectNoEvent(format!("{}\n\n{}", msg, text))
+        }
+        ParsingResult::ItemUsed(msg) => ParsingResult::ItemUsed(format!("{}\n\n{}", msg, text)),
+        ParsingResult::Give(msg) => ParsingResult::Give(format!("{}\n\n{}", msg, text)),
+        ParsingResult::Dialogue(mut dialogue_message) => {
+            if dialogue_message.text.is_empty() {
+                dialogue_message.text = text.to_string();
+            } else {
+                dialogue_message.text = format!("{}\n\n{}", dialogue_message.text, text);
+            }
+            ParsingResult::Dialogue(dialogue_message)
+        }
+        ParsingResult::Score(msg) => ParsingResult::Score(format!("{}\n\n{}", msg, text)),
+        ParsingResult::GameOver { won, text: msg } => ParsingResult::GameOver {
+            won,
+            text: format!("{}\n\n{}", msg, text),
+        },
+        ParsingResult::Quit => ParsingResult::Quit,
+    }
+}
+
+fn append_to_message(
+    mut event_message: EventMessage,
+    message_part: MessageParts,
+    text: &str,
+) -> EventMessage {
+    let part_text = event_message.message_parts.entry(message_part).or_default();
+    if part_text.is_empty() {
+        part_text.push_str(text);
+    } else {
+        part_text.push_str(&format!("\n\n{}", text));
+    }
+    event_message.message = compose_message(&event_message.message_parts);
+    event_message
+}
+
+/// Returns the text for the room the player is in, with
+/// its current narrative, as it's shown when the player
+/// arrives there.
+pub fn current_room_text(state: &State) -> NRResult<EventMessage> {
+    let narrative_text = state.get_narrative().text;
+    parse_room_text(state, narrative_text, "".to_string(), None)
+}
+
 fn process_templated_text(text: String, items_and_subjects: &[String]) -> (String, Vec<String>) {
     let mut templated_words: Vec<String> = Vec::new();
     let processed_text = text
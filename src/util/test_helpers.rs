@@ -1,10 +1,13 @@
+use std::collections::BTreeMap;
+
 use crate::config::{
     determiners::AllowedDeterminers,
     directions::{AllowedDirections, Directions},
     movements::AllowedMovements,
     prepositions::AllowedPrepositions,
     rooms::{Exits, RoomBlueprint},
-    Config, Event, Item, Narrative, State, Subject, Verb, VerbFunction,
+    Config, Event, Item, ItemCapabilities, Narrative, PlayerStats, Settings, State, Subject, Verb,
+    VerbFunction,
 };
 
 /// Returns a sample Config that can be used during testing.
@@ -15,6 +18,11 @@ pub fn mock_config() -> Config {
         allowed_directions: AllowedDirections::init(),
         allowed_movements: AllowedMovements::init(),
         intro: "The introduction text to be displayed at the begining of the game.".to_string(),
+        settings: Settings::default(),
+        daemons: vec![],
+        variables: BTreeMap::new(),
+        stats: PlayerStats::default(),
+        migrations: vec![],
         allowed_verbs: vec![
             Verb {
                 id: 1,
@@ -70,6 +78,16 @@ pub fn mock_config() -> Config {
                 names: vec![String::from("hug")],
                 verb_function: VerbFunction::Normal,
             },
+            Verb {
+                id: 10,
+                names: vec![String::from("undo")],
+                verb_function: VerbFunction::Undo,
+            },
+            Verb {
+                id: 11,
+                names: vec![String::from("redo")],
+                verb_function: VerbFunction::Redo,
+            },
         ],
         items: vec![
             Item {
@@ -77,18 +95,21 @@ pub fn mock_config() -> Config {
                 name: String::from("item1"),
                 description: String::from("item 1 description"),
                 can_pick: false,
+                capabilities: ItemCapabilities::default(),
             },
             Item {
                 id: 2,
                 name: String::from("item2"),
                 description: String::from("item 2 description"),
                 can_pick: true,
+                capabilities: ItemCapabilities::default(),
             },
             Item {
                 id: 3,
                 name: String::from("item3"),
                 description: String::from("item 3 description"),
                 can_pick: true,
+                capabilities: ItemCapabilities::default(),
             }
         ],
         narratives: vec![
@@ -166,7 +187,13 @@ pub fn mock_config() -> Config {
                 add_subject: None,
                 remove_subject: false,
                 move_subject_to_location: None,
+                move_subjects: vec![],
+                at_turn: None,
+                turns_after: None,
+                effects: vec![],
                 narrative_after: None,
+                narrative_after_room: None,
+                narrative_changes: vec![],
             },
             Event {
                 id: 2,
@@ -186,7 +213,13 @@ pub fn mock_config() -> Config {
                 add_subject: None,
                 remove_subject: false,
                 move_subject_to_location: None,
+                move_subjects: vec![],
+                at_turn: None,
+                turns_after: None,
+                effects: vec![],
                 narrative_after: None,
+                narrative_after_room: None,
+                narrative_changes: vec![],
             },
             Event {
                 id: 3,
@@ -206,7 +239,13 @@ pub fn mock_config() -> Config {
                 add_subject: None,
                 remove_subject: false,
                 move_subject_to_location: None,
+                move_subjects: vec![],
+                at_turn: None,
+                turns_after: None,
+                effects: vec![],
                 narrative_after: None,
+                narrative_after_room: None,
+                narrative_changes: vec![],
             },
             Event {
                 id: 4,
@@ -226,7 +265,13 @@ pub fn mock_config() -> Config {
                 add_subject: None,
                 remove_subject: false,
                 move_subject_to_location: None,
+                move_subjects: vec![],
+                at_turn: None,
+                turns_after: None,
+                effects: vec![],
                 narrative_after: None,
+                narrative_after_room: None,
+                narrative_changes: vec![],
             },
             Event {
                 id: 5,
@@ -246,7 +291,13 @@ pub fn mock_config() -> Config {
                 add_subject: None,
                 remove_subject: false,
                 move_subject_to_location: None,
+                move_subjects: vec![],
+                at_turn: None,
+                turns_after: None,
+                effects: vec![],
                 narrative_after: None,
+                narrative_after_room: None,
+                narrative_changes: vec![],
             },
             Event {
                 id: 6,
@@ -266,7 +317,13 @@ pub fn mock_config() -> Config {
                 add_subject: None,
                 remove_subject: false,
                 move_subject_to_location: None,
+                move_subjects: vec![],
+                at_turn: None,
+                turns_after: None,
+                effects: vec![],
                 narrative_after: None,
+                narrative_after_room: None,
+                narrative_changes: vec![],
             }
         ],
 
@@ -276,20 +333,39 @@ pub fn mock_config() -> Config {
                 name: String::from("subject1"),
                 description: String::from("a subject description"),
                 default_text: String::from("default text"),
+                behavior: None,
+                dialogue: None,
+                topics: vec![],
+                unknown_topic_text: None,
+                accepts: vec![],
+                refusal_text: None,
+                disposition: 0,
+                flags: vec![],
+                disposition_texts: vec![],
+                item_ids: vec![],
             },
             Subject {
                 id: 2,
                 name: String::from("subject2"),
                 description: String::from("subject2 description"),
                 default_text: String::from("default text"),
+                behavior: None,
+                dialogue: None,
+                topics: vec![],
+                unknown_topic_text: None,
+                accepts: vec![],
+                refusal_text: None,
+                disposition: 0,
+                flags: vec![],
+                disposition_texts: vec![],
+                item_ids: vec![],
             }
         ],
     }
 }
 /// function to create sample JSON data for testing
 pub fn mock_json_data() -> String {
-    let data = mock_config();
-    serde_json::to_string(&data).unwrap()
+    mock_config().to_json()
 }
 
 /// export json data to a file
@@ -5,7 +5,7 @@ use regex::Regex;
 use self::test_helpers::export_json_data;
 
 use super::*;
-use crate::config::{directions::Directions, Config, State};
+use crate::config::{directions::Directions, Config, ItemCapabilities, State};
 #[cfg(test)]
 use pretty_assertions::assert_eq;
 
@@ -19,6 +19,7 @@ fn player_takes_item() {
         name: "not in room".to_string(),
         description: "not in room".to_string(),
         can_pick: true,
+        capabilities: ItemCapabilities::default(),
     };
     let result1 = player_get_item(&state, item);
     let result2 = player_get_item(&state, item_not_in_room);
@@ -38,6 +39,7 @@ fn player_receives_item() {
         name: "not in room".to_string(),
         description: "not in room".to_string(),
         can_pick: true,
+        capabilities: ItemCapabilities::default(),
     };
     let result1 = player_receive_item(&state, item);
     let result2 = player_receive_item(&state, item_not_in_room);
@@ -55,6 +57,7 @@ fn it_removes_player_item() {
         name: "not in room".to_string(),
         description: "not in room".to_string(),
         can_pick: true,
+        capabilities: ItemCapabilities::default(),
     };
     let result1 = player_remove_item(&new_state, item);
     let result2 = player_remove_item(&new_state, item_not_with_player);
@@ -0,0 +1,186 @@
+#![cfg(feature = "cli")]
+use std::io::Write;
+use std::process::{Command, Output, Stdio};
+
+fn nightrunner(args: &[&str], input: &str) -> Output {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_nightrunner"))
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(input.as_bytes())
+        .unwrap();
+    child.wait_with_output().unwrap()
+}
+
+fn stdout(output: &Output) -> String {
+    String::from_utf8_lossy(&output.stdout).to_string()
+}
+
+#[test]
+fn it_plays_a_game_from_stdin() {
+    let output = nightrunner(
+        &["play", "fixtures", "--seed", "1"],
+        "take item2\nsouth\nquit\n",
+    );
+    assert!(output.status.success());
+    let text = stdout(&output);
+    assert!(text.starts_with("The introduction text"));
+    assert!(text.contains("You now have a item2"));
+    assert!(text.contains("second room"));
+}
+
+#[test]
+fn it_validates_games() {
+    let output = nightrunner(&["validate", "fixtures/"], "");
+    assert!(output.status.success());
+    assert!(stdout(&output).contains("warning: narratives.yml:14: narrative 5"));
+    assert!(stdout(&output).contains("no problems found"));
+}
+
+#[test]
+fn it_runs_walkthroughs() {
+    let output = nightrunner(&["walkthrough", "tests/walkthroughs/fixtures.yml"], "");
+    assert!(output.status.success());
+    assert_eq!(stdout(&output), "tests/walkthroughs/fixtures.yml: ok\n");
+}
+
+#[test]
+fn it_exports_games_to_json() {
+    let output = nightrunner(&["export", "fixtures"], "");
+    assert!(output.status.success());
+    let json = stdout(&output);
+    let exported = nightrunner_lib::config::Config::from_json(&json);
+    assert_eq!(
+        exported,
+        nightrunner_lib::config::Config::from_path("fixtures/")
+    );
+}
+
+#[test]
+fn it_exports_games_to_yaml() {
+    let folder = std::env::temp_dir().join(format!("nightrunner_cli_{}", std::process::id()));
+    let yaml_file = folder.join("game.yml");
+    let yaml_folder = format!("{}/", folder.join("game").to_string_lossy());
+    let yaml_file = yaml_file.to_string_lossy().to_string();
+    std::fs::create_dir_all(&folder).unwrap();
+    assert!(
+        nightrunner(&["export", "fixtures", "--output", &yaml_file], "")
+            .status
+            .success()
+    );
+    assert!(
+        nightrunner(&["export", &yaml_file, "--output", &yaml_folder], "")
+            .status
+            .success()
+    );
+    assert_eq!(
+        nightrunner_lib::config::Config::from_path(&yaml_folder),
+        nightrunner_lib::config::Config::from_path("fixtures/")
+    );
+    std::fs::remove_dir_all(&folder).unwrap();
+}
+
+#[test]
+fn it_validates_and_exports_single_file_games() {
+    let output = nightrunner(&["validate", "fixtures/single_file/game.yml"], "");
+    assert!(output.status.success());
+    assert!(stdout(&output).contains("no problems found"));
+    let folder = std::env::temp_dir().join(format!("nightrunner_cli_toml_{}", std::process::id()));
+    let toml_file = folder.join("game.toml").to_string_lossy().to_string();
+    std::fs::create_dir_all(&folder).unwrap();
+    assert!(nightrunner(
+        &[
+            "export",
+            "fixtures/single_file/game.yml",
+            "--output",
+            &toml_file
+        ],
+        ""
+    )
+    .status
+    .success());
+    assert_eq!(
+        nightrunner_lib::config::Config::from_file(&toml_file).unwrap(),
+        nightrunner_lib::config::Config::from_path("fixtures/")
+    );
+    std::fs::remove_dir_all(&folder).unwrap();
+}
+
+#[test]
+fn it_warns_about_migrated_games() {
+    let folder = std::env::temp_dir().join(format!("nightrunner_cli_old_{}", std::process::id()));
+    std::fs::create_dir_all(&folder).unwrap();
+    let mut data: serde_json::Value =
+        serde_json::from_str(&nightrunner_lib::config::Config::from_path("fixtures/").to_json())
+            .unwrap();
+    data.as_object_mut().unwrap().remove("format_version");
+    data["room_blueprints"][1]["room_events"] = serde_json::json!([5]);
+    let game = folder.join("game.json").to_string_lossy().to_string();
+    std::fs::write(&game, data.to_string()).unwrap();
+    let output = nightrunner(&["validate", &game], "");
+    assert!(output.status.success());
+    assert!(stdout(&output).contains("warning: format version 2: removed room_events from room 2"));
+    std::fs::remove_dir_all(&folder).unwrap();
+}
+
+#[test]
+fn it_writes_json_schemas() {
+    let output = nightrunner(&["schema"], "");
+    assert!(output.status.success());
+    let schema: serde_json::Value = serde_json::from_str(&stdout(&output)).unwrap();
+    assert_eq!(schema, nightrunner_lib::config::Config::json_schema());
+    let folder =
+        std::env::temp_dir().join(format!("nightrunner_cli_schema_{}", std::process::id()));
+    let output = folder.to_string_lossy().to_string();
+    assert!(nightrunner(&["schema", "--output", &output], "")
+        .status
+        .success());
+    let rooms: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(folder.join("rooms.schema.json")).unwrap())
+            .unwrap();
+    assert_eq!(rooms["title"], "rooms.yml");
+    assert!(folder.join("game.schema.json").is_file());
+    assert!(folder.join("format_version.schema.json").is_file());
+    std::fs::remove_dir_all(&folder).unwrap();
+}
+
+#[test]
+fn it_fails_on_unknown_commands_and_games() {
+    assert!(!nightrunner(&["dance"], "").status.success());
+    let output = nightrunner(&["play", "no/such/game"], "");
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("could not find a game"));
+}
+
+#[test]
+fn it_reports_broken_game_folders() {
+    let folder =
+        std::env::temp_dir().join(format!("nightrunner_cli_broken_{}", std::process::id()));
+    std::fs::create_dir_all(&folder).unwrap();
+    for file in std::fs::read_dir("fixtures/").unwrap() {
+        let path = file.unwrap().path();
+        if path.is_file() && !path.ends_with("items.yml") {
+            std::fs::copy(&path, folder.join(path.file_name().unwrap())).unwrap();
+        }
+    }
+    let game = folder.to_string_lossy().to_string();
+    for command in ["play", "validate"] {
+        let output = nightrunner(&[command, &game], "");
+        assert_eq!(output.status.code(), Some(1));
+        assert!(String::from_utf8_lossy(&output.stderr)
+            .contains("error: One of the required game files is missing."));
+    }
+    std::fs::copy("fixtures/items.yml", folder.join("items.yml")).unwrap();
+    std::fs::write(folder.join("format_version.yml"), "99").unwrap();
+    let output = nightrunner(&["validate", &game], "");
+    assert_eq!(output.status.code(), Some(1));
+    assert!(String::from_utf8_lossy(&output.stderr).starts_with("error: "));
+    std::fs::remove_dir_all(&folder).unwrap();
+}
@@ -112,3 +112,108 @@ fn it_works_with_json_data() {
         r#"{"messageType":"look","data":"first room\n\nHere you see: \nan item1\nan item2\nsubject1"}"#
     );
 }
+#[test]
+#[cfg(not(target_arch = "wasm32"))]
+fn it_resets_the_game() {
+    let mut nr = NightRunnerBuilder::new()
+        .with_path_for_config("fixtures/")
+        .with_seed(3)
+        .build();
+    let first_room = nr.parse_input("look").unwrap();
+    nr.parse_input("south").unwrap();
+
+    nr.reset(true).unwrap();
+    assert_eq!(nr.parse_input("look").unwrap(), first_room);
+    nr.rewind_state().unwrap();
+    assert!(
+        nr.rewind_state().is_ok(),
+        "the state before the reset should be kept"
+    );
+
+    nr.reset(false).unwrap();
+    assert!(nr.rewind_state().is_err());
+    assert!(nr.fast_forward_state().is_err());
+    assert_eq!(nr.parse_input("look").unwrap(), first_room);
+}
+#[test]
+#[cfg(not(target_arch = "wasm32"))]
+fn it_limits_the_undo_history() {
+    let mut nr = NightRunnerBuilder::new()
+        .with_path_for_config("fixtures/")
+        .with_history_limit(2)
+        .build();
+    for _ in 0..4 {
+        nr.parse_input("look").unwrap();
+    }
+    assert!(nr.rewind_state().is_ok());
+    assert!(nr.rewind_state().is_ok());
+    assert!(nr.rewind_state().is_err());
+
+    nr.set_history_limit(0);
+    nr.parse_input("look").unwrap();
+    assert!(nr.rewind_state().is_err());
+}
+#[test]
+#[cfg(not(target_arch = "wasm32"))]
+fn it_undoes_and_redoes_commands_with_verbs() {
+    let mut nr = NightRunnerBuilder::new()
+        .with_path_for_config("fixtures/")
+        .build();
+    let first_room = nr.first_room_text().unwrap();
+    nr.parse_input("south").unwrap();
+
+    assert_eq!(
+        nr.parse_input("undo").unwrap(),
+        ParsingResult::Undo(first_room.clone())
+    );
+    match nr.parse_input("redo").unwrap() {
+        ParsingResult::Redo(room_text) => assert!(room_text.message.contains("item3")),
+        result => panic!("expected Redo, got {:?}", result),
+    }
+
+    nr.parse_input("undo").unwrap();
+    nr.parse_input("look").unwrap();
+    assert_eq!(
+        nr.parse_input("redo").unwrap_err().to_string(),
+        "There is nothing to redo.",
+        "a new command should forget the undone commands"
+    );
+    assert_eq!(nr.rewind_state().unwrap(), ParsingResult::Undo(first_room));
+    assert_eq!(
+        nr.parse_input("undo").unwrap_err().to_string(),
+        "There is nothing to undo."
+    );
+}
+#[test]
+#[cfg(not(target_arch = "wasm32"))]
+fn it_replays_a_transcript() {
+    use nightrunner_lib::transcript::Transcript;
+    let mut nr = NightRunnerBuilder::new()
+        .with_path_for_config("fixtures/")
+        .build();
+    nr.parse_input("south").unwrap();
+    nr.parse_input("look").unwrap();
+    let text = nr.transcript().to_text();
+    assert!(text.contains("[turn 0] > south"));
+
+    let transcript = Transcript::from_json(&nr.transcript().to_json()).unwrap();
+    let mut replayed = NightRunnerBuilder::new()
+        .with_path_for_config("fixtures/")
+        .replay(&transcript);
+    assert_eq!(replayed.transcript(), nr.transcript());
+    assert_eq!(
+        replayed.parse_input("look").unwrap(),
+        nr.parse_input("look").unwrap()
+    );
+}
+#[test]
+#[cfg(not(target_arch = "wasm32"))]
+fn it_can_move_a_game_to_another_thread() {
+    let mut nr = NightRunnerBuilder::new()
+        .with_path_for_config("fixtures/")
+        .build();
+    let result = std::thread::spawn(move || nr.parse_input("look").unwrap())
+        .join()
+        .unwrap();
+    assert!(matches!(result, ParsingResult::Look(_)));
+}
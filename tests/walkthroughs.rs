@@ -0,0 +1,20 @@
+#[cfg(not(target_arch = "wasm32"))]
+use nightrunner_lib::testing::Walkthrough;
+
+#[test]
+#[cfg(not(target_arch = "wasm32"))]
+fn it_plays_every_walkthrough_script() {
+    let mut scripts: Vec<String> = std::fs::read_dir("tests/walkthroughs")
+        .unwrap()
+        .map(|entry| entry.unwrap().path().to_string_lossy().to_string())
+        .filter(|path| path.ends_with(".yml") || path.ends_with(".json"))
+        .collect();
+    scripts.sort();
+    assert!(!scripts.is_empty());
+    for script in scripts {
+        let walkthrough = Walkthrough::from_file(&script).unwrap();
+        if let Err(failure) = walkthrough.run_game() {
+            panic!("{}: {}", script, failure);
+        }
+    }
+}